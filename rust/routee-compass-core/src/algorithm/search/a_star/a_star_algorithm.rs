@@ -1,34 +1,133 @@
+use crate::algorithm::search::landmark::LandmarkHeuristic;
+use crate::algorithm::search::resource_constrained::ResourceConstraint;
 use crate::algorithm::search::Direction;
 use crate::algorithm::search::EdgeTraversal;
+use crate::algorithm::search::PartialSearchResult;
 use crate::algorithm::search::SearchError;
 use crate::algorithm::search::SearchInstance;
 use crate::algorithm::search::SearchResult;
 use crate::algorithm::search::SearchTreeBranch;
 use crate::model::network::edge_id::EdgeId;
 use crate::model::network::vertex_id::VertexId;
+use crate::model::state::StateVariable;
+use crate::model::termination::TerminationModelError;
 use crate::model::unit::AsF64;
 use crate::model::unit::Cost;
 use crate::model::unit::ReverseCost;
 use crate::util::priority_queue::InternalPriorityQueue;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 /// run an A* Search over the given directed graph model. traverses links
 /// from the source, via the provided direction, to the target. uses the
 /// provided traversal model for state updates and link costs. estimates
 /// the distance to the destination (the a* heuristic) using the provided
-/// cost estimate function.
+/// cost estimate function. if landmarks are provided and compatible with
+/// this search instance, the estimate is tightened with the landmark bound.
+/// edges whose resulting state exceeds the budget are not traversed, so a search
+/// without a target returns the tree of vertices reachable within the budget.
+#[allow(clippy::too_many_arguments)]
 pub fn run_vertex_oriented(
     source: VertexId,
     target: Option<VertexId>,
     direction: &Direction,
     weight_factor: Option<Cost>,
+    landmarks: Option<&LandmarkHeuristic>,
+    budget: &[ResourceConstraint],
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    run_vertex_oriented_since(
+        source,
+        target,
+        direction,
+        weight_factor,
+        landmarks,
+        budget,
+        &Instant::now(),
+        0,
+        si,
+    )
+}
+
+/// runs a vertex-oriented a* search that is part of a larger search which began at
+/// `start_time` and has already performed `prior_iterations`. the termination model
+/// is tested against the runtime and iterations of the larger search.
+#[allow(clippy::too_many_arguments)]
+pub fn run_vertex_oriented_since(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    weight_factor: Option<Cost>,
+    landmarks: Option<&LandmarkHeuristic>,
+    budget: &[ResourceConstraint],
+    start_time: &Instant,
+    prior_iterations: u64,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    search_vertex_oriented(
+        source,
+        target,
+        None,
+        direction,
+        weight_factor,
+        landmarks,
+        budget,
+        start_time,
+        prior_iterations,
+        si,
+    )
+}
+
+/// runs a forward search without a target from the source that stops as soon as every
+/// vertex in `targets` has been settled, or when no vertex within the budget remains.
+/// targets missing from the resulting tree are not reachable within the budget. like
+/// [`run_vertex_oriented_since`], the search is part of a larger search which began at
+/// `start_time` and has already performed `prior_iterations`.
+pub fn run_vertex_oriented_to_targets(
+    source: VertexId,
+    targets: &HashSet<VertexId>,
+    budget: &[ResourceConstraint],
+    start_time: &Instant,
+    prior_iterations: u64,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    search_vertex_oriented(
+        source,
+        None,
+        Some(targets),
+        &Direction::Forward,
+        None,
+        None,
+        budget,
+        start_time,
+        prior_iterations,
+        si,
+    )
+}
+
+/// the vertex-oriented a* search. when `settle_targets` is provided, the search stops
+/// once each of those vertices has been settled.
+#[allow(clippy::too_many_arguments)]
+fn search_vertex_oriented(
+    source: VertexId,
+    target: Option<VertexId>,
+    settle_targets: Option<&HashSet<VertexId>>,
+    direction: &Direction,
+    weight_factor: Option<Cost>,
+    landmarks: Option<&LandmarkHeuristic>,
+    budget: &[ResourceConstraint],
+    start_time: &Instant,
+    prior_iterations: u64,
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
     if target.map_or(false, |t| t == source) {
         return Ok(SearchResult::default());
     }
+    let landmarks = match landmarks {
+        Some(l) if target.is_some() && l.is_compatible(si)? => Some(l),
+        _ => None,
+    };
 
     // context for the search (graph, search functions, frontier priority queue)
     let mut costs: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
@@ -40,24 +139,43 @@ pub fn run_vertex_oriented(
     let initial_state = si.state_model.initial_state()?;
     let origin_cost = match target {
         None => Cost::ZERO,
-        Some(target) => {
-            let cost_est = si.estimate_traversal_cost(source, target, &initial_state)?;
-            Cost::new(cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64())
-        }
+        Some(target) => estimate_cost(
+            source,
+            target,
+            &initial_state,
+            direction,
+            weight_factor,
+            landmarks,
+            si,
+        )?,
     };
     costs.push(source, origin_cost.into());
 
-    let start_time = Instant::now();
     let mut iterations = 0;
+    let mut unsettled = settle_targets.cloned();
 
     loop {
-        si.termination_model
-            .test(&start_time, solution.len(), iterations)?;
+        if unsettled.as_ref().is_some_and(|u| u.is_empty()) {
+            break;
+        }
+        if let Err(e) =
+            si.termination_model
+                .test(start_time, solution.len(), prior_iterations + iterations)
+        {
+            return Err(terminated_search_error(
+                e, source, target, solution, iterations, direction, si,
+            ));
+        }
 
         let current_vertex_id = match advance_search(&mut costs, source, target)? {
             None => break,
             Some(id) => id,
         };
+        if let Some(u) = unsettled.as_mut() {
+            if u.remove(&current_vertex_id) && u.is_empty() {
+                break;
+            }
+        }
 
         let last_edge_id = get_last_traversed_edge_id(&current_vertex_id, &source, &solution)?;
         // let last_edge = match last_edge_id {
@@ -102,6 +220,9 @@ pub fn run_vertex_oriented(
             }
             let et =
                 direction.perform_edge_traversal(*edge_id, last_edge_id, &current_state, si)?;
+            if !ResourceConstraint::is_feasible(&et.result_state, budget, si)? {
+                continue;
+            }
             let current_gscore = traversal_costs
                 .get(&terminal_vertex_id)
                 .unwrap_or(&Cost::INFINITY)
@@ -123,11 +244,15 @@ pub fn run_vertex_oriented(
 
                 let dst_h_cost = match target {
                     None => Cost::ZERO,
-                    Some(target_v) => {
-                        let cost_est =
-                            si.estimate_traversal_cost(key_vertex_id, target_v, &current_state)?;
-                        Cost::new(cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64())
-                    }
+                    Some(target_v) => estimate_cost(
+                        key_vertex_id,
+                        target_v,
+                        &current_state,
+                        direction,
+                        weight_factor,
+                        landmarks,
+                        si,
+                    )?,
                 };
                 let f_score_value = tentative_gscore + dst_h_cost;
                 costs.push_increase(key_vertex_id, f_score_value.into());
@@ -189,6 +314,8 @@ pub fn run_edge_oriented(
     target: Option<EdgeId>,
     direction: &Direction,
     weight_factor: Option<Cost>,
+    landmarks: Option<&LandmarkHeuristic>,
+    budget: &[ResourceConstraint],
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
     // 1. guard against edge conditions (src==dst, src.dst_v == dst.src_v)
@@ -210,7 +337,15 @@ pub fn run_edge_oriented(
             let SearchResult {
                 mut tree,
                 iterations,
-            } = run_vertex_oriented(e1_dst, None, direction, weight_factor, si)?;
+            } = run_vertex_oriented(
+                e1_dst,
+                None,
+                direction,
+                weight_factor,
+                landmarks,
+                budget,
+                si,
+            )?;
             if !tree.contains_key(&e1_dst) {
                 tree.extend([(e1_dst, src_branch)]);
             }
@@ -255,7 +390,15 @@ pub fn run_edge_oriented(
                 let SearchResult {
                     mut tree,
                     iterations,
-                } = run_vertex_oriented(e1_dst, Some(e2_src), direction, weight_factor, si)?;
+                } = run_vertex_oriented(
+                    e1_dst,
+                    Some(e2_src),
+                    direction,
+                    weight_factor,
+                    landmarks,
+                    budget,
+                    si,
+                )?;
 
                 if tree.is_empty() {
                     return Err(SearchError::NoPathExistsBetweenVertices(e1_dst, e2_src));
@@ -302,6 +445,73 @@ pub fn run_edge_oriented(
     }
 }
 
+/// estimates the cost between a vertex and the search target (the a* heuristic),
+/// scaled by the weight factor. when landmarks are available, the larger of the
+/// traversal model estimate and the landmark bound is used. in a reverse search
+/// the path runs from the target to the vertex, so the landmark bound is reversed.
+pub(super) fn estimate_cost(
+    vertex_id: VertexId,
+    target: VertexId,
+    state: &[StateVariable],
+    direction: &Direction,
+    weight_factor: Option<Cost>,
+    landmarks: Option<&LandmarkHeuristic>,
+    si: &SearchInstance,
+) -> Result<Cost, SearchError> {
+    let model_est = si.estimate_traversal_cost(vertex_id, target, state)?;
+    let cost_est = match (landmarks, direction) {
+        (None, _) => model_est,
+        (Some(l), Direction::Forward) => model_est.max(l.estimate(vertex_id, target)),
+        (Some(l), Direction::Reverse) => model_est.max(l.estimate(target, vertex_id)),
+    };
+    Ok(Cost::new(
+        cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64(),
+    ))
+}
+
+/// builds the error for a search interrupted by the termination model. when the query
+/// was terminated, the error carries the partial search tree along with the vertex of
+/// the tree estimated to be closest to the target, so that callers may return the
+/// partial result instead of failing.
+fn terminated_search_error(
+    error: TerminationModelError,
+    source: VertexId,
+    target: Option<VertexId>,
+    tree: HashMap<VertexId, SearchTreeBranch>,
+    iterations: u64,
+    direction: &Direction,
+    si: &SearchInstance,
+) -> SearchError {
+    let explanation = match error {
+        TerminationModelError::QueryTerminated(explanation) => explanation,
+        other => return SearchError::TerminationModelFailure { source: other },
+    };
+    let closest_vertex = match target {
+        None => None,
+        Some(target) => {
+            let mut closest: Option<(Cost, VertexId)> = None;
+            for (vertex_id, branch) in tree.iter() {
+                let state = &branch.edge_traversal.result_state;
+                let estimate =
+                    match estimate_cost(*vertex_id, target, state, direction, None, None, si) {
+                        Ok(estimate) => estimate,
+                        Err(e) => return e,
+                    };
+                if closest.is_none_or(|(c, _)| estimate < c) {
+                    closest = Some((estimate, *vertex_id));
+                }
+            }
+            closest.map(|(_, vertex_id)| vertex_id)
+        }
+    };
+    SearchError::QueryTerminatedWithPartialResult(Box::new(PartialSearchResult {
+        explanation,
+        source,
+        closest_vertex,
+        result: SearchResult::new(tree, iterations),
+    }))
+}
+
 /// grab the current vertex id, but handle some other termination conditions
 /// based on the state of the priority queue and optional search destination
 /// - we reach the destination                                       (Ok)
@@ -375,36 +585,15 @@ fn get_last_traversed_edge_id(
 mod tests {
     use super::*;
     use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
     use crate::algorithm::search::MinSearchTree;
-    use crate::model::access::default::NoAccessModel;
-    use crate::model::cost::CostAggregation;
-    use crate::model::cost::CostModel;
-    use crate::model::cost::VehicleCostRate;
-    use crate::model::frontier::default::no_restriction::NoRestriction;
-
-    use crate::model::map::MapModel;
-    use crate::model::map::MapModelConfig;
     use crate::model::network::edge_id::EdgeId;
-    use crate::model::network::graph::Graph;
     use crate::model::network::Edge;
-    use crate::model::network::Vertex;
-    use crate::model::state::StateFeature;
-    use crate::model::state::StateModel;
     use crate::model::termination::TerminationModel;
-    use crate::model::traversal::default::DistanceTraversalModel;
-    use crate::model::unit::{Distance, DistanceUnit};
-    use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
     use rayon::prelude::*;
     use std::sync::Arc;
 
-    fn build_mock_graph() -> Graph {
-        let vertices = vec![
-            Vertex::new(0, 0.0, 0.0),
-            Vertex::new(1, 0.0, 0.0),
-            Vertex::new(2, 0.0, 0.0),
-            Vertex::new(3, 0.0, 0.0),
-        ];
-
+    fn build_mock_search_instance() -> SearchInstance {
         let edges = vec![
             Edge::new(0, 0, 1, 10.0),
             Edge::new(1, 1, 0, 10.0),
@@ -415,21 +604,10 @@ mod tests {
             Edge::new(6, 3, 0, 2.0),
             Edge::new(7, 0, 3, 2.0),
         ];
-
-        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
-        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
-
-        for edge in &edges {
-            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
-            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
-        }
-
-        Graph {
-            adj: adj.into_boxed_slice(),
-            rev: rev.into_boxed_slice(),
-            edges: edges.into_boxed_slice(),
-            vertices: vertices.into_boxed_slice(),
-        }
+        build_search_instance(
+            build_graph(build_vertices(4), edges),
+            TerminationModel::IterationsLimit { limit: 20 },
+        )
     }
 
     #[test]
@@ -479,51 +657,14 @@ mod tests {
             (VertexId(2), VertexId(3), vec![EdgeId(4)]), // 2 -[4]-> 3
         ];
 
-        let graph = Arc::new(build_mock_graph());
-        let map_model = Arc::new(MapModel::new(graph.clone(), MapModelConfig::default()).unwrap());
-
-        // setup the graph, traversal model, and a* heuristic to be shared across the queries in parallel
-        // these live in the "driver" process and are passed as read-only memory to each executor process
-        let state_model = Arc::new(
-            StateModel::empty()
-                .extend(vec![(
-                    String::from("distance"),
-                    StateFeature::Distance {
-                        distance_unit: DistanceUnit::Kilometers,
-                        initial: Distance::new(0.0),
-                    },
-                )])
-                .unwrap(),
-        );
-        let cost_model = CostModel::new(
-            // vec![(String::from("distance"), 0usize)],
-            Arc::new(HashMap::from([(String::from("distance"), 1.0)])),
-            Arc::new(HashMap::from([(
-                String::from("distance"),
-                VehicleCostRate::Raw,
-            )])),
-            Arc::new(HashMap::new()),
-            CostAggregation::Sum,
-            state_model.clone(),
-        )
-        .unwrap();
-        let si = SearchInstance {
-            graph,
-            map_model,
-            state_model: state_model.clone(),
-            traversal_model: Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
-            access_model: Arc::new(NoAccessModel {}),
-            cost_model: Arc::new(cost_model),
-            frontier_model: Arc::new(NoRestriction {}),
-            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 20 }),
-        };
+        let si = build_mock_search_instance();
 
         // execute the route search
         let result: Vec<Result<MinSearchTree, SearchError>> = queries
             .clone()
             .into_par_iter()
             .map(|(o, d, _expected)| {
-                run_vertex_oriented(o, Some(d), &Direction::Forward, None, &si)
+                run_vertex_oriented(o, Some(d), &Direction::Forward, None, None, &[], &si)
                     .map(|search_result| search_result.tree)
             })
             .collect();
@@ -540,4 +681,72 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_budgeted_one_to_all() {
+        // using the box world from test_e2e_queries, with a budget of 3 meters from
+        // vertex 0, only vertices 3 (2 meters) and 2 (3 meters) are reachable. the
+        // shortest path to vertex 1 is 5 meters.
+        let si = build_mock_search_instance();
+        let budget = vec![ResourceConstraint {
+            feature: String::from("distance"),
+            min: None,
+            max: Some(3.0),
+        }];
+        let result = run_vertex_oriented(
+            VertexId(0),
+            None,
+            &Direction::Forward,
+            None,
+            None,
+            &budget,
+            &si,
+        )
+        .unwrap();
+        let mut reached = result.tree.keys().cloned().collect::<Vec<_>>();
+        reached.sort();
+        assert_eq!(reached, vec![VertexId(2), VertexId(3)]);
+    }
+
+    #[test]
+    fn test_search_to_targets_stops_when_settled() {
+        // using the box world from test_e2e_queries, vertex 3 is settled right after
+        // the source, so vertex 2 is never reached
+        let si = build_mock_search_instance();
+        let targets = HashSet::from([VertexId(3)]);
+        let result =
+            run_vertex_oriented_to_targets(VertexId(0), &targets, &[], &Instant::now(), 0, &si)
+                .unwrap();
+        assert_eq!(result.iterations, 1);
+        assert!(result.tree.contains_key(&VertexId(3)));
+        assert!(!result.tree.contains_key(&VertexId(2)));
+    }
+
+    #[test]
+    fn test_terminated_search_returns_partial_tree() {
+        // a single iteration expands vertex 0, reaching vertices 1 and 3
+        let mut si = build_mock_search_instance();
+        si.termination_model = Arc::new(TerminationModel::IterationsLimit { limit: 1 });
+        let error = run_vertex_oriented(
+            VertexId(0),
+            Some(VertexId(2)),
+            &Direction::Forward,
+            None,
+            None,
+            &[],
+            &si,
+        )
+        .err()
+        .unwrap();
+        let partial = match error {
+            SearchError::QueryTerminatedWithPartialResult(partial) => partial,
+            other => panic!("expected partial result, found {}", other),
+        };
+        assert_eq!(partial.source, VertexId(0));
+        let mut reached = partial.result.tree.keys().cloned().collect::<Vec<_>>();
+        reached.sort();
+        assert_eq!(reached, vec![VertexId(1), VertexId(3)]);
+        let closest = partial.closest_vertex.unwrap();
+        assert!(partial.result.tree.contains_key(&closest));
+    }
 }
@@ -0,0 +1,214 @@
+use super::a_star_algorithm::run_vertex_oriented_since;
+use crate::algorithm::search::backtrack;
+use crate::algorithm::search::landmark::LandmarkHeuristic;
+use crate::algorithm::search::resource_constrained::ResourceConstraint;
+use crate::algorithm::search::Direction;
+use crate::algorithm::search::SearchError;
+use crate::algorithm::search::SearchInstance;
+use crate::algorithm::search::SearchTreeBranch;
+use crate::model::network::vertex_id::VertexId;
+use crate::model::unit::AsF64;
+use crate::model::unit::Cost;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// result of an anytime a* search.
+#[derive(Default)]
+pub struct AnytimeSearchResult {
+    /// tree of the search that found the least-cost route
+    pub tree: HashMap<VertexId, SearchTreeBranch>,
+    /// iterations across all completed searches
+    pub iterations: u64,
+    /// weight factor of the last completed search, which bounds the route cost
+    /// to at most this factor of the optimal cost. a bound of 1.0 is optimal.
+    pub suboptimality_bound: f64,
+}
+
+/// runs an anytime weighted a* search. the first search inflates the a* heuristic by
+/// `initial_weight_factor`, which quickly finds a route costing at most that factor of
+/// the optimal cost. each following search lowers the weight factor by
+/// `weight_factor_step` until a search with weight factor 1.0 proves the route optimal.
+///
+/// the termination model budget covers the runtime and iterations of all searches. when
+/// it interrupts a search after the first, the least-cost route found so far is returned
+/// with the weight factor of the last completed search as its suboptimality bound. the
+/// bound assumes the traversal model cost estimates are admissible.
+///
+/// # Arguments
+///
+/// * `source`                - search origin
+/// * `target`                - search destination
+/// * `direction`             - direction of the search
+/// * `initial_weight_factor` - weight factor of the first search, at least 1.0
+/// * `weight_factor_step`    - amount to lower the weight factor between searches
+/// * `landmarks`             - optional landmark heuristic
+/// * `budget`                - constraints on the state of every traversal
+/// * `si`                    - search instance
+///
+/// # Returns
+///
+/// The tree of the least-cost route found and its suboptimality bound
+#[allow(clippy::too_many_arguments)]
+pub fn run_vertex_oriented(
+    source: VertexId,
+    target: VertexId,
+    direction: &Direction,
+    initial_weight_factor: Cost,
+    weight_factor_step: Cost,
+    landmarks: Option<&LandmarkHeuristic>,
+    budget: &[ResourceConstraint],
+    si: &SearchInstance,
+) -> Result<AnytimeSearchResult, SearchError> {
+    if initial_weight_factor < Cost::ONE {
+        return Err(SearchError::BuildError(format!(
+            "anytime a* initial weight factor must be at least 1.0, found {}",
+            initial_weight_factor
+        )));
+    }
+    if weight_factor_step <= Cost::ZERO {
+        return Err(SearchError::BuildError(format!(
+            "anytime a* weight factor step must be positive, found {}",
+            weight_factor_step
+        )));
+    }
+    if source == target {
+        return Ok(AnytimeSearchResult {
+            suboptimality_bound: 1.0,
+            ..Default::default()
+        });
+    }
+
+    let start_time = Instant::now();
+    let mut best: Option<(Cost, AnytimeSearchResult)> = None;
+    let mut iterations = 0;
+    let mut weight_factor = initial_weight_factor;
+    loop {
+        let search_result = run_vertex_oriented_since(
+            source,
+            Some(target),
+            direction,
+            Some(weight_factor),
+            landmarks,
+            budget,
+            &start_time,
+            iterations,
+            si,
+        );
+        let result = match (search_result, best.take()) {
+            (Ok(result), incumbent) => {
+                best = incumbent;
+                result
+            }
+            (Err(SearchError::QueryTerminatedWithPartialResult(partial)), Some((_, incumbent))) => {
+                log::debug!(
+                    "anytime a* terminated with bound {} due to {}",
+                    incumbent.suboptimality_bound,
+                    partial.explanation
+                );
+                return Ok(incumbent);
+            }
+            (Err(e), _) => return Err(e),
+        };
+        iterations += result.iterations;
+
+        let route = backtrack::vertex_oriented_route(source, target, &result.tree)?;
+        let route_cost: Cost = route.iter().map(|e| e.total_cost()).sum();
+        best = match best.take() {
+            Some((best_cost, mut incumbent)) if best_cost <= route_cost => {
+                incumbent.suboptimality_bound = weight_factor.as_f64();
+                incumbent.iterations = iterations;
+                Some((best_cost, incumbent))
+            }
+            _ => Some((
+                route_cost,
+                AnytimeSearchResult {
+                    tree: result.tree,
+                    iterations,
+                    suboptimality_bound: weight_factor.as_f64(),
+                },
+            )),
+        };
+
+        if weight_factor <= Cost::ONE {
+            break;
+        }
+        weight_factor = (weight_factor - weight_factor_step).max(Cost::ONE);
+    }
+
+    best.map(|(_, result)| result)
+        .ok_or_else(|| SearchError::InternalError(String::from("anytime a* produced no route")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance};
+    use crate::model::network::edge_id::EdgeId;
+    use crate::model::network::{Edge, Vertex};
+    use crate::model::termination::TerminationModel;
+
+    /// a route 0 -> 1 -> 3 that heads straight toward the destination but detours
+    /// over a long edge, and a route 0 -> 2 -> 3 that heads away from the destination
+    /// and is shorter. an inflated heuristic prefers the first route.
+    fn build_mock_search_instance(termination_model: TerminationModel) -> SearchInstance {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.05, 0.0),
+            Vertex::new(2, -0.01, 0.0),
+            Vertex::new(3, 0.1, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 5600.0),
+            Edge::new(1, 1, 3, 8000.0),
+            Edge::new(2, 0, 2, 1200.0),
+            Edge::new(3, 2, 3, 12300.0),
+        ];
+        build_search_instance(build_graph(vertices, edges), termination_model)
+    }
+
+    fn route_edges(result: &AnytimeSearchResult) -> Vec<EdgeId> {
+        vertex_oriented_route(VertexId(0), VertexId(3), &result.tree)
+            .unwrap()
+            .iter()
+            .map(|e| e.edge_id)
+            .collect()
+    }
+
+    fn run(si: &SearchInstance) -> Result<AnytimeSearchResult, SearchError> {
+        run_vertex_oriented(
+            VertexId(0),
+            VertexId(3),
+            &Direction::Forward,
+            Cost::new(3.0),
+            Cost::new(1.0),
+            None,
+            &[],
+            si,
+        )
+    }
+
+    #[test]
+    fn test_refines_to_optimal() {
+        let si = build_mock_search_instance(TerminationModel::IterationsLimit { limit: 100 });
+        let result = run(&si).unwrap();
+        assert_eq!(route_edges(&result), vec![EdgeId(2), EdgeId(3)]);
+        assert_eq!(result.suboptimality_bound, 1.0);
+    }
+
+    #[test]
+    fn test_returns_bounded_route_on_termination() {
+        // the first search at weight 3.0 takes 2 iterations and finds the route
+        // through vertex 1. the second search exhausts the iteration limit.
+        let si = build_mock_search_instance(TerminationModel::IterationsLimit { limit: 3 });
+        let result = run(&si).unwrap();
+        assert_eq!(route_edges(&result), vec![EdgeId(0), EdgeId(1)]);
+        assert_eq!(result.suboptimality_bound, 3.0);
+    }
+
+    #[test]
+    fn test_fails_without_route() {
+        let si = build_mock_search_instance(TerminationModel::IterationsLimit { limit: 1 });
+        assert!(run(&si).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use super::bidirectional_ops;
+use crate::algorithm::search::backtrack;
+use crate::algorithm::search::Direction;
+use crate::algorithm::search::SearchAlgorithmResult;
+use crate::algorithm::search::SearchError;
+use crate::algorithm::search::SearchInstance;
+use crate::algorithm::search::SearchTreeBranch;
+use crate::model::network::edge_id::EdgeId;
+use crate::model::network::vertex_id::VertexId;
+use crate::model::state::StateVariable;
+use crate::model::unit::Cost;
+use crate::model::unit::ReverseCost;
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// run a bidirectional A* search between a source and a target vertex.
+///
+/// a forward search rooted at the source and a reverse search rooted at the target
+/// are expanded simultaneously, always advancing the side with the smaller frontier.
+/// whenever an edge relaxation reaches a vertex that has already been labeled by the
+/// opposite search, the sum of both costs is a candidate meeting cost.
+///
+/// the search stops once the smallest f-score in either frontier is no smaller than
+/// the best meeting cost found so far. for an admissible and consistent cost estimate,
+/// this proves no cheaper path remains to be discovered. the reverse half of the route
+/// is then re-traversed in the forward direction (see [`bidirectional_ops::reorient_reverse_route`])
+/// so that the state of the stitched route accumulates from the source.
+///
+/// # Arguments
+///
+/// * `source` - search origin vertex
+/// * `target` - search destination vertex
+/// * `si`     - the search assets for this query
+///
+/// # Returns
+///
+/// the forward and reverse search trees, in that order, along with the stitched route
+pub fn run_vertex_oriented(
+    source: VertexId,
+    target: VertexId,
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if source == target {
+        return Ok(SearchAlgorithmResult::default());
+    }
+
+    let initial_state = si.state_model.initial_state()?;
+    let mut fwd = SearchFrontier::new(source, target, Direction::Forward, &initial_state, si)?;
+    let mut rev = SearchFrontier::new(target, source, Direction::Reverse, &initial_state, si)?;
+    let mut best_meeting: Option<(VertexId, Cost)> = None;
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+
+    loop {
+        si.termination_model
+            .test(&start_time, fwd.tree.len() + rev.tree.len(), iterations)?;
+
+        let (fwd_min, rev_min) = match (fwd.min_f_score(), rev.min_f_score()) {
+            (Some(f), Some(r)) => (f, r),
+            // one side of the search has been exhausted. every vertex reachable from
+            // that root has been labeled, including the opposite root, so the best
+            // meeting found so far (if any) is optimal.
+            _ => break,
+        };
+        if let Some((_, best_cost)) = best_meeting {
+            if fwd_min >= best_cost || rev_min >= best_cost {
+                break;
+            }
+        }
+
+        if fwd.queue.len() <= rev.queue.len() {
+            fwd.expand(&rev, &initial_state, &mut best_meeting, si)?;
+        } else {
+            rev.expand(&fwd, &initial_state, &mut best_meeting, si)?;
+        }
+        iterations += 1;
+    }
+
+    log::debug!(
+        "bidirectional search iterations: {}, size of search trees: fwd {} rev {}",
+        iterations,
+        fwd.tree.len(),
+        rev.tree.len()
+    );
+
+    let (meeting_vertex_id, _) =
+        best_meeting.ok_or(SearchError::NoPathExistsBetweenVertices(source, target))?;
+
+    let fwd_route = backtrack::vertex_oriented_route(source, meeting_vertex_id, &fwd.tree)?;
+    let rev_route_backward =
+        backtrack::vertex_oriented_route(target, meeting_vertex_id, &rev.tree)?;
+    let rev_route = bidirectional_ops::reorient_reverse_route(&fwd_route, &rev_route_backward, si)?;
+    let route = fwd_route.into_iter().chain(rev_route).collect::<Vec<_>>();
+
+    let result = SearchAlgorithmResult {
+        trees: vec![fwd.tree, rev.tree],
+        routes: vec![route],
+        iterations,
+        suboptimality_bound: None,
+    };
+    Ok(result)
+}
+
+/// one half of a bidirectional search, tracking its own priority queue,
+/// cost labels and search tree.
+struct SearchFrontier {
+    root: VertexId,
+    goal: VertexId,
+    direction: Direction,
+    queue: InternalPriorityQueue<VertexId, ReverseCost>,
+    traversal_costs: HashMap<VertexId, Cost>,
+    tree: HashMap<VertexId, SearchTreeBranch>,
+}
+
+impl SearchFrontier {
+    fn new(
+        root: VertexId,
+        goal: VertexId,
+        direction: Direction,
+        initial_state: &[StateVariable],
+        si: &SearchInstance,
+    ) -> Result<SearchFrontier, SearchError> {
+        let mut queue = InternalPriorityQueue::default();
+        let origin_cost = si.estimate_traversal_cost(root, goal, initial_state)?;
+        queue.push(root, origin_cost.into());
+        let traversal_costs = HashMap::from([(root, Cost::ZERO)]);
+        Ok(SearchFrontier {
+            root,
+            goal,
+            direction,
+            queue,
+            traversal_costs,
+            tree: HashMap::new(),
+        })
+    }
+
+    /// the smallest f-score on this frontier, or None if the frontier is exhausted
+    fn min_f_score(&self) -> Option<Cost> {
+        self.queue.peek().map(|(_, cost)| (**cost).0)
+    }
+
+    /// pops the next vertex from this frontier and relaxes all of its incident edges
+    /// in this frontier's direction. any relaxation landing on a vertex labeled by the
+    /// opposite frontier is tested as a new best meeting point.
+    fn expand(
+        &mut self,
+        opposite: &SearchFrontier,
+        initial_state: &[StateVariable],
+        best_meeting: &mut Option<(VertexId, Cost)>,
+        si: &SearchInstance,
+    ) -> Result<(), SearchError> {
+        let current_vertex_id = match self.queue.pop() {
+            None => return Ok(()),
+            Some((id, _)) => id,
+        };
+
+        let (last_edge_id, current_state) = if current_vertex_id == self.root {
+            (None, initial_state.to_vec())
+        } else {
+            let branch = self.tree.get(&current_vertex_id).ok_or_else(|| {
+                SearchError::InternalError(format!(
+                    "expected vertex id {} missing from solution",
+                    current_vertex_id
+                ))
+            })?;
+            (
+                Some(branch.edge_traversal.edge_id),
+                branch.edge_traversal.result_state.clone(),
+            )
+        };
+        let current_gscore = self
+            .traversal_costs
+            .get(&current_vertex_id)
+            .cloned()
+            .unwrap_or(Cost::INFINITY);
+
+        let incident_edges: Vec<EdgeId> = self
+            .direction
+            .get_incident_edges(&current_vertex_id, si)
+            .cloned()
+            .collect();
+        for edge_id in incident_edges {
+            let e = si.graph.get_edge(&edge_id)?;
+            let terminal_vertex_id = self.direction.terminal_vertex_id(e);
+            let key_vertex_id = self.direction.tree_key_vertex_id(e);
+
+            let valid_frontier = si.frontier_model.valid_frontier(
+                e,
+                &current_state,
+                &self.tree,
+                &self.direction,
+                &si.state_model,
+            )?;
+            if !valid_frontier {
+                continue;
+            }
+
+            let et =
+                self.direction
+                    .perform_edge_traversal(edge_id, last_edge_id, &current_state, si)?;
+            let tentative_gscore = current_gscore + et.total_cost();
+            let existing_gscore = self
+                .traversal_costs
+                .get(&key_vertex_id)
+                .cloned()
+                .unwrap_or(Cost::INFINITY);
+            if tentative_gscore >= existing_gscore {
+                continue;
+            }
+
+            self.traversal_costs.insert(key_vertex_id, tentative_gscore);
+            self.tree.insert(
+                key_vertex_id,
+                SearchTreeBranch {
+                    terminal_vertex: terminal_vertex_id,
+                    edge_traversal: et,
+                },
+            );
+
+            let h_score = si.estimate_traversal_cost(key_vertex_id, self.goal, &current_state)?;
+            self.queue
+                .push_increase(key_vertex_id, (tentative_gscore + h_score).into());
+
+            if let Some(opposite_gscore) = opposite.traversal_costs.get(&key_vertex_id) {
+                let meeting_cost = tentative_gscore + *opposite_gscore;
+                let improves = match best_meeting {
+                    Some((_, best_cost)) => meeting_cost < *best_cost,
+                    None => true,
+                };
+                if improves {
+                    *best_meeting = Some((key_vertex_id, meeting_cost));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star;
+    use crate::algorithm::search::test_util::{
+        build_bidirectional_graph, build_graph, build_search_instance, build_vertices,
+    };
+    use crate::model::termination::TerminationModel;
+    use std::sync::Arc;
+
+    /// a ring of six vertices with a long "shortcut" chord between 0 and 3.
+    /// all vertices share a coordinate so the cost estimate is zero.
+    fn build_ring_search_instance() -> SearchInstance {
+        let pairs = [
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, 1.0),
+            (3, 4, 4.0),
+            (4, 5, 4.0),
+            (5, 0, 4.0),
+            (0, 3, 5.0),
+        ];
+        build_search_instance(
+            build_bidirectional_graph(6, &pairs),
+            TerminationModel::IterationsLimit { limit: 100 },
+        )
+    }
+
+    #[test]
+    fn test_matches_unidirectional_a_star() {
+        let si = build_ring_search_instance();
+        for src in 0..6 {
+            for dst in 0..6 {
+                if src == dst {
+                    continue;
+                }
+                let (o, d) = (VertexId(src), VertexId(dst));
+                let expected_tree = a_star::run_vertex_oriented(
+                    o,
+                    Some(d),
+                    &Direction::Forward,
+                    None,
+                    None,
+                    &[],
+                    &si,
+                )
+                .unwrap()
+                .tree;
+                let expected = backtrack::vertex_oriented_route(o, d, &expected_tree).unwrap();
+                let result = run_vertex_oriented(o, d, &si).unwrap();
+                assert_eq!(result.trees.len(), 2, "expected forward and reverse trees");
+                let route = result.routes.first().unwrap();
+
+                let route_edges = route.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+                let expected_edges = expected.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+                assert_eq!(route_edges, expected_edges, "route {} -> {}", o, d);
+
+                // the stitched route should accumulate state from the source
+                let final_state = &route.last().unwrap().result_state;
+                let expected_state = &expected.last().unwrap().result_state;
+                assert_eq!(final_state, expected_state, "state {} -> {}", o, d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_disconnected_vertices() {
+        let mut si = build_ring_search_instance();
+        si.graph = Arc::new(build_graph(build_vertices(2), vec![]));
+        let result = run_vertex_oriented(VertexId(0), VertexId(1), &si);
+        assert!(matches!(
+            result,
+            Err(SearchError::NoPathExistsBetweenVertices(_, _))
+        ));
+    }
+}
@@ -1,4 +1,10 @@
 mod a_star_algorithm;
+pub mod anytime_a_star_algorithm;
+pub mod bidirectional_a_star_algorithm;
 pub mod bidirectional_ops;
+pub mod multi_source_a_star_algorithm;
 
-pub use a_star_algorithm::{run_edge_oriented, run_vertex_oriented};
+pub use a_star_algorithm::{
+    run_edge_oriented, run_vertex_oriented, run_vertex_oriented_since,
+    run_vertex_oriented_to_targets,
+};
@@ -0,0 +1,273 @@
+use super::a_star_algorithm::estimate_cost;
+use crate::algorithm::search::Direction;
+use crate::algorithm::search::SearchError;
+use crate::algorithm::search::SearchInstance;
+use crate::algorithm::search::SearchTreeBranch;
+use crate::model::network::vertex_id::VertexId;
+use crate::model::state::StateVariable;
+use crate::model::unit::Cost;
+use crate::model::unit::ReverseCost;
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// result of a multi-source, multi-target a* search.
+pub struct MultiSourceSearchResult {
+    pub tree: HashMap<VertexId, SearchTreeBranch>,
+    /// the source of the least-cost route
+    pub source: VertexId,
+    /// the target of the least-cost route
+    pub target: VertexId,
+    /// the route cost plus the access costs of its source and target
+    pub cost: Cost,
+    pub iterations: u64,
+}
+
+/// runs a forward a* search from a set of sources to a set of targets, each with an
+/// access cost, and finds the route with the least total cost of the source access
+/// cost, route cost and target access cost. the search behaves as if a virtual vertex
+/// was connected to every source and from every target via edges with the access costs.
+///
+/// # Arguments
+///
+/// * `sources`       - source vertices with their access costs
+/// * `targets`       - target vertices with their access costs
+/// * `weight_factor` - optional a* heuristic weight factor
+/// * `si`            - search instance
+///
+/// # Returns
+///
+/// The search tree along with the source and target of the least-cost route
+pub fn run_vertex_oriented(
+    sources: &[(VertexId, Cost)],
+    targets: &[(VertexId, Cost)],
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<MultiSourceSearchResult, SearchError> {
+    let (first_source, first_target) = match (sources.first(), targets.first()) {
+        (Some((s, _)), Some((t, _))) => (*s, *t),
+        _ => {
+            return Err(SearchError::BuildError(String::from(
+                "multi-source search requires at least one source and one target",
+            )))
+        }
+    };
+    let mut target_costs: HashMap<VertexId, Cost> = HashMap::new();
+    for (vertex_id, cost) in targets.iter() {
+        let entry = target_costs.entry(*vertex_id).or_insert(*cost);
+        *entry = (*entry).min(*cost);
+    }
+
+    let mut costs: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    let mut traversal_costs: HashMap<VertexId, Cost> = HashMap::new();
+    let mut solution: HashMap<VertexId, SearchTreeBranch> = HashMap::new();
+
+    let initial_state = si.state_model.initial_state()?;
+    for (vertex_id, cost) in sources.iter() {
+        let existing = traversal_costs.get(vertex_id).unwrap_or(&Cost::INFINITY);
+        if cost < existing {
+            traversal_costs.insert(*vertex_id, *cost);
+        }
+    }
+    for (vertex_id, cost) in traversal_costs.iter() {
+        let h = estimate_to_targets(*vertex_id, &target_costs, &initial_state, weight_factor, si)?;
+        costs.push(*vertex_id, (*cost + h).into());
+    }
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+    let mut best: Option<(Cost, VertexId)> = None;
+    loop {
+        si.termination_model
+            .test(&start_time, solution.len(), iterations)?;
+
+        let (current_vertex_id, priority) = match costs.pop() {
+            None => break,
+            Some(next) => next,
+        };
+        let Reverse(f_score) = *priority;
+        if let Some((best_cost, _)) = best {
+            if f_score >= best_cost {
+                break;
+            }
+        }
+
+        let current_gscore = traversal_costs
+            .get(&current_vertex_id)
+            .unwrap_or(&Cost::INFINITY)
+            .to_owned();
+        if let Some(target_cost) = target_costs.get(&current_vertex_id) {
+            let total = current_gscore + *target_cost;
+            if best.is_none_or(|(best_cost, _)| total < best_cost) {
+                best = Some((total, current_vertex_id));
+            }
+        }
+
+        // sources that were not reached by another source begin with the initial state
+        let (current_state, last_edge_id) = match solution.get(&current_vertex_id) {
+            None => (initial_state.clone(), None),
+            Some(branch) => (
+                branch.edge_traversal.result_state.clone(),
+                Some(branch.edge_traversal.edge_id),
+            ),
+        };
+
+        for edge_id in si.graph.out_edges(&current_vertex_id).iter() {
+            let e = si.graph.get_edge(edge_id)?;
+            let valid_frontier = si.frontier_model.valid_frontier(
+                e,
+                &current_state,
+                &solution,
+                &Direction::Forward,
+                &si.state_model,
+            )?;
+            if !valid_frontier {
+                continue;
+            }
+            let et = Direction::Forward.perform_edge_traversal(
+                *edge_id,
+                last_edge_id,
+                &current_state,
+                si,
+            )?;
+            let tentative_gscore = current_gscore + et.total_cost();
+            let existing_gscore = traversal_costs
+                .get(&e.dst_vertex_id)
+                .unwrap_or(&Cost::INFINITY)
+                .to_owned();
+            if tentative_gscore < existing_gscore {
+                let h = estimate_to_targets(
+                    e.dst_vertex_id,
+                    &target_costs,
+                    &et.result_state,
+                    weight_factor,
+                    si,
+                )?;
+                traversal_costs.insert(e.dst_vertex_id, tentative_gscore);
+                solution.insert(
+                    e.dst_vertex_id,
+                    SearchTreeBranch {
+                        terminal_vertex: current_vertex_id,
+                        edge_traversal: et,
+                    },
+                );
+                costs.push_increase(e.dst_vertex_id, (tentative_gscore + h).into());
+            }
+        }
+        iterations += 1;
+    }
+
+    let (cost, target) = best.ok_or(SearchError::NoPathExistsBetweenVertices(
+        first_source,
+        first_target,
+    ))?;
+
+    // the source of the route is the first vertex of the path that no edge leads to
+    let mut source = target;
+    for _ in 0..=solution.len() {
+        match solution.get(&source) {
+            None => break,
+            Some(branch) => source = branch.terminal_vertex,
+        }
+    }
+    if solution.contains_key(&source) {
+        return Err(SearchError::InternalError(format!(
+            "loop in multi-source search tree while backtracking from vertex {}",
+            target
+        )));
+    }
+
+    Ok(MultiSourceSearchResult {
+        tree: solution,
+        source,
+        target,
+        cost,
+        iterations,
+    })
+}
+
+/// the least estimated cost from a vertex to any target, including the target access cost
+fn estimate_to_targets(
+    vertex_id: VertexId,
+    target_costs: &HashMap<VertexId, Cost>,
+    state: &[StateVariable],
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<Cost, SearchError> {
+    let mut min_estimate = Cost::INFINITY;
+    for (target, target_cost) in target_costs.iter() {
+        let estimate = estimate_cost(
+            vertex_id,
+            *target,
+            state,
+            &Direction::Forward,
+            weight_factor,
+            None,
+            si,
+        )?;
+        min_estimate = min_estimate.min(estimate + *target_cost);
+    }
+    Ok(min_estimate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
+    use crate::model::network::edge_id::EdgeId;
+    use crate::model::network::Edge;
+    use crate::model::termination::TerminationModel;
+
+    fn build_mock_search_instance() -> SearchInstance {
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 1, 0, 10.0),
+            Edge::new(2, 1, 2, 2.0),
+            Edge::new(3, 2, 1, 2.0),
+            Edge::new(4, 2, 3, 1.0),
+            Edge::new(5, 3, 2, 1.0),
+            Edge::new(6, 3, 0, 2.0),
+            Edge::new(7, 0, 3, 2.0),
+        ];
+        build_search_instance(
+            build_graph(build_vertices(4), edges),
+            TerminationModel::IterationsLimit { limit: 20 },
+        )
+    }
+
+    #[test]
+    fn test_picks_least_cost_pair() {
+        // (0) <---> (1)        (1) -> (2) -> (3) costs 3 with no access costs, which
+        //  ^         ^         beats (1) -> (2) at 2 + 4 and (0) -> (3) at 5 + 2.
+        //  |         |
+        //  v         v
+        // (3) <---> (2)
+        let si = build_mock_search_instance();
+        let sources = vec![(VertexId(0), Cost::new(5.0)), (VertexId(1), Cost::ZERO)];
+        let targets = vec![(VertexId(3), Cost::ZERO), (VertexId(2), Cost::new(4.0))];
+        let result = run_vertex_oriented(&sources, &targets, None, &si).unwrap();
+        assert_eq!(result.source, VertexId(1));
+        assert_eq!(result.target, VertexId(3));
+        assert_eq!(result.cost, Cost::new(3.0));
+        let route = vertex_oriented_route(result.source, result.target, &result.tree)
+            .unwrap()
+            .iter()
+            .map(|e| e.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(2), EdgeId(4)]);
+    }
+
+    #[test]
+    fn test_access_costs_change_the_pair() {
+        // with a large access cost at vertex 1, starting at vertex 0 is cheaper
+        let si = build_mock_search_instance();
+        let sources = vec![(VertexId(0), Cost::ZERO), (VertexId(1), Cost::new(10.0))];
+        let targets = vec![(VertexId(3), Cost::ZERO), (VertexId(2), Cost::ZERO)];
+        let result = run_vertex_oriented(&sources, &targets, None, &si).unwrap();
+        assert_eq!(result.source, VertexId(0));
+        assert_eq!(result.target, VertexId(3));
+        assert_eq!(result.cost, Cost::new(2.0));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::model::network::{Edge, EdgeId, VertexId};
+use crate::model::unit::Cost;
+use serde::{Deserialize, Serialize};
+
+/// an arc in a contraction hierarchy. an arc either wraps a single edge of the
+/// road network or is a shortcut that replaces the two arcs, `first_arc` followed by
+/// `second_arc`, which passed through a vertex at the time it was contracted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChArc {
+    pub src_vertex_id: VertexId,
+    pub dst_vertex_id: VertexId,
+    pub cost: Cost,
+    pub edge_id: Option<EdgeId>,
+    pub first_arc: Option<usize>,
+    pub second_arc: Option<usize>,
+}
+
+impl ChArc {
+    /// creates an arc that traverses a single graph edge
+    pub fn edge(edge: &Edge, cost: Cost) -> ChArc {
+        ChArc {
+            src_vertex_id: edge.src_vertex_id,
+            dst_vertex_id: edge.dst_vertex_id,
+            cost,
+            edge_id: Some(edge.edge_id),
+            first_arc: None,
+            second_arc: None,
+        }
+    }
+
+    /// creates a shortcut arc that replaces the arcs at the two provided indices
+    pub fn shortcut(
+        src_vertex_id: VertexId,
+        dst_vertex_id: VertexId,
+        cost: Cost,
+        first_arc: usize,
+        second_arc: usize,
+    ) -> ChArc {
+        ChArc {
+            src_vertex_id,
+            dst_vertex_id,
+            cost,
+            edge_id: None,
+            first_arc: Some(first_arc),
+            second_arc: Some(second_arc),
+        }
+    }
+}
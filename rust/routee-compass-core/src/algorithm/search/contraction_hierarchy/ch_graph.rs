@@ -0,0 +1,622 @@
+use super::ch_arc::ChArc;
+use super::contraction;
+use crate::algorithm::search::{EdgeTraversal, SearchAlgorithmResult, SearchError, SearchInstance};
+use crate::model::network::{EdgeId, VertexId};
+use crate::model::unit::{AsF64, Cost, ReverseCost};
+use crate::util::fs::read_utils;
+use crate::util::priority_queue::InternalPriorityQueue;
+use kdam::Bar;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+/// relative difference allowed between the preprocessed cost of a route and the cost
+/// of re-traversing it, which differ by floating point summation order
+const COST_TOLERANCE: f64 = 1e-9;
+
+/// file locations of a persisted [`ContractionHierarchy`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ContractionHierarchyConfig {
+    /// file with one row per vertex, listing the contraction rank of that vertex
+    pub vertex_rank_input_file: String,
+    /// csv file of [`ChArc`] rows, where shortcut arcs refer to other arcs by row index
+    pub arc_input_file: String,
+    /// json file with the cost and traversal model description used during preprocessing
+    pub cost_info_input_file: String,
+}
+
+/// a contraction hierarchy (CH) over the road network graph.
+///
+/// the hierarchy is built offline via [`ContractionHierarchy::build`], which contracts
+/// each vertex in turn and adds shortcut arcs that preserve shortest path costs
+/// between the remaining vertices. queries run a bidirectional dijkstra search that
+/// only follows arcs leading to higher-ranked vertices, which settles a tiny fraction
+/// of the graph compared to A*.
+///
+/// arc costs are fixed at preprocessing time, so a hierarchy is only valid for
+/// configurations where edge costs do not vary by query or search state, such as the
+/// distance traversal model or a speed table with fixed cost weights. the cost and
+/// traversal models used during preprocessing are stored with the hierarchy, and
+/// queries with a different or query-dependent configuration are rejected. shortcuts
+/// in the resulting route are unpacked and re-traversed with the query's models, so
+/// routes carry full [`EdgeTraversal`] state for the output plugins.
+///
+/// when deserialized as part of a search algorithm configuration, the hierarchy is
+/// loaded from the files listed in [`ContractionHierarchyConfig`].
+#[derive(Deserialize)]
+#[serde(try_from = "ContractionHierarchyConfig")]
+pub struct ContractionHierarchy {
+    config: Option<ContractionHierarchyConfig>,
+    cost_info: serde_json::Value,
+    ranks: Box<[usize]>,
+    arcs: Box<[ChArc]>,
+    /// by vertex, indices of arcs leaving that vertex toward a higher-ranked vertex
+    upward: Box<[Vec<usize>]>,
+    /// by vertex, indices of arcs entering that vertex from a higher-ranked vertex
+    downward: Box<[Vec<usize>]>,
+}
+
+impl ContractionHierarchy {
+    /// builds a contraction hierarchy from the graph and models of a search instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `si`                   - search instance used to compute the fixed edge costs
+    /// * `witness_search_limit` - max vertices settled by each witness search during contraction
+    ///
+    /// # Returns
+    ///
+    /// a contraction hierarchy ready for queries or to be written to file
+    pub fn build(
+        si: &SearchInstance,
+        witness_search_limit: usize,
+    ) -> Result<ContractionHierarchy, SearchError> {
+        if !si.traversal_model.is_query_invariant() {
+            return Err(SearchError::BuildError(String::from(
+                "contraction hierarchies require a traversal model whose edge costs do not vary by query",
+            )));
+        }
+        let cost_info = si.preprocessing_info()?;
+        let (ranks, arcs) = contraction::contract_graph(si, witness_search_limit)?;
+        ContractionHierarchy::new(
+            ranks.into_boxed_slice(),
+            arcs.into_boxed_slice(),
+            cost_info,
+            None,
+        )
+        .map_err(SearchError::BuildError)
+    }
+
+    fn new(
+        ranks: Box<[usize]>,
+        arcs: Box<[ChArc]>,
+        cost_info: serde_json::Value,
+        config: Option<ContractionHierarchyConfig>,
+    ) -> Result<ContractionHierarchy, String> {
+        let mut upward = vec![vec![]; ranks.len()];
+        let mut downward = vec![vec![]; ranks.len()];
+        for (idx, arc) in arcs.iter().enumerate() {
+            let src_rank = ranks.get(arc.src_vertex_id.0);
+            let dst_rank = ranks.get(arc.dst_vertex_id.0);
+            match (src_rank, dst_rank) {
+                (Some(src_rank), Some(dst_rank)) if src_rank < dst_rank => {
+                    upward[arc.src_vertex_id.0].push(idx)
+                }
+                (Some(_), Some(_)) => downward[arc.dst_vertex_id.0].push(idx),
+                _ => {
+                    return Err(format!(
+                        "arc {} from {} to {} references a vertex without a rank",
+                        idx, arc.src_vertex_id, arc.dst_vertex_id
+                    ))
+                }
+            }
+            let children = [arc.first_arc, arc.second_arc];
+            if let Some(child) = children.iter().flatten().find(|c| **c >= idx) {
+                return Err(format!(
+                    "shortcut arc {} refers to arc {} which does not precede it",
+                    idx, child
+                ));
+            }
+        }
+        Ok(ContractionHierarchy {
+            config,
+            cost_info,
+            ranks,
+            arcs,
+            upward: upward.into_boxed_slice(),
+            downward: downward.into_boxed_slice(),
+        })
+    }
+
+    /// loads a contraction hierarchy from a vertex rank file, an arc file and a cost
+    /// info file. the vertex rank and arc files may be gzipped.
+    pub fn from_files<P: AsRef<Path>>(
+        vertex_rank_file: P,
+        arc_file: P,
+        cost_info_file: P,
+    ) -> Result<ContractionHierarchy, String> {
+        let cost_info_str = std::fs::read_to_string(&cost_info_file).map_err(|e| {
+            format!(
+                "failure reading contraction hierarchy cost info from {}: {}",
+                cost_info_file.as_ref().to_string_lossy(),
+                e
+            )
+        })?;
+        let cost_info: serde_json::Value = serde_json::from_str(&cost_info_str)
+            .map_err(|e| format!("failure decoding contraction hierarchy cost info: {}", e))?;
+        let ranks = read_utils::read_raw_file(
+            &vertex_rank_file,
+            |_, row| {
+                row.parse::<usize>().map_err(|e| {
+                    let msg = format!("failure decoding vertex rank {}: {}", row, e);
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+                })
+            },
+            Some(Bar::builder().desc("contraction hierarchy vertex ranks")),
+            None,
+        )
+        .map_err(|e| {
+            format!(
+                "failure reading vertex ranks from {}: {}",
+                vertex_rank_file.as_ref().to_string_lossy(),
+                e
+            )
+        })?;
+        let arcs: Box<[ChArc]> = read_utils::from_csv(
+            &arc_file,
+            true,
+            Some(Bar::builder().desc("contraction hierarchy arcs")),
+            None,
+        )
+        .map_err(|e| {
+            format!(
+                "failure reading arcs from {}: {}",
+                arc_file.as_ref().to_string_lossy(),
+                e
+            )
+        })?;
+        let config = ContractionHierarchyConfig {
+            vertex_rank_input_file: vertex_rank_file.as_ref().to_string_lossy().to_string(),
+            arc_input_file: arc_file.as_ref().to_string_lossy().to_string(),
+            cost_info_input_file: cost_info_file.as_ref().to_string_lossy().to_string(),
+        };
+        ContractionHierarchy::new(ranks, arcs, cost_info, Some(config))
+    }
+
+    /// writes this contraction hierarchy to a vertex rank file, an arc file and a cost
+    /// info file which can later be loaded with [`ContractionHierarchy::from_files`].
+    pub fn write_files<P: AsRef<Path>>(
+        &self,
+        vertex_rank_file: P,
+        arc_file: P,
+        cost_info_file: P,
+    ) -> Result<(), String> {
+        let cost_info_str = serde_json::to_string_pretty(&self.cost_info)
+            .map_err(|e| format!("failure encoding contraction hierarchy cost info: {}", e))?;
+        std::fs::write(&cost_info_file, cost_info_str)
+            .map_err(|e| format!("failure writing contraction hierarchy cost info: {}", e))?;
+
+        let ranks = self
+            .ranks
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&vertex_rank_file, ranks)
+            .map_err(|e| format!("failure writing vertex ranks: {}", e))?;
+
+        let mut writer = csv::Writer::from_path(&arc_file)
+            .map_err(|e| format!("failure opening arc file: {}", e))?;
+        for arc in self.arcs.iter() {
+            writer
+                .serialize(arc)
+                .map_err(|e| format!("failure writing arc: {}", e))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| format!("failure writing arc file: {}", e))?;
+        Ok(())
+    }
+
+    /// number of arcs in this hierarchy, including shortcuts
+    pub fn n_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
+    /// runs a shortest path query between two vertices over this contraction hierarchy.
+    ///
+    /// a forward search from the source and a backward search from the target each
+    /// relax only arcs toward higher-ranked vertices. the search stops once neither
+    /// frontier can improve on the best meeting vertex. the arcs on the resulting path
+    /// are unpacked into graph edges which are traversed from the initial state, so
+    /// the route reflects the costs and state of this query's search instance.
+    ///
+    /// no search trees are returned since the upward search spaces do not form
+    /// meaningful shortest path trees over the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - search origin vertex
+    /// * `target` - search destination vertex
+    /// * `si`     - the search assets for this query
+    ///
+    /// # Returns
+    ///
+    /// a single route from source to target
+    pub fn run_vertex_oriented(
+        &self,
+        source: VertexId,
+        target: VertexId,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        if self.ranks.len() != si.graph.n_vertices() {
+            return Err(SearchError::BuildError(format!(
+                "contraction hierarchy has {} vertices but graph has {}",
+                self.ranks.len(),
+                si.graph.n_vertices()
+            )));
+        }
+        if !si.matches_preprocessing_info(&self.cost_info)? {
+            return Err(SearchError::BuildError(String::from(
+                "query cost or traversal model differs from the one used to build the contraction hierarchy, or its edge costs vary by query",
+            )));
+        }
+        if source == target {
+            return Ok(SearchAlgorithmResult::default());
+        }
+
+        let mut fwd = ChFrontier::new(source);
+        let mut bwd = ChFrontier::new(target);
+        let mut best_meeting: Option<(VertexId, Cost)> = None;
+
+        let start_time = Instant::now();
+        let mut iterations = 0;
+
+        loop {
+            si.termination_model.test(
+                &start_time,
+                fwd.costs.len() + bwd.costs.len(),
+                iterations,
+            )?;
+
+            let fwd_min = fwd.min_cost();
+            let bwd_min = bwd.min_cost();
+            let bound = best_meeting.map(|(_, c)| c).unwrap_or(Cost::INFINITY);
+            let fwd_open = fwd_min.map(|c| c < bound).unwrap_or_default();
+            let bwd_open = bwd_min.map(|c| c < bound).unwrap_or_default();
+            let expand_fwd = match (fwd_open, bwd_open) {
+                (false, false) => break,
+                (true, false) => true,
+                (false, true) => false,
+                (true, true) => fwd_min <= bwd_min,
+            };
+
+            if expand_fwd {
+                fwd.expand(&bwd, &self.upward, &self.arcs, true, &mut best_meeting);
+            } else {
+                bwd.expand(&fwd, &self.downward, &self.arcs, false, &mut best_meeting);
+            }
+            iterations += 1;
+        }
+
+        let (meeting_vertex_id, ch_cost) =
+            best_meeting.ok_or(SearchError::NoPathExistsBetweenVertices(source, target))?;
+
+        // collect the arcs source -> meeting -> target and unpack any shortcuts
+        let mut path_arcs = fwd.backtrack(meeting_vertex_id, &self.arcs, true);
+        path_arcs.reverse();
+        path_arcs.extend(bwd.backtrack(meeting_vertex_id, &self.arcs, false));
+        let edges = self.unpack(&path_arcs)?;
+
+        let mut state = si.state_model.initial_state()?;
+        let mut prev_edge_id: Option<EdgeId> = None;
+        let mut route = Vec::with_capacity(edges.len());
+        for edge_id in edges {
+            let et = EdgeTraversal::forward_traversal(edge_id, prev_edge_id, &state, si)?;
+            state.clone_from(&et.result_state);
+            prev_edge_id = Some(edge_id);
+            route.push(et);
+        }
+
+        let route_cost = route
+            .iter()
+            .fold(Cost::ZERO, |acc, et| acc + et.total_cost());
+        let tolerance = COST_TOLERANCE * ch_cost.as_f64().abs().max(1.0);
+        if (route_cost - ch_cost).as_f64().abs() > tolerance {
+            return Err(SearchError::InternalError(format!(
+                "contraction hierarchy route {} -> {} has cost {} but preprocessed cost was {}. edge costs may depend on the search state, which is not supported.",
+                source, target, route_cost, ch_cost
+            )));
+        }
+
+        Ok(SearchAlgorithmResult {
+            trees: vec![],
+            routes: vec![route],
+            iterations,
+            suboptimality_bound: None,
+        })
+    }
+
+    /// expands a sequence of arcs into the graph edges they represent
+    fn unpack(&self, path_arcs: &[usize]) -> Result<Vec<EdgeId>, SearchError> {
+        let mut edges = vec![];
+        let mut stack: Vec<usize> = path_arcs.iter().rev().cloned().collect();
+        while let Some(arc_idx) = stack.pop() {
+            let arc = self.arcs.get(arc_idx).ok_or_else(|| {
+                SearchError::InternalError(format!("contraction hierarchy missing arc {}", arc_idx))
+            })?;
+            match (arc.edge_id, arc.first_arc, arc.second_arc) {
+                (Some(edge_id), _, _) => edges.push(edge_id),
+                (None, Some(first), Some(second)) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                _ => {
+                    return Err(SearchError::InternalError(format!(
+                        "contraction hierarchy arc {} has neither an edge nor two child arcs",
+                        arc_idx
+                    )))
+                }
+            }
+        }
+        Ok(edges)
+    }
+}
+
+impl TryFrom<ContractionHierarchyConfig> for ContractionHierarchy {
+    type Error = String;
+
+    fn try_from(config: ContractionHierarchyConfig) -> Result<Self, Self::Error> {
+        ContractionHierarchy::from_files(
+            &config.vertex_rank_input_file,
+            &config.arc_input_file,
+            &config.cost_info_input_file,
+        )
+    }
+}
+
+impl Serialize for ContractionHierarchy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match &self.config {
+            Some(config) => config.serialize(serializer),
+            None => Err(serde::ser::Error::custom(
+                "contraction hierarchy was not loaded from files and cannot be serialized as configuration",
+            )),
+        }
+    }
+}
+
+/// one half of a contraction hierarchy query
+struct ChFrontier {
+    queue: InternalPriorityQueue<VertexId, ReverseCost>,
+    costs: HashMap<VertexId, Cost>,
+    /// by vertex, the arc used to reach it
+    parents: HashMap<VertexId, usize>,
+}
+
+impl ChFrontier {
+    fn new(root: VertexId) -> ChFrontier {
+        let mut queue = InternalPriorityQueue::default();
+        queue.push(root, Cost::ZERO.into());
+        ChFrontier {
+            queue,
+            costs: HashMap::from([(root, Cost::ZERO)]),
+            parents: HashMap::new(),
+        }
+    }
+
+    fn min_cost(&self) -> Option<Cost> {
+        self.queue.peek().map(|(_, cost)| (**cost).0)
+    }
+
+    /// settles the next vertex on this frontier, tests it as a meeting point with the
+    /// opposite frontier and relaxes its arcs. a forward frontier follows arcs from src
+    /// to dst, a backward frontier follows arcs from dst to src.
+    fn expand(
+        &mut self,
+        opposite: &ChFrontier,
+        adjacency: &[Vec<usize>],
+        arcs: &[ChArc],
+        forward: bool,
+        best_meeting: &mut Option<(VertexId, Cost)>,
+    ) {
+        let vertex_id = match self.queue.pop() {
+            None => return,
+            Some((v, _)) => v,
+        };
+        let cost = self
+            .costs
+            .get(&vertex_id)
+            .cloned()
+            .unwrap_or(Cost::INFINITY);
+
+        if let Some(opposite_cost) = opposite.costs.get(&vertex_id) {
+            let meeting_cost = cost + *opposite_cost;
+            let improves = match best_meeting {
+                Some((_, best_cost)) => meeting_cost < *best_cost,
+                None => true,
+            };
+            if improves {
+                *best_meeting = Some((vertex_id, meeting_cost));
+            }
+        }
+
+        for arc_idx in adjacency[vertex_id.0].iter() {
+            let arc = &arcs[*arc_idx];
+            let next = if forward {
+                arc.dst_vertex_id
+            } else {
+                arc.src_vertex_id
+            };
+            let tentative = cost + arc.cost;
+            let existing = self.costs.get(&next).cloned().unwrap_or(Cost::INFINITY);
+            if tentative < existing {
+                self.costs.insert(next, tentative);
+                self.parents.insert(next, *arc_idx);
+                self.queue.push_increase(next, tentative.into());
+            }
+        }
+    }
+
+    /// the arcs from the given vertex back to the root of this frontier, in backtrack order
+    fn backtrack(&self, vertex_id: VertexId, arcs: &[ChArc], forward: bool) -> Vec<usize> {
+        let mut result = vec![];
+        let mut current = vertex_id;
+        while let Some(arc_idx) = self.parents.get(&current) {
+            result.push(*arc_idx);
+            let arc = &arcs[*arc_idx];
+            current = if forward {
+                arc.src_vertex_id
+            } else {
+                arc.dst_vertex_id
+            };
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::test_util::{build_bidirectional_graph, build_search_instance};
+    use crate::algorithm::search::{a_star, backtrack, Direction};
+    use crate::model::termination::TerminationModel;
+    use std::sync::Arc;
+
+    /// a 3x3 grid of bidirectional edges with varying lengths. all vertices
+    /// share a coordinate so the A* cost estimate is zero.
+    fn build_grid_search_instance() -> SearchInstance {
+        let pairs = [
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (3, 4, 3.0),
+            (4, 5, 1.0),
+            (6, 7, 2.0),
+            (7, 8, 1.0),
+            (0, 3, 2.0),
+            (3, 6, 1.0),
+            (1, 4, 4.0),
+            (4, 7, 1.0),
+            (2, 5, 1.0),
+            (5, 8, 3.0),
+        ];
+        build_search_instance(
+            build_bidirectional_graph(9, &pairs),
+            TerminationModel::IterationsLimit { limit: 1000 },
+        )
+    }
+
+    fn route_cost(route: &[EdgeTraversal]) -> Cost {
+        route
+            .iter()
+            .fold(Cost::ZERO, |acc, et| acc + et.total_cost())
+    }
+
+    fn assert_matches_a_star(ch: &ContractionHierarchy, si: &SearchInstance) {
+        for src in 0..9 {
+            for dst in 0..9 {
+                if src == dst {
+                    continue;
+                }
+                let (o, d) = (VertexId(src), VertexId(dst));
+                let tree = a_star::run_vertex_oriented(
+                    o,
+                    Some(d),
+                    &Direction::Forward,
+                    None,
+                    None,
+                    &[],
+                    si,
+                )
+                .unwrap()
+                .tree;
+                let expected = backtrack::vertex_oriented_route(o, d, &tree).unwrap();
+                let result = ch.run_vertex_oriented(o, d, si).unwrap();
+                let route = result.routes.first().unwrap();
+                assert_eq!(route_cost(route), route_cost(&expected), "{} -> {}", o, d);
+                assert_eq!(
+                    si.graph
+                        .src_vertex_id(&route.first().unwrap().edge_id)
+                        .unwrap(),
+                    o
+                );
+                assert_eq!(
+                    si.graph
+                        .dst_vertex_id(&route.last().unwrap().edge_id)
+                        .unwrap(),
+                    d
+                );
+                for pair in route.windows(2) {
+                    let prev_dst = si.graph.dst_vertex_id(&pair[0].edge_id).unwrap();
+                    let next_src = si.graph.src_vertex_id(&pair[1].edge_id).unwrap();
+                    assert_eq!(prev_dst, next_src, "route {} -> {} is disconnected", o, d);
+                }
+                assert_eq!(
+                    route.last().unwrap().result_state,
+                    expected.last().unwrap().result_state
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_a_star() {
+        let si = build_grid_search_instance();
+        let ch = ContractionHierarchy::build(&si, 50).unwrap();
+        assert_matches_a_star(&ch, &si);
+    }
+
+    #[test]
+    fn test_matches_a_star_with_limited_witness_search() {
+        let si = build_grid_search_instance();
+        let ch = ContractionHierarchy::build(&si, 1).unwrap();
+        assert_matches_a_star(&ch, &si);
+    }
+
+    #[test]
+    fn test_write_and_read_files() {
+        let si = build_grid_search_instance();
+        let ch = ContractionHierarchy::build(&si, 50).unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "routee_compass_ch_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rank_file = dir.join("vertex_rank.txt");
+        let arc_file = dir.join("ch_arcs.csv");
+        let cost_info_file = dir.join("cost_info.json");
+        ch.write_files(&rank_file, &arc_file, &cost_info_file)
+            .unwrap();
+
+        let config = serde_json::json!({
+            "vertex_rank_input_file": rank_file.to_string_lossy(),
+            "arc_input_file": arc_file.to_string_lossy(),
+            "cost_info_input_file": cost_info_file.to_string_lossy(),
+        });
+        let loaded: ContractionHierarchy = serde_json::from_value(config.clone()).unwrap();
+        assert_eq!(loaded.ranks, ch.ranks);
+        assert_eq!(loaded.n_arcs(), ch.n_arcs());
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), config);
+        assert_matches_a_star(&loaded, &si);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rejects_different_configuration() {
+        let si = build_grid_search_instance();
+        let ch = ContractionHierarchy::build(&si, 50).unwrap();
+        let mut query_si = build_grid_search_instance();
+        query_si.traversal_config = Arc::new(serde_json::json!({"type": "speed_table"}));
+        let result = ch.run_vertex_oriented(VertexId(0), VertexId(8), &query_si);
+        assert!(matches!(result, Err(SearchError::BuildError(_))));
+    }
+}
@@ -0,0 +1,227 @@
+use super::ch_arc::ChArc;
+use crate::algorithm::search::{EdgeTraversal, SearchError, SearchInstance};
+use crate::model::network::VertexId;
+use crate::model::unit::{Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+use kdam::{Bar, BarExt};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// for each vertex, the cheapest arc to each remaining neighbor as (cost, arc index)
+type Adjacency = Vec<HashMap<VertexId, (Cost, usize)>>;
+
+/// a shortcut required to contract some vertex v: (u, w, cost, arc u->v, arc v->w)
+type Shortcut = (VertexId, VertexId, Cost, usize, usize);
+
+/// contracts every vertex of the graph, in order of a lazily-updated edge difference
+/// heuristic, producing the vertex ranks and the full set of arcs of the hierarchy.
+///
+/// edge costs are computed once by traversing each edge from the initial search state
+/// with no previous edge, so the hierarchy is only valid for cost configurations where
+/// edge costs do not depend on the search state or the query.
+///
+/// # Arguments
+///
+/// * `si`                   - search instance used to compute edge costs
+/// * `witness_search_limit` - max vertices settled by each local witness search. smaller
+///   values speed up preprocessing at the expense of additional shortcuts.
+///
+/// # Returns
+///
+/// the rank of each vertex, indexed by VertexId, and all arcs of the hierarchy
+pub fn contract_graph(
+    si: &SearchInstance,
+    witness_search_limit: usize,
+) -> Result<(Vec<usize>, Vec<ChArc>), SearchError> {
+    let n_vertices = si.graph.n_vertices();
+    let initial_state = si.state_model.initial_state()?;
+
+    let mut arcs: Vec<ChArc> = Vec::with_capacity(si.graph.n_edges());
+    let mut out_arcs: Adjacency = vec![HashMap::new(); n_vertices];
+    let mut in_arcs: Adjacency = vec![HashMap::new(); n_vertices];
+    for edge in si.graph.edges.iter() {
+        // self-loops never appear on a shortest path
+        if edge.src_vertex_id == edge.dst_vertex_id || !si.frontier_model.valid_edge(edge)? {
+            continue;
+        }
+        let et = EdgeTraversal::forward_traversal(edge.edge_id, None, &initial_state, si)?;
+        add_arc(
+            ChArc::edge(edge, et.total_cost()),
+            &mut arcs,
+            &mut out_arcs,
+            &mut in_arcs,
+        );
+    }
+
+    // initial contraction order
+    let mut contracted_neighbors = vec![0i64; n_vertices];
+    let mut queue: InternalPriorityQueue<VertexId, Reverse<i64>> = InternalPriorityQueue::default();
+    for vertex_id in si.graph.vertex_ids() {
+        let shortcuts = find_shortcuts(vertex_id, &out_arcs, &in_arcs, witness_search_limit);
+        let priority = edge_difference(vertex_id, &shortcuts, &out_arcs, &in_arcs, 0);
+        queue.push(vertex_id, Reverse(priority));
+    }
+
+    let mut pb = Bar::builder()
+        .total(n_vertices)
+        .animation("fillup")
+        .desc("contract vertices")
+        .build()
+        .map_err(|e| SearchError::InternalError(format!("could not build progress bar: {}", e)))?;
+
+    let mut ranks = vec![0usize; n_vertices];
+    let mut next_rank = 0;
+    while let Some((vertex_id, _)) = queue.pop() {
+        // lazy update: re-evaluate this vertex and defer it if it no longer has the lowest priority
+        let shortcuts = find_shortcuts(vertex_id, &out_arcs, &in_arcs, witness_search_limit);
+        let priority = edge_difference(
+            vertex_id,
+            &shortcuts,
+            &out_arcs,
+            &in_arcs,
+            contracted_neighbors[vertex_id.0],
+        );
+        if let Some((_, Reverse(next_priority))) = queue.peek() {
+            if priority > *next_priority {
+                queue.push(vertex_id, Reverse(priority));
+                continue;
+            }
+        }
+
+        for (src, dst, cost, first_arc, second_arc) in shortcuts {
+            let shortcut = ChArc::shortcut(src, dst, cost, first_arc, second_arc);
+            add_arc(shortcut, &mut arcs, &mut out_arcs, &mut in_arcs);
+        }
+
+        // remove the contracted vertex from the remaining graph
+        let removed_out = std::mem::take(&mut out_arcs[vertex_id.0]);
+        let removed_in = std::mem::take(&mut in_arcs[vertex_id.0]);
+        for dst in removed_out.keys() {
+            in_arcs[dst.0].remove(&vertex_id);
+            contracted_neighbors[dst.0] += 1;
+        }
+        for src in removed_in.keys() {
+            out_arcs[src.0].remove(&vertex_id);
+            contracted_neighbors[src.0] += 1;
+        }
+
+        ranks[vertex_id.0] = next_rank;
+        next_rank += 1;
+        let _ = pb.update(1);
+    }
+    eprintln!();
+
+    log::info!(
+        "contracted {} vertices, adding {} shortcuts to {} edge arcs",
+        n_vertices,
+        arcs.iter().filter(|a| a.edge_id.is_none()).count(),
+        arcs.iter().filter(|a| a.edge_id.is_some()).count(),
+    );
+
+    Ok((ranks, arcs))
+}
+
+/// stores an arc and registers it in the remaining graph if it is the cheapest
+/// arc between its two vertices.
+fn add_arc(arc: ChArc, arcs: &mut Vec<ChArc>, out_arcs: &mut Adjacency, in_arcs: &mut Adjacency) {
+    let (src, dst, cost) = (arc.src_vertex_id, arc.dst_vertex_id, arc.cost);
+    let arc_idx = arcs.len();
+    arcs.push(arc);
+    let improves = match out_arcs[src.0].get(&dst) {
+        Some((existing, _)) => cost < *existing,
+        None => true,
+    };
+    if improves {
+        out_arcs[src.0].insert(dst, (cost, arc_idx));
+        in_arcs[dst.0].insert(src, (cost, arc_idx));
+    }
+}
+
+/// the edge difference heuristic used to order vertex contraction. vertices that
+/// add few shortcuts relative to the arcs they remove, and which have few contracted
+/// neighbors, are contracted first.
+fn edge_difference(
+    vertex_id: VertexId,
+    shortcuts: &[Shortcut],
+    out_arcs: &Adjacency,
+    in_arcs: &Adjacency,
+    contracted_neighbors: i64,
+) -> i64 {
+    let removed = out_arcs[vertex_id.0].len() + in_arcs[vertex_id.0].len();
+    shortcuts.len() as i64 - removed as i64 + contracted_neighbors
+}
+
+/// finds the shortcuts required to preserve shortest path costs if this vertex were
+/// contracted. a shortcut u->w is required for each in-neighbor u and out-neighbor w
+/// unless a local witness search finds a path from u to w that avoids this vertex
+/// and costs no more than the path through it.
+fn find_shortcuts(
+    vertex_id: VertexId,
+    out_arcs: &Adjacency,
+    in_arcs: &Adjacency,
+    witness_search_limit: usize,
+) -> Vec<Shortcut> {
+    let outgoing = &out_arcs[vertex_id.0];
+    let mut shortcuts = vec![];
+    for (src, (src_cost, src_arc)) in in_arcs[vertex_id.0].iter() {
+        let max_out_cost = outgoing
+            .iter()
+            .filter(|(dst, _)| *dst != src)
+            .map(|(_, (c, _))| *c)
+            .max();
+        let max_cost = match max_out_cost {
+            None => continue,
+            Some(c) => *src_cost + c,
+        };
+        let witness_costs =
+            witness_search(*src, vertex_id, max_cost, witness_search_limit, out_arcs);
+        for (dst, (dst_cost, dst_arc)) in outgoing.iter() {
+            if dst == src {
+                continue;
+            }
+            let via_cost = *src_cost + *dst_cost;
+            let has_witness = match witness_costs.get(dst) {
+                Some(witness_cost) => *witness_cost <= via_cost,
+                None => false,
+            };
+            if !has_witness {
+                shortcuts.push((*src, *dst, via_cost, *src_arc, *dst_arc));
+            }
+        }
+    }
+    shortcuts
+}
+
+/// bounded dijkstra search from a source vertex that ignores the vertex being contracted.
+/// returns the (upper bound) cost to each vertex reached.
+fn witness_search(
+    source: VertexId,
+    ignored: VertexId,
+    max_cost: Cost,
+    settled_limit: usize,
+    out_arcs: &Adjacency,
+) -> HashMap<VertexId, Cost> {
+    let mut costs: HashMap<VertexId, Cost> = HashMap::from([(source, Cost::ZERO)]);
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    queue.push(source, Cost::ZERO.into());
+    let mut settled = 0;
+    while let Some((vertex_id, _)) = queue.pop() {
+        let cost = costs.get(&vertex_id).cloned().unwrap_or(Cost::INFINITY);
+        if cost > max_cost || settled >= settled_limit {
+            break;
+        }
+        settled += 1;
+        for (dst, (arc_cost, _)) in out_arcs[vertex_id.0].iter() {
+            if *dst == ignored {
+                continue;
+            }
+            let tentative = cost + *arc_cost;
+            let existing = costs.get(dst).cloned().unwrap_or(Cost::INFINITY);
+            if tentative < existing {
+                costs.insert(*dst, tentative);
+                queue.push_increase(*dst, tentative.into());
+            }
+        }
+    }
+    costs
+}
@@ -0,0 +1,6 @@
+mod ch_arc;
+mod ch_graph;
+mod contraction;
+
+pub use ch_arc::ChArc;
+pub use ch_graph::{ContractionHierarchy, ContractionHierarchyConfig};
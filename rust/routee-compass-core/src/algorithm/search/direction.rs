@@ -6,7 +6,7 @@ use crate::model::state::StateVariable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Copy, Clone, Serialize, Deserialize, Default)]
-#[serde(rename = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
     #[default]
     Forward,
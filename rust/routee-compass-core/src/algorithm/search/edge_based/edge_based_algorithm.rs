@@ -0,0 +1,442 @@
+use crate::algorithm::search::{
+    Direction, EdgeTraversal, MinSearchTree, SearchAlgorithmResult, SearchError, SearchInstance,
+    SearchTreeBranch,
+};
+use crate::model::network::{EdgeId, VertexId};
+use crate::model::unit::{AsF64, Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// the best known way to reach the end of an edge
+#[derive(Clone, Debug)]
+struct EdgeLabel {
+    cost: Cost,
+    edge_traversal: EdgeTraversal,
+    parent: Option<EdgeId>,
+}
+
+/// the edge of the label that reached the target, if any, along with all labels
+/// and the number of iterations
+type EdgeSearchResult = (Option<EdgeId>, HashMap<EdgeId, EdgeLabel>, u64);
+
+/// the goal of an edge-based search
+enum EdgeSearchTarget {
+    /// reach a vertex over any edge
+    Vertex(VertexId),
+    /// reach the start of an edge over an edge that may turn onto it
+    Edge(EdgeId),
+}
+
+/// run an A* search on the line graph, keying labels by the edge traversed instead of the
+/// vertex reached. each edge keeps its own least-cost label, so when the frontier model or
+/// access model depend on the previous edge, such as with turn restrictions or turn delays,
+/// a costlier approach to a vertex that permits a cheaper or legal turn is not discarded.
+///
+/// routes are found by following the labels back to the source. the returned tree is keyed
+/// by vertex and holds the least-cost incoming edge at each vertex. it summarizes the search
+/// but, unlike the routes, backtracking through it does not respect turns.
+///
+/// # Arguments
+///
+/// * `source`        - search origin vertex
+/// * `target`        - optional search destination vertex
+/// * `weight_factor` - optional a* heuristic weight, where zero runs a dijkstra search
+/// * `si`            - the search assets for this query
+pub fn run_vertex_oriented(
+    source: VertexId,
+    target: Option<VertexId>,
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if target == Some(source) {
+        return Ok(SearchAlgorithmResult::default());
+    }
+    let initial_state = si.state_model.initial_state()?;
+    let no_tree = HashMap::new();
+    let mut seeds = vec![];
+    for edge_id in si.graph.out_edges_iter(&source) {
+        let edge = si.graph.get_edge(edge_id)?;
+        let valid = si.frontier_model.valid_frontier(
+            edge,
+            &initial_state,
+            &no_tree,
+            &Direction::Forward,
+            &si.state_model,
+        )?;
+        if valid {
+            let et = EdgeTraversal::forward_traversal(*edge_id, None, &initial_state, si)?;
+            seeds.push(EdgeLabel {
+                cost: et.total_cost(),
+                edge_traversal: et,
+                parent: None,
+            });
+        }
+    }
+    let search_target = target.map(EdgeSearchTarget::Vertex);
+    let (goal, labels, iterations) = run_search(seeds, search_target, weight_factor, si)?;
+
+    let routes = match (target, goal) {
+        (None, _) => vec![],
+        (Some(t), None) => return Err(SearchError::NoPathExistsBetweenVertices(source, t)),
+        (Some(_), Some(goal_edge_id)) => vec![backtrack(goal_edge_id, &labels)?],
+    };
+    Ok(SearchAlgorithmResult {
+        trees: vec![vertex_tree(&labels, si)?],
+        routes,
+        iterations,
+        suboptimality_bound: None,
+    })
+}
+
+/// runs an edge-based search between a source edge and an optional target edge. the search
+/// starts at the end of the source edge, so the first turn of the route is tested against
+/// it. as with edge-oriented searches of other algorithms, the source and target edges are
+/// included in the route with zero cost, and the turn onto the target edge is tested.
+///
+/// # Arguments
+///
+/// * `source`        - search origin edge
+/// * `target`        - optional search destination edge
+/// * `weight_factor` - optional a* heuristic weight, where zero runs a dijkstra search
+/// * `si`            - the search assets for this query
+pub fn run_edge_oriented(
+    source: EdgeId,
+    target: Option<EdgeId>,
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if target == Some(source) {
+        return Ok(SearchAlgorithmResult::default());
+    }
+    let src_et = EdgeTraversal {
+        edge_id: source,
+        access_cost: Cost::ZERO,
+        traversal_cost: Cost::ZERO,
+        result_state: si.state_model.initial_state()?,
+    };
+    let seed = EdgeLabel {
+        cost: Cost::ZERO,
+        edge_traversal: src_et,
+        parent: None,
+    };
+    let search_target = target.map(EdgeSearchTarget::Edge);
+    let (goal, labels, iterations) = run_search(vec![seed], search_target, weight_factor, si)?;
+
+    let routes = match (target, goal) {
+        (None, _) => vec![],
+        (Some(target_edge_id), None) => {
+            let src = si.graph.dst_vertex_id(&source)?;
+            let dst = si.graph.src_vertex_id(&target_edge_id)?;
+            return Err(SearchError::NoPathExistsBetweenVertices(src, dst));
+        }
+        (Some(target_edge_id), Some(goal_edge_id)) => {
+            let mut route = backtrack(goal_edge_id, &labels)?;
+            let final_state = route
+                .last()
+                .map(|et| et.result_state.clone())
+                .ok_or_else(|| SearchError::InternalError(String::from("found empty route")))?;
+            route.push(EdgeTraversal {
+                edge_id: target_edge_id,
+                access_cost: Cost::ZERO,
+                traversal_cost: Cost::ZERO,
+                result_state: final_state,
+            });
+            vec![route]
+        }
+    };
+    Ok(SearchAlgorithmResult {
+        trees: vec![vertex_tree(&labels, si)?],
+        routes,
+        iterations,
+        suboptimality_bound: None,
+    })
+}
+
+/// runs the search from the seed labels until the target is reached or the queue is empty
+fn run_search(
+    seeds: Vec<EdgeLabel>,
+    target: Option<EdgeSearchTarget>,
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<EdgeSearchResult, SearchError> {
+    let target_vertex = match &target {
+        None => None,
+        Some(EdgeSearchTarget::Vertex(v)) => Some(*v),
+        Some(EdgeSearchTarget::Edge(e)) => Some(si.graph.src_vertex_id(e)?),
+    };
+    let mut labels: HashMap<EdgeId, EdgeLabel> = HashMap::new();
+    let mut settled: HashSet<EdgeId> = HashSet::new();
+    let mut queue: InternalPriorityQueue<EdgeId, ReverseCost> = InternalPriorityQueue::default();
+    for seed in seeds.into_iter() {
+        let edge_id = seed.edge_traversal.edge_id;
+        let h = estimate_cost(edge_id, &seed, target_vertex, weight_factor, si)?;
+        queue.push(edge_id, (seed.cost + h).into());
+        labels.insert(edge_id, seed);
+    }
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+
+    while let Some((edge_id, _)) = queue.pop() {
+        si.termination_model
+            .test(&start_time, labels.len(), iterations)?;
+        iterations += 1;
+        settled.insert(edge_id);
+
+        let label = labels.get(&edge_id).cloned().ok_or_else(|| {
+            SearchError::InternalError(format!("label for edge {} missing", edge_id))
+        })?;
+        let edge = si.graph.get_edge(&edge_id)?;
+        let vertex_id = edge.dst_vertex_id;
+        let current_state = &label.edge_traversal.result_state;
+        // the frontier model sees the edge of this label as the previous edge
+        let tree = HashMap::from([(
+            vertex_id,
+            SearchTreeBranch {
+                terminal_vertex: edge.src_vertex_id,
+                edge_traversal: label.edge_traversal.clone(),
+            },
+        )]);
+
+        match &target {
+            Some(EdgeSearchTarget::Vertex(v)) if *v == vertex_id => {
+                return Ok((Some(edge_id), labels, iterations));
+            }
+            Some(EdgeSearchTarget::Edge(e)) if si.graph.src_vertex_id(e)? == vertex_id => {
+                let target_edge = si.graph.get_edge(e)?;
+                let valid = si.frontier_model.valid_frontier(
+                    target_edge,
+                    current_state,
+                    &tree,
+                    &Direction::Forward,
+                    &si.state_model,
+                )?;
+                if valid {
+                    return Ok((Some(edge_id), labels, iterations));
+                }
+            }
+            _ => {}
+        }
+
+        for next_edge_id in si.graph.out_edges_iter(&vertex_id) {
+            if settled.contains(next_edge_id) {
+                continue;
+            }
+            let next_edge = si.graph.get_edge(next_edge_id)?;
+            let valid = si.frontier_model.valid_frontier(
+                next_edge,
+                current_state,
+                &tree,
+                &Direction::Forward,
+                &si.state_model,
+            )?;
+            if !valid {
+                continue;
+            }
+            let et =
+                EdgeTraversal::forward_traversal(*next_edge_id, Some(edge_id), current_state, si)?;
+            let tentative_cost = label.cost + et.total_cost();
+            let existing_cost = labels
+                .get(next_edge_id)
+                .map(|l| l.cost)
+                .unwrap_or(Cost::INFINITY);
+            if tentative_cost < existing_cost {
+                let next_label = EdgeLabel {
+                    cost: tentative_cost,
+                    edge_traversal: et,
+                    parent: Some(edge_id),
+                };
+                let h =
+                    estimate_cost(*next_edge_id, &next_label, target_vertex, weight_factor, si)?;
+                queue.push_increase(*next_edge_id, (tentative_cost + h).into());
+                labels.insert(*next_edge_id, next_label);
+            }
+        }
+    }
+
+    Ok((None, labels, iterations))
+}
+
+/// estimates the cost from the end of the label's edge to the target vertex
+fn estimate_cost(
+    edge_id: EdgeId,
+    label: &EdgeLabel,
+    target: Option<VertexId>,
+    weight_factor: Option<Cost>,
+    si: &SearchInstance,
+) -> Result<Cost, SearchError> {
+    match target {
+        None => Ok(Cost::ZERO),
+        Some(target_vertex) => {
+            let vertex_id = si.graph.dst_vertex_id(&edge_id)?;
+            let cost_est = si.estimate_traversal_cost(
+                vertex_id,
+                target_vertex,
+                &label.edge_traversal.result_state,
+            )?;
+            Ok(Cost::new(
+                cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64(),
+            ))
+        }
+    }
+}
+
+/// follows parent labels back to the first edge, producing the route in forward order
+fn backtrack(
+    edge_id: EdgeId,
+    labels: &HashMap<EdgeId, EdgeLabel>,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let mut route = vec![];
+    let mut current = Some(edge_id);
+    while let Some(id) = current {
+        let label = labels.get(&id).ok_or_else(|| {
+            SearchError::InternalError(format!("label for edge {} missing during backtrack", id))
+        })?;
+        if route.len() > labels.len() {
+            return Err(SearchError::InternalError(format!(
+                "loop in edge-based search result at edge {}",
+                id
+            )));
+        }
+        route.push(label.edge_traversal.clone());
+        current = label.parent;
+    }
+    route.reverse();
+    Ok(route)
+}
+
+/// summarizes the edge labels as a tree keyed by vertex, keeping the least-cost label
+/// of the edges arriving at each vertex
+fn vertex_tree(
+    labels: &HashMap<EdgeId, EdgeLabel>,
+    si: &SearchInstance,
+) -> Result<MinSearchTree, SearchError> {
+    let mut best: HashMap<VertexId, &EdgeLabel> = HashMap::new();
+    for (edge_id, label) in labels.iter() {
+        let dst = si.graph.dst_vertex_id(edge_id)?;
+        match best.get(&dst) {
+            Some(existing) if existing.cost <= label.cost => {}
+            _ => {
+                best.insert(dst, label);
+            }
+        }
+    }
+    best.into_iter()
+        .map(|(vertex_id, label)| {
+            let src = si.graph.src_vertex_id(&label.edge_traversal.edge_id)?;
+            let branch = SearchTreeBranch {
+                terminal_vertex: src,
+                edge_traversal: label.edge_traversal.clone(),
+            };
+            Ok((vertex_id, branch))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
+    use crate::model::frontier::{FrontierModel, FrontierModelError};
+    use crate::model::network::Edge;
+    use crate::model::state::{StateModel, StateVariable};
+    use crate::model::termination::TerminationModel;
+    use std::sync::Arc;
+
+    /// prohibits turning from one edge onto another, as a turn restriction would
+    struct MockTurnRestriction {
+        prev_edge_id: EdgeId,
+        next_edge_id: EdgeId,
+    }
+
+    impl FrontierModel for MockTurnRestriction {
+        fn valid_frontier(
+            &self,
+            edge: &Edge,
+            _state: &[StateVariable],
+            tree: &HashMap<VertexId, SearchTreeBranch>,
+            _direction: &Direction,
+            _state_model: &StateModel,
+        ) -> Result<bool, FrontierModelError> {
+            match tree.get(&edge.src_vertex_id) {
+                Some(branch) => Ok(!(branch.edge_traversal.edge_id == self.prev_edge_id
+                    && edge.edge_id == self.next_edge_id)),
+                None => Ok(true),
+            }
+        }
+
+        fn valid_edge(&self, _edge: &Edge) -> Result<bool, FrontierModelError> {
+            Ok(true)
+        }
+    }
+
+    /// the turn from edge 0 onto edge 1 is restricted. the only legal approach to edge 1
+    /// is the longer path through vertex 4, so a vertex-keyed tree, which keeps edge 0 as
+    /// the best way into vertex 1, cannot find a route past vertex 1. edge 5 allows a
+    /// u-turn back to vertex 0.
+    ///
+    ///   0 -[0]-> 1 -[1]-> 2 -[2]-> 3
+    ///   0 -[3]-> 4 -[4]-> 1
+    ///   1 -[5]-> 0
+    fn build_mock_search_instance() -> SearchInstance {
+        let vertices = build_vertices(5);
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 1.0),
+            Edge::new(2, 2, 3, 1.0),
+            Edge::new(3, 0, 4, 1.0),
+            Edge::new(4, 4, 1, 1.0),
+            Edge::new(5, 1, 0, 1.0),
+        ];
+        let mut si = build_search_instance(
+            build_graph(vertices, edges),
+            TerminationModel::IterationsLimit { limit: 100 },
+        );
+        si.frontier_model = Arc::new(MockTurnRestriction {
+            prev_edge_id: EdgeId(0),
+            next_edge_id: EdgeId(1),
+        });
+        si
+    }
+
+    fn edge_ids(route: &[EdgeTraversal]) -> Vec<usize> {
+        route.iter().map(|et| et.edge_id.0).collect()
+    }
+
+    #[test]
+    fn test_vertex_oriented_respects_turn_restriction() {
+        let si = build_mock_search_instance();
+
+        // a vertex-keyed search loses the legal approach to vertex 1
+        let vertex_keyed = a_star::run_vertex_oriented(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            Some(Cost::ZERO),
+            None,
+            &[],
+            &si,
+        );
+        assert!(vertex_keyed.is_err());
+
+        let result =
+            run_vertex_oriented(VertexId(0), Some(VertexId(3)), Some(Cost::ZERO), &si).unwrap();
+        assert_eq!(result.routes.len(), 1);
+        assert_eq!(edge_ids(&result.routes[0]), vec![3, 4, 1, 2]);
+        assert_eq!(result.trees.len(), 1);
+    }
+
+    #[test]
+    fn test_edge_oriented_respects_turn_restriction() {
+        let si = build_mock_search_instance();
+        let result = run_edge_oriented(EdgeId(0), Some(EdgeId(2)), Some(Cost::ZERO), &si).unwrap();
+        assert_eq!(result.routes.len(), 1);
+        let route = &result.routes[0];
+        assert_eq!(edge_ids(route), vec![0, 5, 3, 4, 1, 2]);
+        // the source and target edges carry no cost
+        let cost = route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+        assert_eq!(cost, 4.0);
+    }
+}
@@ -0,0 +1 @@
+pub mod edge_based_algorithm;
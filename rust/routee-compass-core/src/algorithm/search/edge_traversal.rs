@@ -76,11 +76,14 @@ impl EdgeTraversal {
             access_cost = access_cost + ac;
         }
 
-        si.traversal_model.traverse_edge(
-            traversal_trajectory,
-            &mut result_state,
-            &si.state_model,
-        )?;
+        match &si.edge_delta_cache {
+            Some(cache) => cache.apply(&traversal_trajectory.1.edge_id, &mut result_state)?,
+            None => si.traversal_model.traverse_edge(
+                traversal_trajectory,
+                &mut result_state,
+                &si.state_model,
+            )?,
+        }
 
         let (_, edge, _) = traversal_trajectory;
         let total_cost = si
@@ -146,11 +149,14 @@ impl EdgeTraversal {
             access_cost = access_cost + ac;
         }
 
-        si.traversal_model.traverse_edge(
-            traversal_trajectory,
-            &mut result_state,
-            &si.state_model,
-        )?;
+        match &si.edge_delta_cache {
+            Some(cache) => cache.apply(&traversal_trajectory.1.edge_id, &mut result_state)?,
+            None => si.traversal_model.traverse_edge(
+                traversal_trajectory,
+                &mut result_state,
+                &si.state_model,
+            )?,
+        }
 
         let (_, edge, _) = traversal_trajectory;
         let total_cost = si
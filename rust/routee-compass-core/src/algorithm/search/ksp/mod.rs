@@ -1,5 +1,6 @@
 mod ksp_query;
 mod ksp_termination_criteria;
+pub mod penalty;
 pub mod svp;
 pub mod yens;
 
@@ -0,0 +1,259 @@
+use super::{ksp_query::KspQuery, ksp_termination_criteria::KspTerminationCriteria};
+use crate::{
+    algorithm::search::{
+        direction::Direction, edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        search_instance::SearchInstance, util::RouteSimilarityFunction,
+    },
+    model::network::edge_id::EdgeId,
+};
+use itertools::Itertools;
+use std::{collections::HashMap, sync::Arc};
+
+/// generates a set of k-shortest paths using the penalty method. after each search, the
+/// traversal costs of the edges along the route found are multiplied by the penalty factor
+/// and the underlying search is run again, steering later searches onto other edges. edges
+/// used by several routes are penalized once per route.
+///
+/// routes are re-traversed with the original cost model so that they report their true
+/// costs. a route is accepted if it is not too similar to any accepted route. the search
+/// ends when the termination criteria is met, when no route is found, or when a search
+/// returns a route that was already found, as further penalties will not produce new routes.
+///
+/// # Arguments
+///
+/// * `query`          - the ksp query
+/// * `penalty_factor` - cost multiplier for edges of routes already found, greater than 1
+/// * `termination`    - criteria for stopping the search
+/// * `similarity`     - test for rejecting routes too similar to accepted routes
+/// * `si`             - the search assets for this query
+/// * `underlying`     - the search algorithm to run at each iteration
+///
+/// # Returns
+///
+/// The search tree of the true shortest path, along with all paths accepted
+pub fn run(
+    query: &KspQuery,
+    penalty_factor: f64,
+    termination: &KspTerminationCriteria,
+    similarity: &RouteSimilarityFunction,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if penalty_factor <= 1.0 {
+        return Err(SearchError::BuildError(format!(
+            "ksp penalty factor must be greater than 1, found {}",
+            penalty_factor
+        )));
+    }
+
+    // base case: we always have the true-shortest path
+    let shortest = underlying.run_vertex_oriented(
+        query.source,
+        Some(query.target),
+        query.user_query,
+        &Direction::Forward,
+        si,
+    )?;
+    let shortest_path = match shortest.routes.first() {
+        None => return Ok(SearchAlgorithmResult::default()),
+        Some(route) => route.to_owned(),
+    };
+    let mut penalties: HashMap<EdgeId, f64> = HashMap::new();
+    add_penalties(&shortest_path, penalty_factor, &mut penalties);
+    let mut found: Vec<Vec<EdgeId>> = vec![edge_ids(&shortest_path)];
+    let mut accepted: Vec<Vec<EdgeTraversal>> = vec![shortest_path];
+    let mut iterations: u64 = 1; // number of times we call underlying search
+
+    while !termination.terminate_search(query.k, accepted.len()) {
+        let penalty_cost_model = si
+            .cost_model
+            .with_edge_penalties(Arc::new(penalties.clone()))?;
+        let penalty_si = SearchInstance {
+            graph: si.graph.clone(),
+            map_model: si.map_model.clone(),
+            state_model: si.state_model.clone(),
+            traversal_model: si.traversal_model.clone(),
+            traversal_config: si.traversal_config.clone(),
+            default_preprocessing_info: si.default_preprocessing_info.clone(),
+            edge_delta_cache: si.edge_delta_cache.clone(),
+            access_model: si.access_model.clone(),
+            cost_model: Arc::new(penalty_cost_model),
+            frontier_model: si.frontier_model.clone(),
+            termination_model: si.termination_model.clone(),
+        };
+        let result = underlying.run_vertex_oriented(
+            query.source,
+            Some(query.target),
+            query.user_query,
+            &Direction::Forward,
+            &penalty_si,
+        )?;
+        iterations += 1;
+
+        let penalized_route = match result.routes.first() {
+            None => break,
+            Some(route) => route,
+        };
+        let route_edge_ids = edge_ids(penalized_route);
+        if found.contains(&route_edge_ids) {
+            log::debug!("ksp penalty:{} route already found, quitting", iterations);
+            break;
+        }
+        add_penalties(penalized_route, penalty_factor, &mut penalties);
+        found.push(route_edge_ids.clone());
+
+        let candidate = retraverse(&route_edge_ids, si)?;
+        let mut accept_route = true;
+        for accepted_route in accepted.iter() {
+            let too_similar = similarity.clone().test_similarity(
+                &candidate.iter().collect_vec(),
+                &accepted_route.iter().collect_vec(),
+                si,
+            )?;
+            if too_similar {
+                log::debug!("ksp penalty:{} too similar", iterations);
+                accept_route = false;
+                break;
+            }
+        }
+        if accept_route {
+            log::debug!("ksp penalty:{} alternative accepted", iterations);
+            accepted.push(candidate);
+        }
+    }
+
+    let routes = accepted.into_iter().take(query.k).collect_vec();
+    let result = SearchAlgorithmResult {
+        trees: shortest.trees,
+        routes,
+        iterations,
+        suboptimality_bound: None,
+    };
+    Ok(result)
+}
+
+/// multiplies the penalty of each edge along the route by the penalty factor
+fn add_penalties(
+    route: &[EdgeTraversal],
+    penalty_factor: f64,
+    penalties: &mut HashMap<EdgeId, f64>,
+) {
+    for et in route.iter() {
+        let penalty = penalties.entry(et.edge_id).or_insert(1.0);
+        *penalty *= penalty_factor;
+    }
+}
+
+fn edge_ids(route: &[EdgeTraversal]) -> Vec<EdgeId> {
+    route.iter().map(|et| et.edge_id).collect_vec()
+}
+
+/// traverses the edges of a route from the initial state using this search instance
+fn retraverse(route: &[EdgeId], si: &SearchInstance) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let mut state = si.state_model.initial_state()?;
+    let mut prev_edge_id = None;
+    let mut result = Vec::with_capacity(route.len());
+    for edge_id in route.iter() {
+        let et = EdgeTraversal::forward_traversal(*edge_id, prev_edge_id, &state, si)?;
+        state = et.result_state.clone();
+        prev_edge_id = Some(*edge_id);
+        result.push(et);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::ksp::KspTerminationCriteria;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
+    use crate::model::network::{Edge, VertexId};
+    use crate::model::termination::TerminationModel;
+    use crate::model::unit::AsF64;
+
+    /// three parallel routes from 0 to 3 with distances 2, 3 and 4, plus a near-duplicate
+    /// of the shortest route through vertex 4 that shares its first edge.
+    ///
+    ///   0 -[0]-> 1 -[1]-> 3            (1 + 1)
+    ///   0 -[2]-> 2 -[3]-> 3            (1.5 + 1.5)
+    ///   0 -[4]-> 5 -[5]-> 3            (2 + 2)
+    ///   0 -[0]-> 1 -[6]-> 4 -[7]-> 3   (1 + 0.6 + 0.6)
+    fn build_mock_search_instance() -> SearchInstance {
+        let vertices = build_vertices(6);
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 3, 1.0),
+            Edge::new(2, 0, 2, 1.5),
+            Edge::new(3, 2, 3, 1.5),
+            Edge::new(4, 0, 5, 2.0),
+            Edge::new(5, 5, 3, 2.0),
+            Edge::new(6, 1, 4, 0.6),
+            Edge::new(7, 4, 3, 0.6),
+        ];
+        build_search_instance(
+            build_graph(vertices, edges),
+            TerminationModel::IterationsLimit { limit: 100 },
+        )
+    }
+
+    fn run_penalty_ksp(k: usize, similarity: RouteSimilarityFunction) -> Vec<Vec<usize>> {
+        let si = build_mock_search_instance();
+        let user_query = serde_json::json!({});
+        let query = KspQuery::new(VertexId(0), VertexId(3), &user_query, k).unwrap();
+        let underlying = SearchAlgorithm::Dijkstra;
+        let result = run(
+            &query,
+            2.0,
+            &KspTerminationCriteria::Exact,
+            &similarity,
+            &si,
+            &underlying,
+        )
+        .unwrap();
+        for route in result.routes.iter() {
+            // routes report their true, unpenalized costs
+            let cost = route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+            let distance = route
+                .iter()
+                .map(|et| si.graph.get_edge(&et.edge_id).unwrap().distance.as_f64())
+                .sum::<f64>();
+            assert!((cost - distance).abs() < 1e-9);
+        }
+        result
+            .routes
+            .iter()
+            .map(|r| r.iter().map(|et| et.edge_id.0).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_penalty_alternatives() {
+        // with a penalty factor of 2, the shortest route (2) becomes 4 and the
+        // near-duplicate (2.2) becomes 3.2, so the 3-unit route is found second.
+        // only identical routes are considered similar.
+        let similarity = RouteSimilarityFunction::EdgeIdCosineSimilarity { threshold: 1.0 };
+        let routes = run_penalty_ksp(3, similarity);
+        assert_eq!(routes, vec![vec![0, 1], vec![2, 3], vec![0, 6, 7]]);
+    }
+
+    #[test]
+    fn test_penalty_rejects_similar_routes() {
+        // the near-duplicate shares half of its distance with the shortest route
+        let similarity =
+            RouteSimilarityFunction::DistanceWeightedCosineSimilarity { threshold: 0.3 };
+        let routes = run_penalty_ksp(3, similarity);
+        assert_eq!(routes, vec![vec![0, 1], vec![2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_invalid_edge_penalties() {
+        let si = build_mock_search_instance();
+        for penalty in [0.5, 0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let penalties = Arc::new(HashMap::from([(EdgeId(0), penalty)]));
+            assert!(si.cost_model.with_edge_penalties(penalties).is_err());
+        }
+        let penalties = Arc::new(HashMap::from([(EdgeId(0), 1.0)]));
+        assert!(si.cost_model.with_edge_penalties(penalties).is_ok());
+    }
+}
@@ -24,6 +24,7 @@ pub fn run(
         trees: fwd_trees,
         routes: _,
         iterations: fwd_iterations,
+        suboptimality_bound: _,
     } = underlying.run_vertex_oriented(
         query.source,
         Some(query.target),
@@ -35,6 +36,7 @@ pub fn run(
         trees: rev_trees,
         routes: _,
         iterations: rev_iterations,
+        suboptimality_bound: _,
     } = underlying.run_vertex_oriented(
         query.target,
         Some(query.source),
@@ -155,6 +157,7 @@ pub fn run(
         trees: vec![fwd_tree.clone(), rev_tree.clone()], // todo: figure out how to avoid this clone
         routes,
         iterations: fwd_iterations + rev_iterations + ksp_it, // todo: figure out how to report individually
+        suboptimality_bound: None,
     };
     Ok(result)
 }
@@ -171,3 +174,54 @@ fn test_id_similarity(a: &[EdgeTraversal], b: &[EdgeTraversal]) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
+    use crate::model::network::Edge;
+    use crate::model::termination::TerminationModel;
+
+    /// two parallel routes from 0 to 2 with distances 2 and 3
+    fn run_svp(k: usize, similarity: RouteSimilarityFunction) -> Vec<Vec<usize>> {
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 1.0),
+            Edge::new(2, 0, 3, 1.5),
+            Edge::new(3, 3, 2, 1.5),
+        ];
+        let si = build_search_instance(
+            build_graph(build_vertices(4), edges),
+            TerminationModel::IterationsLimit { limit: 100 },
+        );
+        let user_query = serde_json::json!({});
+        let query = KspQuery::new(VertexId(0), VertexId(2), &user_query, k).unwrap();
+        let result = run(
+            &query,
+            &KspTerminationCriteria::Exact,
+            &similarity,
+            &si,
+            &SearchAlgorithm::Dijkstra,
+        )
+        .unwrap();
+        result
+            .routes
+            .iter()
+            .map(|r| r.iter().map(|et| et.edge_id.0).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_similarity_returns_shortest_route() {
+        // the default similarity function treats every pair of routes as similar
+        let routes = run_svp(2, RouteSimilarityFunction::default());
+        assert_eq!(routes, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_dissimilar_route_accepted() {
+        let similarity = RouteSimilarityFunction::EdgeIdCosineSimilarity { threshold: 1.0 };
+        let routes = run_svp(2, similarity);
+        assert_eq!(routes, vec![vec![0, 1], vec![2, 3]]);
+    }
+}
@@ -89,6 +89,9 @@ pub fn run(
                 map_model: si.map_model.clone(),
                 state_model: si.state_model.clone(),
                 traversal_model: si.traversal_model.clone(),
+                traversal_config: si.traversal_config.clone(),
+                default_preprocessing_info: si.default_preprocessing_info.clone(),
+                edge_delta_cache: si.edge_delta_cache.clone(),
                 access_model: si.access_model.clone(),
                 cost_model: si.cost_model.clone(),
                 frontier_model: Arc::new(yens_frontier),
@@ -141,6 +144,7 @@ pub fn run(
         trees: shortest.trees,
         routes: accepted,
         iterations,
+        suboptimality_bound: None,
     };
     Ok(result)
 }
@@ -0,0 +1,14 @@
+use crate::model::network::VertexId;
+use crate::model::unit::Cost;
+use serde::{Deserialize, Serialize};
+
+/// a row in a landmark cost file. records the shortest path costs between
+/// one landmark and one vertex, in both directions. a cost is omitted when
+/// no path exists in that direction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LandmarkCostRow {
+    pub landmark_vertex_id: VertexId,
+    pub vertex_id: VertexId,
+    pub cost_from_landmark: Option<Cost>,
+    pub cost_to_landmark: Option<Cost>,
+}
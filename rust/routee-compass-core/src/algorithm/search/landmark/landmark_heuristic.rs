@@ -0,0 +1,428 @@
+use super::landmark_cost_row::LandmarkCostRow;
+use super::landmark_selection;
+use crate::algorithm::search::{SearchError, SearchInstance};
+use crate::model::network::VertexId;
+use crate::model::unit::Cost;
+use crate::util::fs::read_utils;
+use kdam::Bar;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// file locations of a persisted [`LandmarkHeuristic`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LandmarkHeuristicConfig {
+    /// csv file of [`LandmarkCostRow`] records
+    pub landmark_cost_input_file: String,
+    /// json file with the cost and traversal model description used during preprocessing
+    pub cost_info_input_file: String,
+}
+
+/// the ALT (A*, landmarks, triangle inequality) cost estimate.
+///
+/// for a set of landmark vertices, the shortest path cost from each landmark to every
+/// vertex and from every vertex to each landmark is precomputed. by the triangle
+/// inequality, for any landmark L the cost of the shortest path from v to t is at least
+/// `d(L, t) - d(L, v)` and at least `d(v, L) - d(t, L)`. the largest such bound over all
+/// landmarks is an admissible estimate that is usually far tighter than one based on
+/// straight-line distance.
+///
+/// the tables are only valid for the edge costs used to build them. at query time,
+/// [`LandmarkHeuristic::is_compatible`] compares the query's cost and traversal models
+/// with the ones recorded during preprocessing, and the search falls back to the
+/// traversal model estimate when they differ or when the query's edge costs vary by
+/// query, such as with speed overrides. edge costs must not depend on the search state.
+#[derive(Deserialize)]
+#[serde(try_from = "LandmarkHeuristicConfig")]
+pub struct LandmarkHeuristic {
+    config: Option<LandmarkHeuristicConfig>,
+    landmarks: Vec<VertexId>,
+    cost_info: serde_json::Value,
+    from_landmark: Vec<Box<[Cost]>>,
+    to_landmark: Vec<Box<[Cost]>>,
+}
+
+impl LandmarkHeuristic {
+    /// selects landmarks and computes their cost tables for the cost function of
+    /// the provided search instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `si`          - search instance used to compute the fixed edge costs
+    /// * `n_landmarks` - number of landmarks to select
+    ///
+    /// # Returns
+    ///
+    /// a landmark heuristic ready for queries or to be written to file
+    pub fn build(
+        si: &SearchInstance,
+        n_landmarks: usize,
+    ) -> Result<LandmarkHeuristic, SearchError> {
+        if !si.traversal_model.is_query_invariant() {
+            return Err(SearchError::BuildError(String::from(
+                "landmarks require a traversal model whose edge costs do not vary by query",
+            )));
+        }
+        let tables = landmark_selection::select_landmarks(si, n_landmarks)?;
+        let cost_info = si.preprocessing_info()?;
+        Ok(LandmarkHeuristic {
+            config: None,
+            landmarks: tables.landmarks,
+            cost_info,
+            from_landmark: tables.from_landmark,
+            to_landmark: tables.to_landmark,
+        })
+    }
+
+    /// loads a landmark heuristic from a landmark cost file and a cost info file.
+    /// the landmark cost file may be gzipped.
+    pub fn from_files<P: AsRef<Path>>(
+        landmark_cost_file: P,
+        cost_info_file: P,
+    ) -> Result<LandmarkHeuristic, String> {
+        let cost_info_str = std::fs::read_to_string(&cost_info_file).map_err(|e| {
+            format!(
+                "failure reading landmark cost info from {}: {}",
+                cost_info_file.as_ref().to_string_lossy(),
+                e
+            )
+        })?;
+        let cost_info: serde_json::Value = serde_json::from_str(&cost_info_str)
+            .map_err(|e| format!("failure decoding landmark cost info: {}", e))?;
+
+        let rows: Box<[LandmarkCostRow]> = read_utils::from_csv(
+            &landmark_cost_file,
+            true,
+            Some(Bar::builder().desc("landmark costs")),
+            None,
+        )
+        .map_err(|e| {
+            format!(
+                "failure reading landmark costs from {}: {}",
+                landmark_cost_file.as_ref().to_string_lossy(),
+                e
+            )
+        })?;
+
+        let n_vertices = rows
+            .iter()
+            .map(|r| r.vertex_id.0 + 1)
+            .max()
+            .unwrap_or_default();
+        let mut landmarks: Vec<VertexId> = vec![];
+        let mut landmark_index: HashMap<VertexId, usize> = HashMap::new();
+        let mut from_landmark: Vec<Box<[Cost]>> = vec![];
+        let mut to_landmark: Vec<Box<[Cost]>> = vec![];
+        for row in rows.iter() {
+            let idx = *landmark_index
+                .entry(row.landmark_vertex_id)
+                .or_insert_with(|| {
+                    landmarks.push(row.landmark_vertex_id);
+                    from_landmark.push(vec![Cost::INFINITY; n_vertices].into_boxed_slice());
+                    to_landmark.push(vec![Cost::INFINITY; n_vertices].into_boxed_slice());
+                    landmarks.len() - 1
+                });
+            if let Some(cost) = row.cost_from_landmark {
+                from_landmark[idx][row.vertex_id.0] = cost;
+            }
+            if let Some(cost) = row.cost_to_landmark {
+                to_landmark[idx][row.vertex_id.0] = cost;
+            }
+        }
+
+        let config = LandmarkHeuristicConfig {
+            landmark_cost_input_file: landmark_cost_file.as_ref().to_string_lossy().to_string(),
+            cost_info_input_file: cost_info_file.as_ref().to_string_lossy().to_string(),
+        };
+        Ok(LandmarkHeuristic {
+            config: Some(config),
+            landmarks,
+            cost_info,
+            from_landmark,
+            to_landmark,
+        })
+    }
+
+    /// writes this landmark heuristic to a landmark cost file and a cost info file
+    /// which can later be loaded with [`LandmarkHeuristic::from_files`].
+    pub fn write_files<P: AsRef<Path>>(
+        &self,
+        landmark_cost_file: P,
+        cost_info_file: P,
+    ) -> Result<(), String> {
+        let cost_info_str = serde_json::to_string_pretty(&self.cost_info)
+            .map_err(|e| format!("failure encoding landmark cost info: {}", e))?;
+        std::fs::write(&cost_info_file, cost_info_str)
+            .map_err(|e| format!("failure writing landmark cost info: {}", e))?;
+
+        let mut writer = csv::Writer::from_path(&landmark_cost_file)
+            .map_err(|e| format!("failure opening landmark cost file: {}", e))?;
+        let finite = |c: Cost| if c < Cost::INFINITY { Some(c) } else { None };
+        for (idx, landmark_vertex_id) in self.landmarks.iter().enumerate() {
+            let from = &self.from_landmark[idx];
+            let to = &self.to_landmark[idx];
+            for vertex_idx in 0..from.len() {
+                let row = LandmarkCostRow {
+                    landmark_vertex_id: *landmark_vertex_id,
+                    vertex_id: VertexId(vertex_idx),
+                    cost_from_landmark: finite(from[vertex_idx]),
+                    cost_to_landmark: finite(to[vertex_idx]),
+                };
+                writer
+                    .serialize(row)
+                    .map_err(|e| format!("failure writing landmark cost row: {}", e))?;
+            }
+        }
+        writer
+            .flush()
+            .map_err(|e| format!("failure writing landmark cost file: {}", e))?;
+        Ok(())
+    }
+
+    /// the landmark vertices of this heuristic
+    pub fn landmarks(&self) -> &[VertexId] {
+        &self.landmarks
+    }
+
+    /// tests if the tables can be used with the given search instance. they are
+    /// compatible when they cover every vertex in the graph, the query's edge costs
+    /// do not vary by query and its cost and traversal models match the ones used
+    /// during preprocessing.
+    pub fn is_compatible(&self, si: &SearchInstance) -> Result<bool, SearchError> {
+        let n_vertices = si.graph.n_vertices();
+        if self.from_landmark.iter().any(|t| t.len() != n_vertices) {
+            log::warn!(
+                "landmark tables do not match graph with {} vertices, ignoring landmarks",
+                n_vertices
+            );
+            return Ok(false);
+        }
+        if !si.matches_preprocessing_info(&self.cost_info)? {
+            log::debug!(
+                "query cost or traversal model differs from landmark preprocessing, ignoring landmarks"
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// computes a lower bound on the cost of the shortest path from src to dst.
+    pub fn estimate(&self, src: VertexId, dst: VertexId) -> Cost {
+        let mut estimate = Cost::ZERO;
+        for (from, to) in self.from_landmark.iter().zip(self.to_landmark.iter()) {
+            // d(L, dst) - d(L, src)
+            let (l_src, l_dst) = (from[src.0], from[dst.0]);
+            if l_src < Cost::INFINITY && l_dst < Cost::INFINITY {
+                estimate = estimate.max(l_dst - l_src);
+            }
+            // d(src, L) - d(dst, L)
+            let (src_l, dst_l) = (to[src.0], to[dst.0]);
+            if src_l < Cost::INFINITY && dst_l < Cost::INFINITY {
+                estimate = estimate.max(src_l - dst_l);
+            }
+        }
+        estimate
+    }
+}
+
+impl TryFrom<LandmarkHeuristicConfig> for LandmarkHeuristic {
+    type Error = String;
+
+    fn try_from(config: LandmarkHeuristicConfig) -> Result<Self, Self::Error> {
+        LandmarkHeuristic::from_files(
+            &config.landmark_cost_input_file,
+            &config.cost_info_input_file,
+        )
+    }
+}
+
+impl Serialize for LandmarkHeuristic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match &self.config {
+            Some(config) => config.serialize(serializer),
+            None => Err(serde::ser::Error::custom(
+                "landmark heuristic was not loaded from files and cannot be serialized as configuration",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::test_util::{
+        build_graph, build_search_instance_with_model, build_vertices,
+    };
+    use crate::algorithm::search::{a_star, backtrack, Direction, EdgeTraversal};
+    use crate::model::network::Edge;
+    use crate::model::state::StateFeature;
+    use crate::model::termination::TerminationModel;
+    use crate::model::traversal::default::DistanceTraversalModel;
+    use crate::model::unit::{Distance, DistanceUnit};
+    use std::sync::Arc;
+
+    /// a 3x3 grid of edges with varying lengths. the edges between 1 and 4 are
+    /// one-way so that the landmark bounds are asymmetric. all vertices share a
+    /// coordinate so the traversal model estimate is zero.
+    fn build_grid_search_instance(distance_weight: f64) -> SearchInstance {
+        let pairs = [
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (3, 4, 3.0),
+            (4, 5, 1.0),
+            (6, 7, 2.0),
+            (7, 8, 1.0),
+            (0, 3, 2.0),
+            (3, 6, 1.0),
+            (4, 7, 1.0),
+            (2, 5, 1.0),
+            (5, 8, 3.0),
+        ];
+        let mut edges = vec![];
+        for (src, dst, distance) in pairs {
+            edges.push(Edge::new(edges.len(), src, dst, distance));
+            edges.push(Edge::new(edges.len(), dst, src, distance));
+        }
+        edges.push(Edge::new(edges.len(), 1, 4, 1.0));
+        build_search_instance_with_model(
+            build_graph(build_vertices(9), edges),
+            Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+            vec![(
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Kilometers,
+                    initial: Distance::new(0.0),
+                },
+            )],
+            distance_weight,
+            TerminationModel::IterationsLimit { limit: 1000 },
+        )
+    }
+
+    fn route_cost(route: &[EdgeTraversal]) -> Cost {
+        route
+            .iter()
+            .fold(Cost::ZERO, |acc, et| acc + et.total_cost())
+    }
+
+    fn shortest_route(
+        o: VertexId,
+        d: VertexId,
+        landmarks: Option<&LandmarkHeuristic>,
+        si: &SearchInstance,
+    ) -> Vec<EdgeTraversal> {
+        let tree =
+            a_star::run_vertex_oriented(o, Some(d), &Direction::Forward, None, landmarks, &[], si)
+                .unwrap()
+                .tree;
+        backtrack::vertex_oriented_route(o, d, &tree).unwrap()
+    }
+
+    #[test]
+    fn test_estimate_is_admissible() {
+        let si = build_grid_search_instance(1.0);
+        let landmarks = LandmarkHeuristic::build(&si, 3).unwrap();
+        assert_eq!(landmarks.landmarks().len(), 3);
+        for src in 0..9 {
+            for dst in 0..9 {
+                if src == dst {
+                    continue;
+                }
+                let (o, d) = (VertexId(src), VertexId(dst));
+                let expected = route_cost(&shortest_route(o, d, None, &si));
+                let estimate = landmarks.estimate(o, d);
+                assert!(
+                    estimate <= expected,
+                    "estimate {} exceeds cost {} for {} -> {}",
+                    estimate,
+                    expected,
+                    o,
+                    d
+                );
+                let result = route_cost(&shortest_route(o, d, Some(&landmarks), &si));
+                assert_eq!(result, expected, "{} -> {}", o, d);
+            }
+        }
+        // landmarks are exact for paths that end at a landmark
+        let landmark = landmarks.landmarks()[0];
+        let o = if landmark == VertexId(0) {
+            VertexId(8)
+        } else {
+            VertexId(0)
+        };
+        let expected = route_cost(&shortest_route(o, landmark, None, &si));
+        assert_eq!(landmarks.estimate(o, landmark), expected);
+    }
+
+    #[test]
+    fn test_incompatible_cost_model() {
+        let si = build_grid_search_instance(1.0);
+        let landmarks = LandmarkHeuristic::build(&si, 2).unwrap();
+        assert!(landmarks.is_compatible(&si).unwrap());
+        let query_si = build_grid_search_instance(2.0);
+        assert!(!landmarks.is_compatible(&query_si).unwrap());
+        let result = shortest_route(VertexId(0), VertexId(8), Some(&landmarks), &query_si);
+        let expected = shortest_route(VertexId(0), VertexId(8), None, &query_si);
+        assert_eq!(route_cost(&result), route_cost(&expected));
+    }
+
+    #[test]
+    fn test_incompatible_traversal_model() {
+        let si = build_grid_search_instance(1.0);
+        let landmarks = LandmarkHeuristic::build(&si, 2).unwrap();
+        let mut query_si = build_grid_search_instance(1.0);
+        query_si.traversal_config = Arc::new(serde_json::json!({"type": "speed_table"}));
+        assert!(!landmarks.is_compatible(&query_si).unwrap());
+    }
+
+    #[test]
+    fn test_default_preprocessing_info() {
+        let si = build_grid_search_instance(1.0);
+        let landmarks = LandmarkHeuristic::build(&si, 2).unwrap();
+        // the shared description is compared instead of the query's models
+        let mut query_si = build_grid_search_instance(2.0);
+        query_si.default_preprocessing_info = Some(Arc::new(si.preprocessing_info().unwrap()));
+        assert!(landmarks.is_compatible(&query_si).unwrap());
+        query_si.default_preprocessing_info =
+            Some(Arc::new(query_si.preprocessing_info().unwrap()));
+        assert!(!landmarks.is_compatible(&query_si).unwrap());
+    }
+
+    #[test]
+    fn test_write_and_read_files() {
+        let si = build_grid_search_instance(1.0);
+        let landmarks = LandmarkHeuristic::build(&si, 2).unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "routee_compass_landmark_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cost_file = dir.join("landmark_costs.csv");
+        let cost_info_file = dir.join("landmark_cost_info.json");
+        landmarks.write_files(&cost_file, &cost_info_file).unwrap();
+
+        let config = serde_json::json!({
+            "landmark_cost_input_file": cost_file.to_string_lossy(),
+            "cost_info_input_file": cost_info_file.to_string_lossy(),
+        });
+        let loaded: LandmarkHeuristic = serde_json::from_value(config.clone()).unwrap();
+        assert_eq!(loaded.landmarks(), landmarks.landmarks());
+        assert!(loaded.is_compatible(&si).unwrap());
+        for src in 0..9 {
+            for dst in 0..9 {
+                let (o, d) = (VertexId(src), VertexId(dst));
+                assert_eq!(loaded.estimate(o, d), landmarks.estimate(o, d));
+            }
+        }
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), config);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
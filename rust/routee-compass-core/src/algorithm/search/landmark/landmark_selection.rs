@@ -0,0 +1,155 @@
+use crate::algorithm::search::{Direction, EdgeTraversal, SearchError, SearchInstance};
+use crate::model::network::VertexId;
+use crate::model::unit::{Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+
+/// shortest path cost tables for each landmark, indexed by VertexId
+pub struct LandmarkTables {
+    pub landmarks: Vec<VertexId>,
+    pub from_landmark: Vec<Box<[Cost]>>,
+    pub to_landmark: Vec<Box<[Cost]>>,
+}
+
+/// selects landmarks with the "farthest" strategy and computes their cost tables.
+///
+/// the first landmark is the vertex farthest from vertex 0. each following landmark
+/// is the vertex whose round-trip cost to its nearest landmark is greatest, which
+/// spreads landmarks toward the periphery of the network where they give the
+/// tightest bounds.
+///
+/// edge costs are computed once by traversing each edge from the initial search state
+/// with no previous edge. frontier restrictions are ignored so that the tables remain
+/// lower bounds for any query that restricts the network further.
+///
+/// # Arguments
+///
+/// * `si`          - search instance used to compute edge costs
+/// * `n_landmarks` - number of landmarks to select
+///
+/// # Returns
+///
+/// the selected landmarks and their cost tables
+pub fn select_landmarks(
+    si: &SearchInstance,
+    n_landmarks: usize,
+) -> Result<LandmarkTables, SearchError> {
+    let n_vertices = si.graph.n_vertices();
+    if n_vertices == 0 || n_landmarks == 0 {
+        return Err(SearchError::BuildError(String::from(
+            "landmark selection requires at least one vertex and one landmark",
+        )));
+    }
+
+    let initial_state = si.state_model.initial_state()?;
+    let edge_costs = si
+        .graph
+        .edge_ids()
+        .map(|edge_id| {
+            EdgeTraversal::forward_traversal(edge_id, None, &initial_state, si)
+                .map(|et| et.total_cost())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let initial = shortest_path_costs(VertexId(0), &Direction::Forward, &edge_costs, si);
+    let reachable = initial
+        .iter()
+        .map(|c| if *c < Cost::INFINITY { Some(*c) } else { None });
+    let mut next_landmark = farthest_vertex(reachable).unwrap_or(VertexId(0));
+
+    let mut tables = LandmarkTables {
+        landmarks: vec![],
+        from_landmark: vec![],
+        to_landmark: vec![],
+    };
+    // round-trip cost from each vertex to its nearest landmark
+    let mut nearest = vec![Cost::INFINITY; n_vertices];
+    while tables.landmarks.len() < n_landmarks.min(n_vertices) {
+        let from = shortest_path_costs(next_landmark, &Direction::Forward, &edge_costs, si);
+        let to = shortest_path_costs(next_landmark, &Direction::Reverse, &edge_costs, si);
+        for (idx, nearest_cost) in nearest.iter_mut().enumerate() {
+            *nearest_cost = (*nearest_cost).min(from[idx] + to[idx]);
+        }
+        tables.landmarks.push(next_landmark);
+        tables.from_landmark.push(from);
+        tables.to_landmark.push(to);
+
+        // vertices already selected have a nearest cost of zero and are never re-selected
+        // unless every remaining vertex is unreachable from the landmarks
+        let candidates = nearest.iter().enumerate().map(|(idx, c)| {
+            if tables.landmarks.contains(&VertexId(idx)) {
+                None
+            } else {
+                Some(*c)
+            }
+        });
+        next_landmark = match farthest_vertex(candidates) {
+            Some(v) => v,
+            None => break,
+        };
+    }
+
+    log::info!(
+        "selected {} landmarks: {:?}",
+        tables.landmarks.len(),
+        tables.landmarks.iter().map(|v| v.0).collect::<Vec<_>>()
+    );
+    Ok(tables)
+}
+
+/// finds the vertex with the greatest finite cost. unreachable vertices are only
+/// selected when no vertex has a finite cost, so that disconnected components
+/// eventually receive landmarks.
+fn farthest_vertex(costs: impl Iterator<Item = Option<Cost>>) -> Option<VertexId> {
+    let mut best: Option<(VertexId, Cost)> = None;
+    let mut unreachable: Option<VertexId> = None;
+    for (idx, cost) in costs.enumerate() {
+        match cost {
+            None => {}
+            Some(c) if c == Cost::INFINITY => {
+                unreachable = unreachable.or(Some(VertexId(idx)));
+            }
+            Some(c) => {
+                let improves = match best {
+                    Some((_, best_cost)) => c > best_cost,
+                    None => true,
+                };
+                if improves {
+                    best = Some((VertexId(idx), c));
+                }
+            }
+        }
+    }
+    match (unreachable, best) {
+        (Some(v), _) => Some(v),
+        (None, Some((v, _))) => Some(v),
+        (None, None) => None,
+    }
+}
+
+/// one-to-all dijkstra search over fixed edge costs. in the forward direction the
+/// result is the cost from the root to each vertex, in the reverse direction it is
+/// the cost from each vertex to the root. unreachable vertices have infinite cost.
+fn shortest_path_costs(
+    root: VertexId,
+    direction: &Direction,
+    edge_costs: &[Cost],
+    si: &SearchInstance,
+) -> Box<[Cost]> {
+    let mut costs = vec![Cost::INFINITY; si.graph.n_vertices()];
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    costs[root.0] = Cost::ZERO;
+    queue.push(root, Cost::ZERO.into());
+    while let Some((vertex_id, _)) = queue.pop() {
+        let cost = costs[vertex_id.0];
+        for edge_id in direction.get_incident_edges(&vertex_id, si) {
+            let edge = &si.graph.edges[edge_id.0];
+            let next = direction.tree_key_vertex_id(edge);
+            let tentative = cost + edge_costs[edge_id.0];
+            if tentative < costs[next.0] {
+                costs[next.0] = tentative;
+                queue.push_increase(next, tentative.into());
+            }
+        }
+    }
+    costs.into_boxed_slice()
+}
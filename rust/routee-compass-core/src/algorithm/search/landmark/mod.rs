@@ -0,0 +1,6 @@
+mod landmark_cost_row;
+mod landmark_heuristic;
+mod landmark_selection;
+
+pub use landmark_cost_row::LandmarkCostRow;
+pub use landmark_heuristic::{LandmarkHeuristic, LandmarkHeuristicConfig};
@@ -0,0 +1,247 @@
+use super::od_matrix::{OdMatrix, OdMatrixCell};
+use crate::algorithm::search::backtrack;
+use crate::algorithm::search::{
+    Direction, EdgeTraversal, MinSearchTree, SearchError, SearchInstance, SearchTreeBranch,
+};
+use crate::model::network::VertexId;
+use crate::model::unit::{Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// computes the least-cost path between every origin and every destination on a shared
+/// search instance. runs one one-to-many Dijkstra search per origin, which stops as soon
+/// as every destination has been settled.
+///
+/// # Arguments
+///
+/// * `origins`        - matrix row vertices
+/// * `destinations`   - matrix column vertices
+/// * `include_routes` - if true, the route of each origin-destination pair is stored
+/// * `si`             - the search assets for this query
+///
+/// # Returns
+///
+/// the cost, final state, and (optionally) route for each origin-destination pair
+pub fn run_vertex_oriented(
+    origins: &[VertexId],
+    destinations: &[VertexId],
+    include_routes: bool,
+    si: &SearchInstance,
+) -> Result<OdMatrix, SearchError> {
+    let mut cells = Vec::with_capacity(origins.len());
+    let mut iterations = 0;
+    for origin in origins.iter() {
+        let (tree, costs, search_iterations) = run_one_to_many(*origin, destinations, si)?;
+        iterations += search_iterations;
+        let row = destinations
+            .iter()
+            .map(|destination| build_cell(*origin, *destination, &tree, &costs, include_routes, si))
+            .collect::<Result<Vec<_>, _>>()?;
+        cells.push(row);
+    }
+
+    Ok(OdMatrix {
+        origins: origins.to_vec(),
+        destinations: destinations.to_vec(),
+        cells,
+        iterations,
+    })
+}
+
+/// runs a forward Dijkstra search from the source until all targets are settled or the
+/// graph is exhausted. returns the search tree, the settled cost of each vertex, and the
+/// number of iterations.
+fn run_one_to_many(
+    source: VertexId,
+    targets: &[VertexId],
+    si: &SearchInstance,
+) -> Result<(MinSearchTree, HashMap<VertexId, Cost>, u64), SearchError> {
+    let mut remaining: HashSet<VertexId> = targets.iter().cloned().collect();
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    let mut costs: HashMap<VertexId, Cost> = HashMap::from([(source, Cost::ZERO)]);
+    let mut settled: HashSet<VertexId> = HashSet::new();
+    let mut tree: MinSearchTree = HashMap::new();
+    let initial_state = si.state_model.initial_state()?;
+    queue.push(source, Cost::ZERO.into());
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+
+    while let Some((vertex_id, _)) = queue.pop() {
+        if remaining.is_empty() {
+            break;
+        }
+        si.termination_model
+            .test(&start_time, tree.len(), iterations)?;
+        iterations += 1;
+
+        settled.insert(vertex_id);
+        remaining.remove(&vertex_id);
+
+        let (current_state, last_edge_id) = match tree.get(&vertex_id) {
+            None => (initial_state.clone(), None),
+            Some(branch) => (
+                branch.edge_traversal.result_state.clone(),
+                Some(branch.edge_traversal.edge_id),
+            ),
+        };
+        let current_cost = costs.get(&vertex_id).cloned().unwrap_or(Cost::INFINITY);
+
+        for edge_id in si.graph.out_edges_iter(&vertex_id) {
+            let edge = si.graph.get_edge(edge_id)?;
+            if settled.contains(&edge.dst_vertex_id) {
+                continue;
+            }
+            let valid = si.frontier_model.valid_frontier(
+                edge,
+                &current_state,
+                &tree,
+                &Direction::Forward,
+                &si.state_model,
+            )?;
+            if !valid {
+                continue;
+            }
+            let et = EdgeTraversal::forward_traversal(*edge_id, last_edge_id, &current_state, si)?;
+            let tentative_cost = current_cost + et.total_cost();
+            let existing_cost = costs
+                .get(&edge.dst_vertex_id)
+                .cloned()
+                .unwrap_or(Cost::INFINITY);
+            if tentative_cost < existing_cost {
+                costs.insert(edge.dst_vertex_id, tentative_cost);
+                tree.insert(
+                    edge.dst_vertex_id,
+                    SearchTreeBranch {
+                        terminal_vertex: vertex_id,
+                        edge_traversal: et,
+                    },
+                );
+                queue.push_increase(edge.dst_vertex_id, tentative_cost.into());
+            }
+        }
+    }
+
+    Ok((tree, costs, iterations))
+}
+
+/// builds the matrix cell for an origin-destination pair from a one-to-many search tree
+fn build_cell(
+    origin: VertexId,
+    destination: VertexId,
+    tree: &MinSearchTree,
+    costs: &HashMap<VertexId, Cost>,
+    include_routes: bool,
+    si: &SearchInstance,
+) -> Result<Option<OdMatrixCell>, SearchError> {
+    if origin == destination {
+        let cell = OdMatrixCell {
+            cost: Cost::ZERO,
+            state: si.state_model.initial_state()?,
+            route: include_routes.then(Vec::new),
+        };
+        return Ok(Some(cell));
+    }
+    let (branch, cost) = match (tree.get(&destination), costs.get(&destination)) {
+        (Some(branch), Some(cost)) => (branch, *cost),
+        _ => return Ok(None),
+    };
+    let route = if include_routes {
+        Some(backtrack::vertex_oriented_route(origin, destination, tree)?)
+    } else {
+        None
+    };
+    let cell = OdMatrixCell {
+        cost,
+        state: branch.edge_traversal.result_state.clone(),
+        route,
+    };
+    Ok(Some(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star;
+    use crate::algorithm::search::test_util::{build_graph, build_search_instance, build_vertices};
+    use crate::model::network::Edge;
+    use crate::model::termination::TerminationModel;
+    use crate::model::unit::AsF64;
+
+    /// a small graph with edge distances shown. vertex 4 is unreachable.
+    ///
+    ///   3 --2--> 0 --1--> 1 --1--> 2
+    ///            |                 ^
+    ///            +--------5--------+
+    fn build_mock_search_instance() -> SearchInstance {
+        let vertices = build_vertices(5);
+        let edges = vec![
+            Edge::new(0, 0, 1, 1.0),
+            Edge::new(1, 1, 2, 1.0),
+            Edge::new(2, 0, 2, 5.0),
+            Edge::new(3, 3, 0, 2.0),
+        ];
+        build_search_instance(
+            build_graph(vertices, edges),
+            TerminationModel::IterationsLimit { limit: 100 },
+        )
+    }
+
+    #[test]
+    fn test_matrix_costs() {
+        let si = build_mock_search_instance();
+        let origins = [VertexId(0), VertexId(3)];
+        let destinations = [VertexId(2), VertexId(0), VertexId(4)];
+        let matrix = run_vertex_oriented(&origins, &destinations, false, &si).unwrap();
+
+        let costs = (0..origins.len())
+            .map(|o| {
+                (0..destinations.len())
+                    .map(|d| matrix.get(o, d).map(|c| c.cost.as_f64()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            vec![Some(2.0), Some(0.0), None],
+            vec![Some(4.0), Some(2.0), None],
+        ];
+        assert_eq!(costs, expected);
+        assert!(matrix.get(0, 0).unwrap().route.is_none());
+    }
+
+    #[test]
+    fn test_matrix_routes_match_a_star() {
+        let si = build_mock_search_instance();
+        let origins = [VertexId(3), VertexId(1)];
+        let destinations = [VertexId(2), VertexId(1)];
+        let matrix = run_vertex_oriented(&origins, &destinations, true, &si).unwrap();
+
+        for (o_idx, origin) in origins.iter().enumerate() {
+            for (d_idx, destination) in destinations.iter().enumerate() {
+                let cell = matrix.get(o_idx, d_idx).unwrap();
+                let route = cell.route.as_ref().unwrap();
+                let expected = if origin == destination {
+                    vec![]
+                } else {
+                    let result = a_star::run_vertex_oriented(
+                        *origin,
+                        Some(*destination),
+                        &Direction::Forward,
+                        Some(Cost::ZERO),
+                        None,
+                        &[],
+                        &si,
+                    )
+                    .unwrap();
+                    backtrack::vertex_oriented_route(*origin, *destination, &result.tree).unwrap()
+                };
+                let route_ids = route.iter().map(|et| et.edge_id).collect::<Vec<_>>();
+                let expected_ids = expected.iter().map(|et| et.edge_id).collect::<Vec<_>>();
+                assert_eq!(route_ids, expected_ids);
+                let route_cost = route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+                assert_eq!(route_cost, cell.cost.as_f64());
+            }
+        }
+    }
+}
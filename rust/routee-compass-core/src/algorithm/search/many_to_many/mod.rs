@@ -0,0 +1,4 @@
+pub mod many_to_many_algorithm;
+mod od_matrix;
+
+pub use od_matrix::{OdMatrix, OdMatrixCell};
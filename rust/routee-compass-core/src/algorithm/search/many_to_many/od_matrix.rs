@@ -0,0 +1,32 @@
+use crate::algorithm::search::EdgeTraversal;
+use crate::model::network::VertexId;
+use crate::model::state::StateVariable;
+use crate::model::unit::Cost;
+
+/// the least-cost path found between one origin and one destination of a matrix query
+#[derive(Clone, Debug)]
+pub struct OdMatrixCell {
+    pub cost: Cost,
+    pub state: Vec<StateVariable>,
+    pub route: Option<Vec<EdgeTraversal>>,
+}
+
+/// result of a many-to-many search. cells are indexed by origin (row) and then by
+/// destination (column) in the order the vertices were provided. a cell is None when
+/// no path exists between the pair.
+#[derive(Clone, Debug, Default)]
+pub struct OdMatrix {
+    pub origins: Vec<VertexId>,
+    pub destinations: Vec<VertexId>,
+    pub cells: Vec<Vec<Option<OdMatrixCell>>>,
+    pub iterations: u64,
+}
+
+impl OdMatrix {
+    pub fn get(&self, origin_index: usize, destination_index: usize) -> Option<&OdMatrixCell> {
+        self.cells
+            .get(origin_index)
+            .and_then(|row| row.get(destination_index))
+            .and_then(|cell| cell.as_ref())
+    }
+}
@@ -3,19 +3,30 @@ use std::collections::HashMap;
 
 pub mod a_star;
 pub mod backtrack;
+pub mod contraction_hierarchy;
 mod direction;
+pub mod edge_based;
 mod edge_traversal;
 pub mod ksp;
+pub mod landmark;
+pub mod many_to_many;
+pub mod pareto;
+mod partial_search_result;
+pub mod resource_constrained;
 mod search_algorithm;
 mod search_algorithm_result;
 mod search_error;
 mod search_instance;
 mod search_result;
 mod search_tree_branch;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod tsp;
 pub mod util;
 
 pub use direction::Direction;
 pub use edge_traversal::EdgeTraversal;
+pub use partial_search_result::PartialSearchResult;
 pub use search_algorithm::SearchAlgorithm;
 pub use search_algorithm_result::SearchAlgorithmResult;
 pub use search_error::SearchError;
@@ -0,0 +1,4 @@
+pub mod pareto_algorithm;
+mod pareto_label;
+
+pub use pareto_label::ParetoLabel;
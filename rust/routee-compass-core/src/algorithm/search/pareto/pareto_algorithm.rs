@@ -0,0 +1,345 @@
+use super::pareto_label::ParetoLabel;
+use crate::algorithm::search::{
+    Direction, EdgeTraversal, SearchAlgorithmResult, SearchError, SearchInstance, SearchTreeBranch,
+};
+use crate::model::network::{EdgeId, VertexId};
+use crate::model::state::StateVariable;
+use crate::model::unit::{Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// run a multi-objective label-setting search between a source and a target vertex.
+///
+/// instead of a single cost label, each vertex keeps the set of non-dominated labels
+/// over the provided state features, where each objective is the change in that
+/// feature since the initial state. labels are settled in order of the sum of their
+/// objectives, so a settled label can never be dominated by one discovered later.
+/// labels dominated by any label already at the target are pruned.
+///
+/// the objectives must be non-decreasing along any edge traversal (for example
+/// distance, time or energy without regeneration) for the result to be exact.
+///
+/// # Arguments
+///
+/// * `source`   - search origin vertex
+/// * `target`   - search destination vertex
+/// * `features` - names of the state features to optimize
+/// * `si`       - the search assets for this query
+///
+/// # Returns
+///
+/// one route for each point on the Pareto front at the target, ordered by the
+/// first objective. no search trees are returned.
+pub fn run_vertex_oriented(
+    source: VertexId,
+    target: VertexId,
+    features: &[String],
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if features.is_empty() {
+        return Err(SearchError::BuildError(String::from(
+            "pareto search requires at least one feature",
+        )));
+    }
+    for feature in features.iter() {
+        if !si.state_model.contains_key(feature) {
+            return Err(SearchError::BuildError(format!(
+                "pareto search feature '{}' not found in state model, must be one of {}",
+                feature,
+                si.state_model.get_names()
+            )));
+        }
+    }
+    if source == target {
+        return Ok(SearchAlgorithmResult::default());
+    }
+
+    let initial_state = si.state_model.initial_state()?;
+    let mut labels: Vec<ParetoLabel> = vec![ParetoLabel {
+        vertex_id: source,
+        objectives: vec![0.0; features.len()],
+        edge_traversal: None,
+        parent: None,
+    }];
+    // by vertex, the ids of the non-dominated labels found so far
+    let mut bags: HashMap<VertexId, Vec<usize>> = HashMap::from([(source, vec![0])]);
+    // labels removed from their bag after being dominated are skipped when popped
+    let mut active: Vec<bool> = vec![true];
+    let mut queue: InternalPriorityQueue<usize, ReverseCost> = InternalPriorityQueue::default();
+    queue.push(0, Cost::ZERO.into());
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+
+    while let Some((label_id, _)) = queue.pop() {
+        si.termination_model
+            .test(&start_time, labels.len(), iterations)?;
+        iterations += 1;
+
+        let label = labels[label_id].clone();
+        if !active[label_id] || label.vertex_id == target {
+            continue;
+        }
+
+        let current_state = match &label.edge_traversal {
+            None => initial_state.clone(),
+            Some(et) => et.result_state.clone(),
+        };
+        let last_edge_id = label.edge_traversal.as_ref().map(|et| et.edge_id);
+        // the frontier model can only inspect the branch that reached this label
+        let tree = match &label.edge_traversal {
+            None => HashMap::new(),
+            Some(et) => {
+                let src = si.graph.src_vertex_id(&et.edge_id)?;
+                let branch = SearchTreeBranch {
+                    terminal_vertex: src,
+                    edge_traversal: et.clone(),
+                };
+                HashMap::from([(label.vertex_id, branch)])
+            }
+        };
+
+        let out_edges: Vec<EdgeId> = si.graph.out_edges_iter(&label.vertex_id).cloned().collect();
+        for edge_id in out_edges {
+            let edge = si.graph.get_edge(&edge_id)?;
+            let valid = si.frontier_model.valid_frontier(
+                edge,
+                &current_state,
+                &tree,
+                &Direction::Forward,
+                &si.state_model,
+            )?;
+            if !valid {
+                continue;
+            }
+            let et = EdgeTraversal::forward_traversal(edge_id, last_edge_id, &current_state, si)?;
+            let objectives = get_objectives(&initial_state, &et.result_state, features, si)?;
+            let candidate = ParetoLabel {
+                vertex_id: edge.dst_vertex_id,
+                objectives,
+                edge_traversal: Some(et),
+                parent: Some(label_id),
+            };
+
+            // prune candidates dominated at their vertex or at the target
+            let dominated = [candidate.vertex_id, target].iter().any(|v| {
+                bags.get(v)
+                    .map(|bag| bag.iter().any(|id| labels[*id].dominates(&candidate)))
+                    .unwrap_or_default()
+            });
+            if dominated {
+                continue;
+            }
+
+            let candidate_id = labels.len();
+            let priority = Cost::new(candidate.objectives.iter().sum());
+            let bag = bags.entry(candidate.vertex_id).or_default();
+            bag.retain(|id| {
+                let keep = !candidate.dominates(&labels[*id]);
+                active[*id] = keep;
+                keep
+            });
+            bag.push(candidate_id);
+            labels.push(candidate);
+            active.push(true);
+            queue.push(candidate_id, priority.into());
+        }
+    }
+
+    log::debug!(
+        "pareto search iterations: {}, labels created: {}",
+        iterations,
+        labels.len()
+    );
+
+    let mut front = bags.remove(&target).unwrap_or_default();
+    if front.is_empty() {
+        return Err(SearchError::NoPathExistsBetweenVertices(source, target));
+    }
+    front.sort_by(|a, b| {
+        let (a, b) = (&labels[*a].objectives, &labels[*b].objectives);
+        a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let routes = front
+        .into_iter()
+        .map(|label_id| ParetoLabel::backtrack(label_id, &labels))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SearchAlgorithmResult {
+        trees: vec![],
+        routes,
+        iterations,
+        suboptimality_bound: None,
+    })
+}
+
+/// the change in each objective feature from the initial state
+fn get_objectives(
+    initial_state: &[StateVariable],
+    state: &[StateVariable],
+    features: &[String],
+    si: &SearchInstance,
+) -> Result<Vec<f64>, SearchError> {
+    features
+        .iter()
+        .map(|f| {
+            si.state_model
+                .get_delta(initial_state, state, f)
+                .map(|delta| delta.0)
+                .map_err(SearchError::from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::test_util::{
+        build_graph, build_search_instance_with_model, build_vertices,
+    };
+    use crate::model::network::{Edge, Vertex};
+    use crate::model::state::{StateFeature, StateModel};
+    use crate::model::termination::TerminationModel;
+    use crate::model::traversal::{TraversalModel, TraversalModelError};
+    use crate::model::unit::{AsF64, Distance, DistanceUnit, Time, TimeUnit};
+    use std::sync::Arc;
+
+    /// adds the edge distance and a fixed travel time per edge
+    struct MockTraversalModel {
+        times: Vec<f64>,
+    }
+
+    impl TraversalModel for MockTraversalModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVariable>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            state_model.add_distance(
+                state,
+                &String::from("distance"),
+                &edge.distance,
+                &DistanceUnit::Meters,
+            )?;
+            state_model.add_time(
+                state,
+                &String::from("time"),
+                &Time::new(self.times[edge.edge_id.0]),
+                &TimeUnit::Seconds,
+            )?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    /// three parallel paths from 0 to 3 via 1, via 2 and via 4 -> 5. the path via 1 is
+    /// shortest, the path via 2 is fastest, the path via 4 is a compromise, and the
+    /// direct edge 0 -> 3 is dominated by the path via 4.
+    fn build_search_instance() -> SearchInstance {
+        let vertices = build_vertices(6);
+        // (src, dst, distance, time)
+        let edge_data = [
+            (0, 1, 1.0, 10.0),
+            (1, 3, 1.0, 10.0),
+            (0, 2, 5.0, 1.0),
+            (2, 3, 5.0, 1.0),
+            (0, 4, 2.0, 3.0),
+            (4, 5, 1.0, 2.0),
+            (5, 3, 1.0, 2.0),
+            (0, 3, 5.0, 8.0),
+        ];
+        let edges = edge_data
+            .iter()
+            .enumerate()
+            .map(|(idx, (src, dst, dist, _))| Edge::new(idx, *src, *dst, *dist))
+            .collect::<Vec<_>>();
+        let times = edge_data.iter().map(|(_, _, _, t)| *t).collect::<Vec<_>>();
+
+        build_search_instance_with_model(
+            build_graph(vertices, edges),
+            Arc::new(MockTraversalModel { times }),
+            vec![
+                (
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Meters,
+                        initial: Distance::new(0.0),
+                    },
+                ),
+                (
+                    String::from("time"),
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Seconds,
+                        initial: Time::new(0.0),
+                    },
+                ),
+            ],
+            1.0,
+            TerminationModel::IterationsLimit { limit: 100 },
+        )
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let si = build_search_instance();
+        let features = vec![String::from("distance"), String::from("time")];
+        let result = run_vertex_oriented(VertexId(0), VertexId(3), &features, &si).unwrap();
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| r.iter().map(|et| et.edge_id.0).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(routes, vec![vec![0, 1], vec![4, 5, 6], vec![2, 3]]);
+
+        // the final state of each route carries the accumulated objectives
+        let times = result
+            .routes
+            .iter()
+            .map(|r| {
+                let state = &r.last().unwrap().result_state;
+                si.state_model
+                    .get_time(state, &String::from("time"), &TimeUnit::Seconds)
+                    .unwrap()
+                    .as_f64()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(times, vec![20.0, 7.0, 2.0]);
+    }
+
+    #[test]
+    fn test_single_objective() {
+        let si = build_search_instance();
+        let features = vec![String::from("time")];
+        let result = run_vertex_oriented(VertexId(0), VertexId(3), &features, &si).unwrap();
+        assert_eq!(result.routes.len(), 1);
+        let route = result.routes[0]
+            .iter()
+            .map(|et| et.edge_id.0)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_unknown_feature() {
+        let si = build_search_instance();
+        let features = vec![String::from("energy")];
+        let result = run_vertex_oriented(VertexId(0), VertexId(3), &features, &si);
+        assert!(matches!(result, Err(SearchError::BuildError(_))));
+    }
+}
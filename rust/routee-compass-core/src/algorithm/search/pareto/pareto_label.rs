@@ -0,0 +1,53 @@
+use crate::algorithm::search::{EdgeTraversal, SearchError};
+use crate::model::network::VertexId;
+
+/// a partial path in a multi-objective search. each label records the objective
+/// values accumulated from the source along with the traversal that reached its
+/// vertex and the label it was extended from. all objectives are minimized.
+#[derive(Clone, Debug)]
+pub struct ParetoLabel {
+    pub vertex_id: VertexId,
+    pub objectives: Vec<f64>,
+    pub edge_traversal: Option<EdgeTraversal>,
+    pub parent: Option<usize>,
+}
+
+impl ParetoLabel {
+    /// true if this label is at least as good as the other label in every objective.
+    /// equal labels dominate each other, so only the first of a set of equal labels is kept.
+    pub fn dominates(&self, other: &ParetoLabel) -> bool {
+        self.objectives
+            .iter()
+            .zip(other.objectives.iter())
+            .all(|(a, b)| a <= b)
+    }
+
+    /// follows parent labels back to the source, producing the route in forward order
+    ///
+    /// # Arguments
+    ///
+    /// * `label_id` - index of the final label of the route
+    /// * `labels`   - all labels created during the search, indexed by label id
+    ///
+    /// # Returns
+    ///
+    /// the edge traversals from the source to the vertex of the final label
+    pub fn backtrack(
+        label_id: usize,
+        labels: &[ParetoLabel],
+    ) -> Result<Vec<EdgeTraversal>, SearchError> {
+        let mut route = vec![];
+        let mut current = Some(label_id);
+        while let Some(id) = current {
+            let label = labels.get(id).ok_or_else(|| {
+                SearchError::InternalError(format!("label {} missing during backtrack", id))
+            })?;
+            if let Some(et) = &label.edge_traversal {
+                route.push(et.clone());
+            }
+            current = label.parent;
+        }
+        route.reverse();
+        Ok(route)
+    }
+}
@@ -0,0 +1,16 @@
+use super::search_result::SearchResult;
+use crate::model::network::vertex_id::VertexId;
+
+/// the state of a search when the termination model interrupted it
+#[derive(Debug)]
+pub struct PartialSearchResult {
+    /// explanation of why the search was terminated
+    pub explanation: String,
+    /// the vertex where the search began
+    pub source: VertexId,
+    /// the vertex of the search tree estimated to be closest to the search target,
+    /// if the search had a target and reached any vertex
+    pub closest_vertex: Option<VertexId>,
+    /// the search tree and iterations at the time of termination
+    pub result: SearchResult,
+}
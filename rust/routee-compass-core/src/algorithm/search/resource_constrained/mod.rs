@@ -0,0 +1,4 @@
+pub mod resource_constrained_algorithm;
+mod resource_constraint;
+
+pub use resource_constraint::{DominanceObjective, DominanceRule, ResourceConstraint};
@@ -0,0 +1,368 @@
+use super::resource_constraint::{DominanceRule, ResourceConstraint};
+use crate::algorithm::search::pareto::ParetoLabel;
+use crate::algorithm::search::{
+    Direction, EdgeTraversal, SearchAlgorithmResult, SearchError, SearchInstance, SearchTreeBranch,
+};
+use crate::model::network::{EdgeId, VertexId};
+use crate::model::state::StateVariable;
+use crate::model::unit::{AsF64, Cost, ReverseCost};
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// run a resource-constrained shortest path search between a source and a target vertex.
+///
+/// unlike A*, which keeps a single branch per vertex, each vertex keeps every label that
+/// is not dominated by another label at that vertex. the first objective of a label is
+/// its path cost, followed by one objective per dominance rule. labels whose state
+/// violates any constraint are discarded, so a cheaper label that breaks a constraint
+/// can never replace a feasible one.
+///
+/// dominance rules are added automatically for each constrained feature (see
+/// [`DominanceRule::with_constraint_rules`]). labels are settled in cost order, so the
+/// first label settled at the target is the least-cost feasible route.
+///
+/// # Arguments
+///
+/// * `source`      - search origin vertex
+/// * `target`      - search destination vertex
+/// * `constraints` - feasibility constraints on state features
+/// * `dominance`   - additional state features to compare between labels
+/// * `si`          - the search assets for this query
+///
+/// # Returns
+///
+/// the least-cost route that satisfies all constraints. no search trees are returned.
+pub fn run_vertex_oriented(
+    source: VertexId,
+    target: VertexId,
+    constraints: &[ResourceConstraint],
+    dominance: &[DominanceRule],
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    let rules = DominanceRule::with_constraint_rules(dominance, constraints);
+    let features = constraints
+        .iter()
+        .map(|c| &c.feature)
+        .chain(rules.iter().map(|r| &r.feature));
+    for feature in features {
+        if !si.state_model.contains_key(feature) {
+            return Err(SearchError::BuildError(format!(
+                "resource constrained search feature '{}' not found in state model, must be one of {}",
+                feature,
+                si.state_model.get_names()
+            )));
+        }
+    }
+
+    let initial_state = si.state_model.initial_state()?;
+    if !ResourceConstraint::is_feasible(&initial_state, constraints, si)? {
+        return Err(SearchError::BuildError(String::from(
+            "initial search state violates resource constraints",
+        )));
+    }
+    if source == target {
+        return Ok(SearchAlgorithmResult::default());
+    }
+
+    let mut labels: Vec<ParetoLabel> = vec![ParetoLabel {
+        vertex_id: source,
+        objectives: get_objectives(Cost::ZERO, &initial_state, &rules, si)?,
+        edge_traversal: None,
+        parent: None,
+    }];
+    let mut bags: HashMap<VertexId, Vec<usize>> = HashMap::from([(source, vec![0])]);
+    // labels removed from their bag after being dominated are skipped when popped
+    let mut active: Vec<bool> = vec![true];
+    let mut queue: InternalPriorityQueue<usize, ReverseCost> = InternalPriorityQueue::default();
+    queue.push(0, Cost::ZERO.into());
+
+    let start_time = Instant::now();
+    let mut iterations = 0;
+    let mut solution: Option<usize> = None;
+
+    while let Some((label_id, _)) = queue.pop() {
+        si.termination_model
+            .test(&start_time, labels.len(), iterations)?;
+        iterations += 1;
+
+        if !active[label_id] {
+            continue;
+        }
+        let label = labels[label_id].clone();
+        if label.vertex_id == target {
+            solution = Some(label_id);
+            break;
+        }
+
+        let current_cost = Cost::new(label.objectives[0]);
+        let current_state = match &label.edge_traversal {
+            None => initial_state.clone(),
+            Some(et) => et.result_state.clone(),
+        };
+        let last_edge_id = label.edge_traversal.as_ref().map(|et| et.edge_id);
+        // the frontier model can only inspect the branch that reached this label
+        let tree = match &label.edge_traversal {
+            None => HashMap::new(),
+            Some(et) => {
+                let src = si.graph.src_vertex_id(&et.edge_id)?;
+                let branch = SearchTreeBranch {
+                    terminal_vertex: src,
+                    edge_traversal: et.clone(),
+                };
+                HashMap::from([(label.vertex_id, branch)])
+            }
+        };
+
+        let out_edges: Vec<EdgeId> = si.graph.out_edges_iter(&label.vertex_id).cloned().collect();
+        for edge_id in out_edges {
+            let edge = si.graph.get_edge(&edge_id)?;
+            let valid = si.frontier_model.valid_frontier(
+                edge,
+                &current_state,
+                &tree,
+                &Direction::Forward,
+                &si.state_model,
+            )?;
+            if !valid {
+                continue;
+            }
+            let et = EdgeTraversal::forward_traversal(edge_id, last_edge_id, &current_state, si)?;
+            if !ResourceConstraint::is_feasible(&et.result_state, constraints, si)? {
+                continue;
+            }
+            let cost = current_cost + et.total_cost();
+            let candidate = ParetoLabel {
+                vertex_id: edge.dst_vertex_id,
+                objectives: get_objectives(cost, &et.result_state, &rules, si)?,
+                edge_traversal: Some(et),
+                parent: Some(label_id),
+            };
+
+            let bag = bags.entry(candidate.vertex_id).or_default();
+            if bag.iter().any(|id| labels[*id].dominates(&candidate)) {
+                continue;
+            }
+            let candidate_id = labels.len();
+            bag.retain(|id| {
+                let keep = !candidate.dominates(&labels[*id]);
+                active[*id] = keep;
+                keep
+            });
+            bag.push(candidate_id);
+            labels.push(candidate);
+            active.push(true);
+            queue.push(candidate_id, cost.into());
+        }
+    }
+
+    log::debug!(
+        "resource constrained search iterations: {}, labels created: {}",
+        iterations,
+        labels.len()
+    );
+
+    let label_id = solution.ok_or(SearchError::NoPathExistsBetweenVertices(source, target))?;
+    let route = ParetoLabel::backtrack(label_id, &labels)?;
+    Ok(SearchAlgorithmResult {
+        trees: vec![],
+        routes: vec![route],
+        iterations,
+        suboptimality_bound: None,
+    })
+}
+
+/// the label objectives: path cost followed by each dominance rule value
+fn get_objectives(
+    cost: Cost,
+    state: &[StateVariable],
+    rules: &[DominanceRule],
+    si: &SearchInstance,
+) -> Result<Vec<f64>, SearchError> {
+    let mut objectives = vec![cost.as_f64()];
+    for rule in rules.iter() {
+        let value = si.state_model.get_state_variable(state, &rule.feature)?;
+        objectives.push(rule.objective_value(value.0));
+    }
+    Ok(objectives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star;
+    use crate::algorithm::search::backtrack;
+    use crate::algorithm::search::resource_constrained::DominanceObjective;
+    use crate::algorithm::search::test_util::{
+        build_graph, build_search_instance_with_model, build_vertices,
+    };
+    use crate::model::network::{Edge, Vertex};
+    use crate::model::state::{CustomFeatureFormat, StateFeature, StateModel};
+    use crate::model::termination::TerminationModel;
+    use crate::model::traversal::{TraversalModel, TraversalModelError};
+    use crate::model::unit::{Distance, DistanceUnit};
+    use ordered_float::OrderedFloat;
+    use std::sync::Arc;
+
+    const BATTERY: &str = "battery_state";
+
+    /// adds the edge distance and drains a fixed battery percentage per edge
+    struct MockTraversalModel {
+        battery_use: Vec<f64>,
+    }
+
+    impl TraversalModel for MockTraversalModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVariable>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            state_model.add_distance(
+                state,
+                &String::from("distance"),
+                &edge.distance,
+                &DistanceUnit::Meters,
+            )?;
+            let battery = state_model.get_custom_f64(state, &String::from(BATTERY))?;
+            let remaining = battery - self.battery_use[edge.edge_id.0];
+            state_model.set_custom_f64(state, &String::from(BATTERY), &remaining)?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    /// two ways from 0 to 2: a short path 0 -> 1 -> 2 that drains the battery and a
+    /// longer path 0 -> 3 -> 1 -> 2 that conserves it. both paths reach vertex 1, where
+    /// a vertex-keyed tree would only keep the cheaper, battery-depleted branch.
+    fn build_search_instance() -> SearchInstance {
+        let vertices = build_vertices(4);
+        // (src, dst, distance, battery use)
+        let edge_data = [
+            (0, 1, 1.0, 60.0),
+            (1, 2, 1.0, 20.0),
+            (0, 3, 2.0, 10.0),
+            (3, 1, 2.0, 10.0),
+        ];
+        let edges = edge_data
+            .iter()
+            .enumerate()
+            .map(|(idx, (src, dst, dist, _))| Edge::new(idx, *src, *dst, *dist))
+            .collect::<Vec<_>>();
+        let battery_use = edge_data.iter().map(|(_, _, _, b)| *b).collect::<Vec<_>>();
+
+        build_search_instance_with_model(
+            build_graph(vertices, edges),
+            Arc::new(MockTraversalModel { battery_use }),
+            vec![
+                (
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Meters,
+                        initial: Distance::new(0.0),
+                    },
+                ),
+                (
+                    String::from(BATTERY),
+                    StateFeature::Custom {
+                        r#type: String::from("soc"),
+                        unit: String::from("percent"),
+                        format: CustomFeatureFormat::FloatingPoint {
+                            initial: OrderedFloat(100.0),
+                        },
+                    },
+                ),
+            ],
+            1.0,
+            TerminationModel::IterationsLimit { limit: 100 },
+        )
+    }
+
+    fn battery_constraint(min: f64) -> Vec<ResourceConstraint> {
+        vec![ResourceConstraint {
+            feature: String::from(BATTERY),
+            min: Some(min),
+            max: None,
+        }]
+    }
+
+    fn edge_ids(route: &[EdgeTraversal]) -> Vec<usize> {
+        route.iter().map(|et| et.edge_id.0).collect()
+    }
+
+    #[test]
+    fn test_constraint_keeps_feasible_label() {
+        let si = build_search_instance();
+        let (o, d) = (VertexId(0), VertexId(2));
+
+        // the shortest path ends with 20% battery
+        let tree =
+            a_star::run_vertex_oriented(o, Some(d), &Direction::Forward, None, None, &[], &si)
+                .unwrap()
+                .tree;
+        let shortest = backtrack::vertex_oriented_route(o, d, &tree).unwrap();
+        assert_eq!(edge_ids(&shortest), vec![0, 1]);
+
+        // requiring 50% battery selects the longer path through 3
+        let result = run_vertex_oriented(o, d, &battery_constraint(50.0), &[], &si).unwrap();
+        let route = &result.routes[0];
+        assert_eq!(edge_ids(route), vec![2, 3, 1]);
+        let battery = si
+            .state_model
+            .get_custom_f64(&route.last().unwrap().result_state, &String::from(BATTERY))
+            .unwrap();
+        assert_eq!(battery, 60.0);
+
+        // a loose constraint does not change the least-cost route
+        let result = run_vertex_oriented(o, d, &battery_constraint(10.0), &[], &si).unwrap();
+        assert_eq!(edge_ids(&result.routes[0]), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_infeasible() {
+        let si = build_search_instance();
+        let result = run_vertex_oriented(
+            VertexId(0),
+            VertexId(2),
+            &battery_constraint(80.0),
+            &[],
+            &si,
+        );
+        assert!(matches!(
+            result,
+            Err(SearchError::NoPathExistsBetweenVertices(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_constraint_rules() {
+        let constraints = vec![ResourceConstraint {
+            feature: String::from(BATTERY),
+            min: Some(10.0),
+            max: Some(90.0),
+        }];
+        let explicit = vec![DominanceRule {
+            feature: String::from(BATTERY),
+            objective: DominanceObjective::Maximize,
+        }];
+        let rules = DominanceRule::with_constraint_rules(&explicit, &constraints);
+        let objectives = rules.iter().map(|r| r.objective).collect::<Vec<_>>();
+        assert_eq!(
+            objectives,
+            vec![DominanceObjective::Maximize, DominanceObjective::Minimize]
+        );
+    }
+}
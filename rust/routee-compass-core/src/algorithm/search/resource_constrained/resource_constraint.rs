@@ -0,0 +1,100 @@
+use crate::algorithm::search::{SearchError, SearchInstance};
+use crate::model::state::StateVariable;
+use serde::{Deserialize, Serialize};
+
+/// a feasibility constraint on a state feature. every label of a resource-constrained
+/// search must keep the feature value within the bounds, inclusive. values are in the
+/// units of the state feature, for example a `battery_state` constraint is in percent.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceConstraint {
+    pub feature: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl ResourceConstraint {
+    pub fn is_satisfied(&self, value: f64) -> bool {
+        let above_min = match self.min {
+            Some(min) => value >= min,
+            None => true,
+        };
+        let below_max = match self.max {
+            Some(max) => value <= max,
+            None => true,
+        };
+        above_min && below_max
+    }
+
+    /// true if the state satisfies every constraint
+    pub fn is_feasible(
+        state: &[StateVariable],
+        constraints: &[ResourceConstraint],
+        si: &SearchInstance,
+    ) -> Result<bool, SearchError> {
+        for constraint in constraints.iter() {
+            let value = si
+                .state_model
+                .get_state_variable(state, &constraint.feature)?;
+            if !constraint.is_satisfied(value.0) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// the preferred direction of a state feature when comparing two labels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DominanceObjective {
+    Minimize,
+    Maximize,
+}
+
+/// a state feature compared when testing if one label dominates another. a label
+/// dominates another at the same vertex when its cost is no greater and it is at
+/// least as good in every dominance rule.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DominanceRule {
+    pub feature: String,
+    pub objective: DominanceObjective,
+}
+
+impl DominanceRule {
+    /// converts a feature value into an objective value where lower is better
+    pub fn objective_value(&self, value: f64) -> f64 {
+        match self.objective {
+            DominanceObjective::Minimize => value,
+            DominanceObjective::Maximize => -value,
+        }
+    }
+
+    /// builds the dominance rules needed to enforce constraints exactly. a feature with a
+    /// lower bound is maximized and a feature with an upper bound is minimized, so a label
+    /// that is closer to violating a constraint never dominates one with more headroom.
+    /// rules already present for a feature and direction are not repeated.
+    pub fn with_constraint_rules(
+        rules: &[DominanceRule],
+        constraints: &[ResourceConstraint],
+    ) -> Vec<DominanceRule> {
+        let mut result = rules.to_vec();
+        for constraint in constraints.iter() {
+            let objectives = [
+                constraint.min.map(|_| DominanceObjective::Maximize),
+                constraint.max.map(|_| DominanceObjective::Minimize),
+            ];
+            for objective in objectives.into_iter().flatten() {
+                let exists = result
+                    .iter()
+                    .any(|r| r.feature == constraint.feature && r.objective == objective);
+                if !exists {
+                    result.push(DominanceRule {
+                        feature: constraint.feature.clone(),
+                        objective,
+                    });
+                }
+            }
+        }
+        result
+    }
+}
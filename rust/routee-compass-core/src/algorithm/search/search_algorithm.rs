@@ -1,8 +1,16 @@
+use super::a_star::{anytime_a_star_algorithm, bidirectional_a_star_algorithm};
 use super::backtrack;
+use super::contraction_hierarchy::ContractionHierarchy;
+use super::edge_based::edge_based_algorithm;
 use super::edge_traversal::EdgeTraversal;
 use super::ksp::KspQuery;
 use super::ksp::KspTerminationCriteria;
-use super::ksp::{svp, yens};
+use super::ksp::{penalty, svp, yens};
+use super::landmark::LandmarkHeuristic;
+use super::pareto::pareto_algorithm;
+use super::resource_constrained::{
+    resource_constrained_algorithm, DominanceRule, ResourceConstraint,
+};
 use super::search_algorithm_result::SearchAlgorithmResult;
 use super::search_error::SearchError;
 use super::search_instance::SearchInstance;
@@ -14,6 +22,11 @@ use crate::model::unit::Cost;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// default weight factor of the first anytime a* search
+const ANYTIME_INITIAL_WEIGHT_FACTOR: f64 = 3.0;
+/// default amount the anytime a* weight factor is lowered between searches
+const ANYTIME_WEIGHT_FACTOR_STEP: f64 = 0.5;
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum SearchAlgorithm {
@@ -21,6 +34,33 @@ pub enum SearchAlgorithm {
     #[serde(rename = "a*")]
     AStarAlgorithm {
         weight_factor: Option<Cost>,
+        landmarks: Option<LandmarkHeuristic>,
+    },
+    /// weighted a* that refines its route toward optimal by lowering the weight
+    /// factor until the termination model interrupts it, reporting the proven
+    /// suboptimality bound of the route returned
+    #[serde(rename = "anytime_a*")]
+    AnytimeAStar {
+        initial_weight_factor: Option<Cost>,
+        weight_factor_step: Option<Cost>,
+        landmarks: Option<LandmarkHeuristic>,
+    },
+    #[serde(rename = "bidirectional_a*")]
+    BidirectionalAStar,
+    /// a* search over edges instead of vertices, for exact routing with
+    /// turn-dependent frontier, access, and traversal models
+    #[serde(rename = "edge_based_a*")]
+    EdgeBasedAStar {
+        weight_factor: Option<Cost>,
+    },
+    ContractionHierarchy(ContractionHierarchy),
+    Pareto {
+        features: Vec<String>,
+    },
+    ResourceConstrained {
+        constraints: Vec<ResourceConstraint>,
+        #[serde(default)]
+        dominance: Vec<DominanceRule>,
     },
     KspSingleVia {
         k: usize,
@@ -34,9 +74,50 @@ pub enum SearchAlgorithm {
         similarity: Option<RouteSimilarityFunction>,
         termination: Option<KspTerminationCriteria>,
     },
+    KspPenalty {
+        k: usize,
+        underlying: Box<SearchAlgorithm>,
+        penalty_factor: f64,
+        similarity: Option<RouteSimilarityFunction>,
+        termination: Option<KspTerminationCriteria>,
+    },
 }
 
 impl SearchAlgorithm {
+    /// true if this algorithm honors [`Direction::Reverse`], expanding the search
+    /// backward from its source over incoming edges. algorithms that search in both
+    /// directions or build their own query plan always run forward.
+    pub fn supports_reverse_search(&self) -> bool {
+        matches!(
+            self,
+            SearchAlgorithm::Dijkstra
+                | SearchAlgorithm::AStarAlgorithm { .. }
+                | SearchAlgorithm::AnytimeAStar { .. }
+        )
+    }
+
+    /// true if this algorithm honors a `budget` on the query, limiting the search
+    /// to the vertices reachable within the budget.
+    pub fn supports_budget(&self) -> bool {
+        matches!(
+            self,
+            SearchAlgorithm::Dijkstra
+                | SearchAlgorithm::AStarAlgorithm { .. }
+                | SearchAlgorithm::AnytimeAStar { .. }
+        )
+    }
+
+    /// true if this algorithm returns a partial result when the termination model
+    /// interrupts its search. see [`crate::model::termination::TerminationBehavior`].
+    pub fn supports_partial_result(&self) -> bool {
+        matches!(
+            self,
+            SearchAlgorithm::Dijkstra
+                | SearchAlgorithm::AStarAlgorithm { .. }
+                | SearchAlgorithm::AnytimeAStar { .. }
+        )
+    }
+
     pub fn run_vertex_oriented(
         &self,
         src_id: VertexId,
@@ -45,24 +126,28 @@ impl SearchAlgorithm {
         direction: &Direction,
         si: &SearchInstance,
     ) -> Result<SearchAlgorithmResult, SearchError> {
+        self.validate_budget(query)?;
         match self {
             SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
                 weight_factor: Some(Cost::ZERO),
+                landmarks: None,
             }
             .run_vertex_oriented(src_id, dst_id_opt, query, direction, si),
-            SearchAlgorithm::AStarAlgorithm { weight_factor } => {
-                let w_val = match query.get("weight_factor") {
-                    Some(w_json) => w_json
-                        .as_f64()
-                        .ok_or(SearchError::BuildError(format!(
-                            "weight_factor must be a float, found {}",
-                            w_json
-                        )))
-                        .map(|f| Some(Cost::new(f))),
-                    None => Ok(*weight_factor),
-                }?;
-                let search_result =
-                    a_star::run_vertex_oriented(src_id, dst_id_opt, direction, w_val, si)?;
+            SearchAlgorithm::AStarAlgorithm {
+                weight_factor,
+                landmarks,
+            } => {
+                let w_val = get_weight_factor(query, *weight_factor)?;
+                let budget = get_budget(query, si)?;
+                let search_result = a_star::run_vertex_oriented(
+                    src_id,
+                    dst_id_opt,
+                    direction,
+                    w_val,
+                    landmarks.as_ref(),
+                    &budget,
+                    si,
+                )?;
                 let routes = match dst_id_opt {
                     None => vec![],
                     Some(dst_id) => {
@@ -75,8 +160,87 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    suboptimality_bound: None,
                 })
             }
+            SearchAlgorithm::AnytimeAStar {
+                initial_weight_factor,
+                weight_factor_step,
+                landmarks,
+            } => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run anytime a* algorithm without destination",
+                    ))
+                })?;
+                let initial_weight_factor = get_weight_factor(query, *initial_weight_factor)?
+                    .unwrap_or(Cost::new(ANYTIME_INITIAL_WEIGHT_FACTOR));
+                let weight_factor_step =
+                    weight_factor_step.unwrap_or(Cost::new(ANYTIME_WEIGHT_FACTOR_STEP));
+                let budget = get_budget(query, si)?;
+                let search_result = anytime_a_star_algorithm::run_vertex_oriented(
+                    src_id,
+                    dst_id,
+                    direction,
+                    initial_weight_factor,
+                    weight_factor_step,
+                    landmarks.as_ref(),
+                    &budget,
+                    si,
+                )?;
+                let route = backtrack::vertex_oriented_route(src_id, dst_id, &search_result.tree)?;
+                Ok(SearchAlgorithmResult {
+                    trees: vec![search_result.tree],
+                    routes: vec![route],
+                    iterations: search_result.iterations,
+                    suboptimality_bound: Some(search_result.suboptimality_bound),
+                })
+            }
+            SearchAlgorithm::BidirectionalAStar => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run bidirectional algorithm without destination",
+                    ))
+                })?;
+                bidirectional_a_star_algorithm::run_vertex_oriented(src_id, dst_id, si)
+            }
+            SearchAlgorithm::EdgeBasedAStar { weight_factor } => {
+                let w_val = get_weight_factor(query, *weight_factor)?;
+                edge_based_algorithm::run_vertex_oriented(src_id, dst_id_opt, w_val, si)
+            }
+            SearchAlgorithm::ContractionHierarchy(ch) => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run contraction hierarchy algorithm without destination",
+                    ))
+                })?;
+                ch.run_vertex_oriented(src_id, dst_id, si)
+            }
+            SearchAlgorithm::Pareto { features } => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run pareto algorithm without destination",
+                    ))
+                })?;
+                pareto_algorithm::run_vertex_oriented(src_id, dst_id, features, si)
+            }
+            SearchAlgorithm::ResourceConstrained {
+                constraints,
+                dominance,
+            } => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run resource constrained algorithm without destination",
+                    ))
+                })?;
+                resource_constrained_algorithm::run_vertex_oriented(
+                    src_id,
+                    dst_id,
+                    constraints,
+                    dominance,
+                    si,
+                )
+            }
             SearchAlgorithm::Yens {
                 k,
                 underlying,
@@ -92,6 +256,7 @@ impl SearchAlgorithm {
                 let term_fn = termination.as_ref().cloned().unwrap_or_default();
                 let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
                 yens::run(&ksp_query, &term_fn, &sim_fn, si, underlying)
+                    .map_err(SearchError::without_partial_result)
             }
             SearchAlgorithm::KspSingleVia {
                 k,
@@ -108,6 +273,32 @@ impl SearchAlgorithm {
                 let term_fn = termination.as_ref().cloned().unwrap_or_default();
                 let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
                 svp::run(&ksp_query, &term_fn, &sim_fn, si, underlying)
+                    .map_err(SearchError::without_partial_result)
+            }
+            SearchAlgorithm::KspPenalty {
+                k,
+                underlying,
+                penalty_factor,
+                similarity,
+                termination,
+            } => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run KSP algorithm without destination",
+                    ))
+                })?;
+                let sim_fn = similarity.as_ref().cloned().unwrap_or_default();
+                let term_fn = termination.as_ref().cloned().unwrap_or_default();
+                let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
+                penalty::run(
+                    &ksp_query,
+                    *penalty_factor,
+                    &term_fn,
+                    &sim_fn,
+                    si,
+                    underlying,
+                )
+                .map_err(SearchError::without_partial_result)
             }
         }
     }
@@ -119,17 +310,25 @@ impl SearchAlgorithm {
         direction: &Direction,
         search_instance: &SearchInstance,
     ) -> Result<SearchAlgorithmResult, SearchError> {
+        self.validate_budget(query)?;
         match self {
             SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
                 weight_factor: Some(Cost::ZERO),
+                landmarks: None,
             }
             .run_edge_oriented(src_id, dst_id_opt, query, direction, search_instance),
-            SearchAlgorithm::AStarAlgorithm { weight_factor } => {
+            SearchAlgorithm::AStarAlgorithm {
+                weight_factor,
+                landmarks,
+            } => {
+                let budget = get_budget(query, search_instance)?;
                 let search_result = a_star::run_edge_oriented(
                     src_id,
                     dst_id_opt,
                     direction,
                     *weight_factor,
+                    landmarks.as_ref(),
+                    &budget,
                     search_instance,
                 )?;
                 let routes = match dst_id_opt {
@@ -148,8 +347,29 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    suboptimality_bound: None,
                 })
             }
+            SearchAlgorithm::AnytimeAStar { .. } => {
+                run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance)
+            }
+            SearchAlgorithm::BidirectionalAStar => {
+                run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance)
+            }
+            SearchAlgorithm::EdgeBasedAStar { weight_factor } => {
+                let w_val = get_weight_factor(query, *weight_factor)?;
+                edge_based_algorithm::run_edge_oriented(src_id, dst_id_opt, w_val, search_instance)
+            }
+            SearchAlgorithm::ContractionHierarchy(_) => {
+                run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance)
+            }
+            SearchAlgorithm::Pareto { features: _ } => {
+                run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance)
+            }
+            SearchAlgorithm::ResourceConstrained {
+                constraints: _,
+                dominance: _,
+            } => run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance),
             SearchAlgorithm::KspSingleVia {
                 k: _,
                 underlying: _,
@@ -162,10 +382,83 @@ impl SearchAlgorithm {
                 similarity: _,
                 termination: _,
             } => run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance),
+            SearchAlgorithm::KspPenalty {
+                k: _,
+                underlying: _,
+                penalty_factor: _,
+                similarity: _,
+                termination: _,
+            } => run_edge_oriented(src_id, dst_id_opt, query, direction, self, search_instance),
+        }
+    }
+
+    /// fails if the query has a budget that this algorithm would ignore
+    fn validate_budget(&self, query: &serde_json::Value) -> Result<(), SearchError> {
+        if query.get("budget").is_some() && !self.supports_budget() {
+            return Err(SearchError::BuildError(String::from(
+                "query budget is only supported by the a*, dijkstra and anytime a* algorithms",
+            )));
         }
+        Ok(())
+    }
+}
+
+/// reads the optional a* weight factor override from the query, falling back to the
+/// weight factor configured for the algorithm.
+fn get_weight_factor(
+    query: &serde_json::Value,
+    default: Option<Cost>,
+) -> Result<Option<Cost>, SearchError> {
+    match query.get("weight_factor") {
+        Some(w_json) => w_json
+            .as_f64()
+            .ok_or(SearchError::BuildError(format!(
+                "weight_factor must be a float, found {}",
+                w_json
+            )))
+            .map(|f| Some(Cost::new(f))),
+        None => Ok(default),
     }
 }
 
+/// reads the optional search budget from the query. the budget is an object mapping
+/// state feature names to the largest value that feature may reach, such as
+/// `{"time": 15.0}`, in the units of the state feature. used by a* and dijkstra
+/// searches to return the tree of vertices reachable within the budget.
+fn get_budget(
+    query: &serde_json::Value,
+    si: &SearchInstance,
+) -> Result<Vec<ResourceConstraint>, SearchError> {
+    let budget_json = match query.get("budget") {
+        None => return Ok(vec![]),
+        Some(b) => b,
+    };
+    let limits: HashMap<String, f64> =
+        serde_json::from_value(budget_json.clone()).map_err(|e| {
+            SearchError::BuildError(format!(
+                "budget must be an object mapping state feature names to limits, found {}: {}",
+                budget_json, e
+            ))
+        })?;
+    limits
+        .into_iter()
+        .map(|(feature, max)| {
+            if !si.state_model.contains_key(&feature) {
+                return Err(SearchError::BuildError(format!(
+                    "budget feature '{}' not found in state model, must be one of {}",
+                    feature,
+                    si.state_model.get_names()
+                )));
+            }
+            Ok(ResourceConstraint {
+                feature,
+                min: None,
+                max: Some(max),
+            })
+        })
+        .collect()
+}
+
 // convenience method when origin and destination are specified using
 /// edge ids instead of vertex ids. invokes a vertex-oriented search
 /// from the out-vertex of the source edge to the in-vertex of the
@@ -200,6 +493,7 @@ pub fn run_edge_oriented(
                 mut trees,
                 mut routes,
                 iterations,
+                suboptimality_bound,
             } = alg.run_vertex_oriented(e1_dst, None, query, direction, si)?;
             for tree in trees.iter_mut() {
                 if !tree.contains_key(&e1_dst) {
@@ -213,6 +507,7 @@ pub fn run_edge_oriented(
                 trees,
                 routes,
                 iterations: iterations + 1,
+                suboptimality_bound,
             };
             Ok(updated)
         }
@@ -246,6 +541,7 @@ pub fn run_edge_oriented(
                     trees: vec![tree],
                     routes: vec![route],
                     iterations: 1,
+                    suboptimality_bound: None,
                 };
                 return Ok(result);
             } else {
@@ -254,9 +550,11 @@ pub fn run_edge_oriented(
                     trees,
                     mut routes,
                     iterations,
+                    suboptimality_bound,
                 } = alg.run_vertex_oriented(e1_dst, Some(e2_src), query, direction, si)?;
 
-                if trees.is_empty() {
+                // some algorithms, such as contraction hierarchies, only produce routes
+                if trees.is_empty() && routes.is_empty() {
                     return Err(SearchError::NoPathExistsBetweenVertices(e1_dst, e2_src));
                 }
 
@@ -281,9 +579,39 @@ pub fn run_edge_oriented(
                     trees,
                     routes,
                     iterations: iterations + 2,
+                    suboptimality_bound,
                 };
                 Ok(result)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::test_util::{build_bidirectional_graph, build_search_instance};
+    use crate::model::termination::TerminationModel;
+
+    #[test]
+    fn test_budget_requires_supporting_algorithm() {
+        let si = build_search_instance(
+            build_bidirectional_graph(3, &[(0, 1, 1.0), (1, 2, 1.0)]),
+            TerminationModel::IterationsLimit { limit: 100 },
+        );
+        let query = serde_json::json!({"budget": {"distance": 1.5}});
+        let tree = SearchAlgorithm::Dijkstra
+            .run_vertex_oriented(VertexId(0), None, &query, &Direction::Forward, &si)
+            .unwrap()
+            .trees;
+        assert_eq!(tree[0].len(), 1);
+        let result = SearchAlgorithm::BidirectionalAStar.run_vertex_oriented(
+            VertexId(0),
+            Some(VertexId(2)),
+            &query,
+            &Direction::Forward,
+            &si,
+        );
+        assert!(matches!(result, Err(SearchError::BuildError(_))));
+    }
+}
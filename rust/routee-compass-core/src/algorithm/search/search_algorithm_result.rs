@@ -7,4 +7,7 @@ pub struct SearchAlgorithmResult {
     pub trees: Vec<HashMap<VertexId, SearchTreeBranch>>,
     pub routes: Vec<Vec<EdgeTraversal>>,
     pub iterations: u64,
+    /// for anytime algorithms, the proven factor by which the route cost may exceed
+    /// the optimal cost. a bound of 1.0 means the route is optimal.
+    pub suboptimality_bound: Option<f64>,
 }
@@ -1,3 +1,4 @@
+use super::PartialSearchResult;
 use crate::model::{
     access::AccessModelError,
     cost::CostModelError,
@@ -49,6 +50,10 @@ pub enum SearchError {
     },
     #[error("query terminated due to {0}")]
     QueryTerminated(String),
+    #[error("query terminated due to {}", .0.explanation)]
+    QueryTerminatedWithPartialResult(Box<PartialSearchResult>),
+    #[error("query terminated due to {1}, partial results are not supported for {0} queries")]
+    PartialResultUnsupported(String, String),
     #[error("no path exists between vertices {0} and {1}")]
     NoPathExistsBetweenVertices(VertexId, VertexId),
     #[error("no path exists between edges {0} and {1}")]
@@ -58,3 +63,19 @@ pub enum SearchError {
     #[error("internal error due to search logic: {0}")]
     InternalError(String),
 }
+
+impl SearchError {
+    /// replaces a partial search result with the termination error of the interrupted
+    /// search. searches that are composed of several searches use this, since the tree
+    /// of the interrupted search alone does not describe their progress.
+    pub fn without_partial_result(self) -> SearchError {
+        match self {
+            SearchError::QueryTerminatedWithPartialResult(partial) => {
+                SearchError::TerminationModelFailure {
+                    source: TerminationModelError::QueryTerminated(partial.explanation),
+                }
+            }
+            other => other,
+        }
+    }
+}
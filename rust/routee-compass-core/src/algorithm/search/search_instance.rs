@@ -7,7 +7,7 @@ use crate::model::{
     network::{graph::Graph, vertex_id::VertexId},
     state::{StateModel, StateVariable},
     termination::TerminationModel,
-    traversal::TraversalModel,
+    traversal::{EdgeDeltaCache, TraversalModel},
     unit::Cost,
 };
 use std::sync::Arc;
@@ -19,6 +19,15 @@ pub struct SearchInstance {
     pub map_model: Arc<MapModel>,
     pub state_model: Arc<StateModel>,
     pub traversal_model: Arc<dyn TraversalModel>,
+    /// traversal model configuration of the application, which identifies the edge
+    /// costs that preprocessed search data was built for
+    pub traversal_config: Arc<serde_json::Value>,
+    /// description of the cost and traversal models shared by the queries of an
+    /// application that use its default cost model, so that it is not recomputed for
+    /// each query. when None, it is computed from this search instance.
+    pub default_preprocessing_info: Option<Arc<serde_json::Value>>,
+    /// precomputed edge deltas that replace the traversal model when it is query-invariant
+    pub edge_delta_cache: Option<EdgeDeltaCache>,
     pub access_model: Arc<dyn AccessModel>,
     pub cost_model: Arc<CostModel>,
     pub frontier_model: Arc<dyn FrontierModel>,
@@ -43,4 +52,31 @@ impl SearchInstance {
         let cost_estimate = self.cost_model.cost_estimate(state, &dst_state)?;
         Ok(cost_estimate)
     }
+
+    /// describes the cost and traversal models of this search instance. search data
+    /// preprocessed from fixed edge costs, such as landmark tables and contraction
+    /// hierarchies, stores this description alongside its files.
+    pub fn preprocessing_info(&self) -> Result<serde_json::Value, SearchError> {
+        let cost_info = self.cost_model.serialize_cost_info()?;
+        Ok(serde_json::json!({
+            "cost_model": cost_info,
+            "traversal_model": self.traversal_config.as_ref(),
+        }))
+    }
+
+    /// tests if search data preprocessed with the given description applies to this
+    /// search instance. the edge costs must not vary by query, and the cost and
+    /// traversal models must match the ones used during preprocessing.
+    pub fn matches_preprocessing_info(
+        &self,
+        preprocessing_info: &serde_json::Value,
+    ) -> Result<bool, SearchError> {
+        if !self.traversal_model.is_query_invariant() {
+            return Ok(false);
+        }
+        match &self.default_preprocessing_info {
+            Some(info) => Ok(info.as_ref() == preprocessing_info),
+            None => Ok(&self.preprocessing_info()? == preprocessing_info),
+        }
+    }
 }
@@ -2,7 +2,7 @@ use super::search_tree_branch::SearchTreeBranch;
 use crate::model::network::vertex_id::VertexId;
 use std::collections::HashMap;
 
-#[derive(Default)]
+#[derive(Default, Debug)]
 pub struct SearchResult {
     pub tree: HashMap<VertexId, SearchTreeBranch>,
     pub iterations: u64,
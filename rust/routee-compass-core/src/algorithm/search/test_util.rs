@@ -0,0 +1,103 @@
+use super::SearchInstance;
+use crate::model::access::default::NoAccessModel;
+use crate::model::cost::{CostAggregation, CostModel, VehicleCostRate};
+use crate::model::frontier::default::no_restriction::NoRestriction;
+use crate::model::map::{MapModel, MapModelConfig};
+use crate::model::network::graph::Graph;
+use crate::model::network::{Edge, Vertex};
+use crate::model::state::{StateFeature, StateModel};
+use crate::model::termination::TerminationModel;
+use crate::model::traversal::default::DistanceTraversalModel;
+use crate::model::traversal::TraversalModel;
+use crate::model::unit::{Distance, DistanceUnit};
+use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// builds a graph with adjacency lists for the given vertices and edges
+pub fn build_graph(vertices: Vec<Vertex>, edges: Vec<Edge>) -> Graph {
+    let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    for edge in &edges {
+        adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
+        rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
+    }
+    Graph {
+        adj: adj.into_boxed_slice(),
+        rev: rev.into_boxed_slice(),
+        edges: edges.into_boxed_slice(),
+        vertices: vertices.into_boxed_slice(),
+    }
+}
+
+/// builds vertices that all share a coordinate, so that traversal model
+/// estimates are zero
+pub fn build_vertices(count: usize) -> Vec<Vertex> {
+    (0..count).map(|i| Vertex::new(i, 0.0, 0.0)).collect()
+}
+
+/// builds a graph of vertices that share a coordinate, with a pair of opposing
+/// edges for each (src, dst, distance) entry
+pub fn build_bidirectional_graph(vertex_count: usize, pairs: &[(usize, usize, f64)]) -> Graph {
+    let mut edges = vec![];
+    for (src, dst, distance) in pairs.iter() {
+        edges.push(Edge::new(edges.len(), *src, *dst, *distance));
+        edges.push(Edge::new(edges.len(), *dst, *src, *distance));
+    }
+    build_graph(build_vertices(vertex_count), edges)
+}
+
+/// builds a search instance that minimizes distance using the distance traversal model
+pub fn build_search_instance(graph: Graph, termination_model: TerminationModel) -> SearchInstance {
+    build_search_instance_with_model(
+        graph,
+        Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+        vec![(
+            String::from("distance"),
+            StateFeature::Distance {
+                distance_unit: DistanceUnit::Meters,
+                initial: Distance::new(0.0),
+            },
+        )],
+        1.0,
+        termination_model,
+    )
+}
+
+/// builds a search instance with a custom traversal model and state features. the
+/// cost is the "distance" feature scaled by `distance_weight`.
+pub fn build_search_instance_with_model(
+    graph: Graph,
+    traversal_model: Arc<dyn TraversalModel>,
+    state_features: Vec<(String, StateFeature)>,
+    distance_weight: f64,
+    termination_model: TerminationModel,
+) -> SearchInstance {
+    let graph = Arc::new(graph);
+    let map_model = Arc::new(MapModel::new(graph.clone(), MapModelConfig::default()).unwrap());
+    let state_model = Arc::new(StateModel::empty().extend(state_features).unwrap());
+    let cost_model = CostModel::new(
+        Arc::new(HashMap::from([(String::from("distance"), distance_weight)])),
+        Arc::new(HashMap::from([(
+            String::from("distance"),
+            VehicleCostRate::Raw,
+        )])),
+        Arc::new(HashMap::new()),
+        CostAggregation::Sum,
+        state_model.clone(),
+    )
+    .unwrap();
+    SearchInstance {
+        graph,
+        map_model,
+        state_model,
+        traversal_model,
+        traversal_config: Arc::new(serde_json::Value::Null),
+        default_preprocessing_info: None,
+        edge_delta_cache: None,
+        access_model: Arc::new(NoAccessModel {}),
+        cost_model: Arc::new(cost_model),
+        frontier_model: Arc::new(NoRestriction {}),
+        termination_model: Arc::new(termination_model),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod tsp_algorithm;
+mod tsp_solution;
+
+pub use tsp_solution::TspSolution;
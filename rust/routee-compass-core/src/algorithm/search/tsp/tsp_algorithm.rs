@@ -0,0 +1,418 @@
+use super::TspSolution;
+use crate::algorithm::search::{many_to_many::OdMatrix, SearchError};
+use crate::model::unit::{AsF64, Cost};
+
+/// largest number of stops solved exactly. larger sets are solved with a heuristic.
+pub const EXACT_STOP_LIMIT: usize = 10;
+
+/// improvements smaller than this are ignored by the 2-opt heuristic to avoid cycling
+/// on floating point noise
+const IMPROVEMENT_TOLERANCE: f64 = 1e-9;
+
+/// finds the least-cost order to visit a set of stops using the network costs between
+/// them. the matrix must be square, with the same stops as origins and destinations,
+/// such as the result of a many-to-many search from the stops to themselves.
+///
+/// the start and end of the tour may be fixed to a stop. when both are fixed to the same
+/// stop, the tour is a round trip that returns to it. otherwise, the tour is an open path
+/// that begins or ends at any stop unless fixed.
+///
+/// up to [`EXACT_STOP_LIMIT`] stops are solved exactly with the Held-Karp dynamic program.
+/// larger sets are solved with nearest insertion followed by 2-opt improvement, which
+/// finds a near-optimal order. costs may be asymmetric.
+///
+/// # Arguments
+///
+/// * `matrix` - least-cost paths between every pair of stops
+/// * `start`  - optional index of the stop that begins the tour
+/// * `end`    - optional index of the stop that ends the tour
+///
+/// # Returns
+///
+/// the visiting order and its cost, or an error if no tour reaches every stop
+pub fn run(
+    matrix: &OdMatrix,
+    start: Option<usize>,
+    end: Option<usize>,
+) -> Result<TspSolution, SearchError> {
+    let n = matrix.origins.len();
+    if matrix.origins != matrix.destinations {
+        return Err(SearchError::BuildError(String::from(
+            "stop order requires a matrix with the same origins and destinations",
+        )));
+    }
+    for stop in start.iter().chain(end.iter()) {
+        if *stop >= n {
+            return Err(SearchError::BuildError(format!(
+                "tour endpoint {} is not a valid index into {} stops",
+                stop, n
+            )));
+        }
+    }
+    if n == 0 {
+        return Ok(TspSolution {
+            order: vec![],
+            cost: Cost::ZERO,
+        });
+    }
+
+    let tour = Tour::new(matrix, start, end);
+    let order = if n <= EXACT_STOP_LIMIT {
+        tour.solve_exact()
+    } else {
+        tour.solve_heuristic()
+    };
+    let order = tour.close(order);
+
+    // report the first pair without a path in the best order found
+    for (a, b) in order.iter().zip(order.iter().skip(1)) {
+        if matrix.get(*a, *b).is_none() && a != b {
+            return Err(SearchError::NoPathExistsBetweenVertices(
+                matrix.origins[*a],
+                matrix.origins[*b],
+            ));
+        }
+    }
+    let cost = order
+        .iter()
+        .zip(order.iter().skip(1))
+        .map(|(a, b)| tour.cost(*a, *b))
+        .sum::<f64>();
+    Ok(TspSolution {
+        order,
+        cost: Cost::new(cost),
+    })
+}
+
+/// the costs and fixed endpoints of a tour. orders are handled without the return to
+/// the start of a round trip, which is added by [`Tour::close`].
+struct Tour {
+    costs: Vec<Vec<f64>>,
+    start: Option<usize>,
+    end: Option<usize>,
+    round_trip: bool,
+}
+
+impl Tour {
+    fn new(matrix: &OdMatrix, start: Option<usize>, end: Option<usize>) -> Tour {
+        let n = matrix.origins.len();
+        let costs = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| match matrix.get(a, b) {
+                        _ if a == b => 0.0,
+                        Some(cell) => cell.cost.as_f64(),
+                        None => f64::INFINITY,
+                    })
+                    .collect()
+            })
+            .collect();
+        let round_trip = start.is_some() && start == end;
+        Tour {
+            costs,
+            start,
+            end: if round_trip { None } else { end },
+            round_trip,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    fn cost(&self, a: usize, b: usize) -> f64 {
+        self.costs[a][b]
+    }
+
+    /// cost of visiting the stops in this order, including the return of a round trip
+    fn order_cost(&self, order: &[usize]) -> f64 {
+        let path = order
+            .iter()
+            .zip(order.iter().skip(1))
+            .map(|(a, b)| self.cost(*a, *b))
+            .sum::<f64>();
+        match (self.round_trip, order.first(), order.last()) {
+            (true, Some(first), Some(last)) => path + self.cost(*last, *first),
+            _ => path,
+        }
+    }
+
+    /// appends the return to the start of a round trip
+    fn close(&self, mut order: Vec<usize>) -> Vec<usize> {
+        if let (true, Some(first)) = (self.round_trip, order.first().cloned()) {
+            order.push(first);
+        }
+        order
+    }
+
+    /// Held-Karp dynamic program over subsets of stops. the table holds the least cost
+    /// of a path that visits the stops of a subset and ends at a given stop.
+    fn solve_exact(&self) -> Vec<usize> {
+        let n = self.len();
+        let full = (1usize << n) - 1;
+        let mut table = vec![vec![f64::INFINITY; n]; 1 << n];
+        let mut parent = vec![vec![usize::MAX; n]; 1 << n];
+        for first in 0..n {
+            let allowed = match self.start {
+                Some(s) => s == first,
+                None => self.end != Some(first) || n == 1,
+            };
+            if allowed {
+                table[1 << first][first] = 0.0;
+            }
+        }
+        for subset in 1..=full {
+            for last in 0..n {
+                let current = table[subset][last];
+                if subset & (1 << last) == 0 || current == f64::INFINITY {
+                    continue;
+                }
+                for next in 0..n {
+                    let next_subset = subset | (1 << next);
+                    if subset & (1 << next) != 0 {
+                        continue;
+                    }
+                    // a fixed end may only be visited last
+                    if self.end == Some(next) && next_subset != full {
+                        continue;
+                    }
+                    let candidate = current + self.cost(last, next);
+                    if candidate < table[next_subset][next] {
+                        table[next_subset][next] = candidate;
+                        parent[next_subset][next] = last;
+                    }
+                }
+            }
+        }
+
+        let mut best_last = None;
+        let mut best_cost = f64::INFINITY;
+        for (last, path_cost) in table[full].iter().enumerate() {
+            if self.end.is_some_and(|e| e != last) {
+                continue;
+            }
+            let mut cost = *path_cost;
+            if let (true, Some(s)) = (self.round_trip, self.start) {
+                cost += self.cost(last, s);
+            }
+            if best_last.is_none() || cost < best_cost {
+                best_last = Some(last);
+                best_cost = cost;
+            }
+        }
+
+        // walk the parents back from the last stop. when no tour has a finite cost, the
+        // parents are incomplete and the heuristic order is used instead.
+        let mut order = vec![];
+        let mut subset = full;
+        let mut current = best_last;
+        while let Some(stop) = current {
+            order.push(stop);
+            let prev = parent[subset][stop];
+            subset &= !(1 << stop);
+            current = if prev == usize::MAX { None } else { Some(prev) };
+        }
+        if order.len() != n {
+            return self.solve_heuristic();
+        }
+        order.reverse();
+        order
+    }
+
+    /// nearest insertion followed by 2-opt improvement
+    fn solve_heuristic(&self) -> Vec<usize> {
+        let mut order = self.nearest_insertion();
+        self.two_opt(&mut order);
+        order
+    }
+
+    /// builds an order by repeatedly inserting the unvisited stop nearest to any
+    /// visited stop at the position that adds the least cost
+    fn nearest_insertion(&self) -> Vec<usize> {
+        let n = self.len();
+        let mut order = match (self.start, self.end) {
+            (Some(s), Some(e)) => vec![s, e],
+            (Some(s), None) => vec![s],
+            (None, Some(e)) => vec![e],
+            (None, None) => vec![0],
+        };
+        let mut visited = vec![false; n];
+        for stop in order.iter() {
+            visited[*stop] = true;
+        }
+
+        while order.len() < n {
+            let next = (0..n)
+                .filter(|k| !visited[*k])
+                .min_by(|a, b| {
+                    let a_dist = self.distance_to_order(*a, &order);
+                    let b_dist = self.distance_to_order(*b, &order);
+                    a_dist.total_cmp(&b_dist)
+                })
+                .unwrap_or_default();
+            let position = self
+                .insertion_positions(order.len())
+                .min_by(|a, b| {
+                    let a_cost = self.insertion_cost(next, *a, &order);
+                    let b_cost = self.insertion_cost(next, *b, &order);
+                    a_cost.total_cmp(&b_cost)
+                })
+                .unwrap_or(order.len());
+            order.insert(position, next);
+            visited[next] = true;
+        }
+        order
+    }
+
+    /// least cost between a stop and any stop of the order, in either direction
+    fn distance_to_order(&self, stop: usize, order: &[usize]) -> f64 {
+        order
+            .iter()
+            .map(|v| self.cost(*v, stop).min(self.cost(stop, *v)))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// positions where a stop may be inserted into an order without displacing
+    /// a fixed start or end
+    fn insertion_positions(&self, len: usize) -> impl Iterator<Item = usize> {
+        let first = if self.start.is_some() { 1 } else { 0 };
+        let last = if self.end.is_some() { len - 1 } else { len };
+        first..=last
+    }
+
+    /// cost added by inserting a stop before the given position of the order
+    fn insertion_cost(&self, stop: usize, position: usize, order: &[usize]) -> f64 {
+        let prev = position.checked_sub(1).map(|p| order[p]);
+        let next = match order.get(position) {
+            Some(next) => Some(*next),
+            None if self.round_trip => order.first().cloned(),
+            None => None,
+        };
+        match (prev, next) {
+            (Some(p), Some(n)) => self.cost(p, stop) + self.cost(stop, n) - self.cost(p, n),
+            (Some(p), None) => self.cost(p, stop),
+            (None, Some(n)) => self.cost(stop, n),
+            (None, None) => 0.0,
+        }
+    }
+
+    /// reverses segments of the order while doing so lowers its cost. fixed endpoints
+    /// are never moved. since costs may be asymmetric, each candidate order is costed
+    /// in full.
+    fn two_opt(&self, order: &mut [usize]) {
+        let len = order.len();
+        let first = if self.start.is_some() { 1 } else { 0 };
+        let last = if self.end.is_some() {
+            len.saturating_sub(2)
+        } else {
+            len.saturating_sub(1)
+        };
+        let mut best_cost = self.order_cost(order);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in first..last {
+                for j in (i + 1)..=last {
+                    order[i..=j].reverse();
+                    let cost = self.order_cost(order);
+                    if cost < best_cost - IMPROVEMENT_TOLERANCE {
+                        best_cost = cost;
+                        improved = true;
+                    } else {
+                        order[i..=j].reverse();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::many_to_many::OdMatrixCell;
+    use crate::model::network::VertexId;
+
+    /// builds a matrix of stops on a line at the given positions, where travel costs
+    /// the distance between them plus a surcharge for travelling toward the origin
+    fn line_matrix(positions: &[f64], backtrack_surcharge: f64) -> OdMatrix {
+        let stops = (0..positions.len()).map(VertexId).collect::<Vec<_>>();
+        let cells = positions
+            .iter()
+            .map(|a| {
+                positions
+                    .iter()
+                    .map(|b| {
+                        let surcharge = if b < a { backtrack_surcharge } else { 0.0 };
+                        Some(OdMatrixCell {
+                            cost: Cost::new((b - a).abs() + surcharge),
+                            state: vec![],
+                            route: None,
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        OdMatrix {
+            origins: stops.clone(),
+            destinations: stops,
+            cells,
+            iterations: 0,
+        }
+    }
+
+    #[test]
+    fn test_exact_open_tour() {
+        let matrix = line_matrix(&[3.0, 0.0, 2.0, 1.0], 0.0);
+        let solution = run(&matrix, None, None).unwrap();
+        assert!(solution.order == vec![1, 3, 2, 0] || solution.order == vec![0, 2, 3, 1]);
+        assert_eq!(solution.cost.as_f64(), 3.0);
+    }
+
+    #[test]
+    fn test_exact_fixed_start_and_end() {
+        let matrix = line_matrix(&[3.0, 0.0, 2.0, 1.0], 0.5);
+        let solution = run(&matrix, Some(2), Some(0)).unwrap();
+        // from 2.0, backtrack once to 0.0, then visit 1.0 on the way to 3.0
+        assert_eq!(solution.order, vec![2, 1, 3, 0]);
+        assert_eq!(solution.cost.as_f64(), 2.5 + 1.0 + 2.0);
+    }
+
+    #[test]
+    fn test_exact_round_trip() {
+        let matrix = line_matrix(&[0.0, 2.0, 1.0, 3.0], 0.5);
+        let solution = run(&matrix, Some(0), Some(0)).unwrap();
+        // travel out in order, then come back once
+        assert_eq!(solution.order, vec![0, 2, 1, 3, 0]);
+        assert_eq!(solution.cost.as_f64(), 3.0 + 3.5);
+    }
+
+    #[test]
+    fn test_heuristic_matches_line_order() {
+        let positions = [7.0, 2.0, 11.0, 0.0, 5.0, 9.0, 1.0, 3.0, 10.0, 6.0, 4.0, 8.0];
+        assert!(positions.len() > EXACT_STOP_LIMIT);
+        let matrix = line_matrix(&positions, 0.5);
+        let solution = run(&matrix, Some(3), None).unwrap();
+        let visited = solution
+            .order
+            .iter()
+            .map(|i| positions[*i])
+            .collect::<Vec<_>>();
+        let expected = (0..positions.len()).map(|p| p as f64).collect::<Vec<_>>();
+        assert_eq!(visited, expected);
+        assert_eq!(solution.cost.as_f64(), 11.0);
+    }
+
+    #[test]
+    fn test_unreachable_stop() {
+        let mut matrix = line_matrix(&[0.0, 1.0, 2.0], 0.0);
+        for row in matrix.cells.iter_mut() {
+            row[2] = None;
+        }
+        let result = run(&matrix, Some(0), None);
+        assert!(matches!(
+            result,
+            Err(SearchError::NoPathExistsBetweenVertices(_, _))
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+use crate::model::unit::Cost;
+
+/// the visiting order found for a set of stops. the order lists indices into the stops
+/// in the order they are visited. for round trips, the start stop is listed again at
+/// the end of the order.
+#[derive(Clone, Debug)]
+pub struct TspSolution {
+    pub order: Vec<usize>,
+    pub cost: Cost,
+}
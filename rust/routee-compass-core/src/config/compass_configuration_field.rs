@@ -21,6 +21,7 @@ pub enum CompassConfigurationField {
     ChargeSustaining,
     ResponsePersistencePolicy,
     ResponseOutputPolicy,
+    Charging,
 }
 
 impl CompassConfigurationField {
@@ -45,6 +46,7 @@ impl CompassConfigurationField {
             CompassConfigurationField::ChargeSustaining => "charge_sustaining",
             CompassConfigurationField::ResponsePersistencePolicy => "response_persistence_policy",
             CompassConfigurationField::ResponseOutputPolicy => "response_output_policy",
+            CompassConfigurationField::Charging => "charging",
         }
     }
 }
@@ -0,0 +1,133 @@
+use super::ChargingModelError;
+use serde::{Deserialize, Serialize};
+
+/// the share of charger power a battery accepts as it fills. the curve is a list of
+/// `[soc_percent, power_fraction]` breakpoints with increasing state of charge, such as
+/// `[[0.0, 1.0], [80.0, 1.0], [100.0, 0.2]]`, which tapers charging above 80%. power is
+/// interpolated linearly between breakpoints and held constant beyond the first and last.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(try_from = "Vec<(f64, f64)>")]
+pub struct ChargingCurve(Vec<(f64, f64)>);
+
+impl TryFrom<Vec<(f64, f64)>> for ChargingCurve {
+    type Error = ChargingModelError;
+
+    fn try_from(breakpoints: Vec<(f64, f64)>) -> Result<Self, Self::Error> {
+        ChargingCurve::new(breakpoints)
+    }
+}
+
+impl Default for ChargingCurve {
+    /// charges at full power at any state of charge
+    fn default() -> Self {
+        ChargingCurve(vec![(0.0, 1.0), (100.0, 1.0)])
+    }
+}
+
+impl ChargingCurve {
+    /// step size used to integrate charging time over the curve, in percent
+    const SOC_STEP: f64 = 0.1;
+
+    pub fn new(breakpoints: Vec<(f64, f64)>) -> Result<ChargingCurve, ChargingModelError> {
+        if breakpoints.is_empty() {
+            return Err(ChargingModelError::BuildError(String::from(
+                "charging curve must have at least one breakpoint",
+            )));
+        }
+        for (soc, fraction) in breakpoints.iter() {
+            if !(0.0..=100.0).contains(soc) || *fraction <= 0.0 {
+                return Err(ChargingModelError::BuildError(format!(
+                    "charging curve breakpoint [{}, {}] must have a soc in [0, 100] and a positive power fraction",
+                    soc, fraction
+                )));
+            }
+        }
+        let increasing = breakpoints
+            .iter()
+            .zip(breakpoints.iter().skip(1))
+            .all(|((a, _), (b, _))| a < b);
+        if !increasing {
+            return Err(ChargingModelError::BuildError(String::from(
+                "charging curve breakpoints must have increasing soc",
+            )));
+        }
+        Ok(ChargingCurve(breakpoints))
+    }
+
+    /// the share of charger power accepted at a state of charge, in percent
+    pub fn power_fraction(&self, soc: f64) -> f64 {
+        let first = self.0.first().cloned().unwrap_or((0.0, 1.0));
+        if soc <= first.0 {
+            return first.1;
+        }
+        for ((soc_a, frac_a), (soc_b, frac_b)) in self.0.iter().zip(self.0.iter().skip(1)) {
+            if soc <= *soc_b {
+                let t = (soc - soc_a) / (soc_b - soc_a);
+                return frac_a + t * (frac_b - frac_a);
+            }
+        }
+        self.0.last().map(|(_, frac)| *frac).unwrap_or(1.0)
+    }
+
+    /// hours required to charge a battery between two states of charge
+    ///
+    /// # Arguments
+    ///
+    /// * `from_soc`             - starting state of charge, in percent
+    /// * `to_soc`               - final state of charge, in percent
+    /// * `battery_capacity_kwh` - battery capacity in kilowatt-hours
+    /// * `power_kw`             - rated charger power in kilowatts
+    pub fn charging_hours(
+        &self,
+        from_soc: f64,
+        to_soc: f64,
+        battery_capacity_kwh: f64,
+        power_kw: f64,
+    ) -> f64 {
+        let mut hours = 0.0;
+        let mut soc = from_soc;
+        while soc < to_soc {
+            let step = Self::SOC_STEP.min(to_soc - soc);
+            let energy_kwh = battery_capacity_kwh * step / 100.0;
+            let power = power_kw * self.power_fraction(soc + step / 2.0);
+            hours += energy_kwh / power;
+            soc += step;
+        }
+        hours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_curve() {
+        let curve = ChargingCurve::default();
+        // 30 kWh at 60 kW
+        let hours = curve.charging_hours(20.0, 80.0, 50.0, 60.0);
+        assert!(
+            (hours - 0.5).abs() < 1e-9,
+            "expected 0.5 hours, found {}",
+            hours
+        );
+    }
+
+    #[test]
+    fn test_tapered_curve() {
+        let curve = ChargingCurve::new(vec![(0.0, 1.0), (80.0, 1.0), (100.0, 0.5)]).unwrap();
+        assert_eq!(curve.power_fraction(50.0), 1.0);
+        assert_eq!(curve.power_fraction(90.0), 0.75);
+        assert_eq!(curve.power_fraction(100.0), 0.5);
+        let full_power = curve.charging_hours(70.0, 80.0, 100.0, 50.0);
+        let tapered = curve.charging_hours(80.0, 90.0, 100.0, 50.0);
+        assert!((full_power - 0.2).abs() < 1e-9);
+        assert!(tapered > full_power);
+    }
+
+    #[test]
+    fn test_invalid_curve() {
+        assert!(ChargingCurve::new(vec![(50.0, 1.0), (20.0, 1.0)]).is_err());
+        assert!(ChargingCurve::new(vec![(0.0, 0.0)]).is_err());
+    }
+}
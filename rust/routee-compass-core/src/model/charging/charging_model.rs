@@ -0,0 +1,174 @@
+use super::{
+    ChargingCurve, ChargingModelConfig, ChargingModelError, ChargingStation, ChargingStop,
+};
+use crate::model::{
+    network::VertexId,
+    state::{StateModel, StateVariable},
+    unit::{Time, TimeUnit},
+};
+use std::collections::HashMap;
+
+/// charging stations and the battery and charger behavior used to plan charging stops.
+/// charging raises the state of charge feature to the target and adds the time spent
+/// charging to the time feature.
+pub struct ChargingModel {
+    pub stations: HashMap<VertexId, ChargingStation>,
+    pub charging_curve: ChargingCurve,
+    pub battery_capacity_kwh: f64,
+    pub reserve_soc_percent: f64,
+    pub target_soc_percent: f64,
+    pub soc_feature: String,
+    pub time_feature: String,
+}
+
+impl ChargingModel {
+    pub fn new(
+        config: &ChargingModelConfig,
+        stations: Vec<ChargingStation>,
+    ) -> Result<ChargingModel, ChargingModelError> {
+        if config.battery_capacity_kwh <= 0.0 {
+            return Err(ChargingModelError::BuildError(format!(
+                "battery_capacity_kwh must be positive, found {}",
+                config.battery_capacity_kwh
+            )));
+        }
+        let reserve = config.reserve_soc_percent;
+        let target = config.target_soc_percent;
+        if !(0.0..=100.0).contains(&reserve) || !(0.0..=100.0).contains(&target) {
+            return Err(ChargingModelError::BuildError(String::from(
+                "reserve_soc_percent and target_soc_percent must be in [0, 100]",
+            )));
+        }
+        if reserve >= target {
+            return Err(ChargingModelError::BuildError(format!(
+                "reserve_soc_percent {} must be below target_soc_percent {}",
+                reserve, target
+            )));
+        }
+        if let Some(station) = stations.iter().find(|s| s.power_kw <= 0.0) {
+            return Err(ChargingModelError::BuildError(format!(
+                "charging station at vertex {} has non-positive power {}",
+                station.vertex_id, station.power_kw
+            )));
+        }
+        Ok(ChargingModel {
+            stations: stations.into_iter().map(|s| (s.vertex_id, s)).collect(),
+            charging_curve: config.charging_curve.clone(),
+            battery_capacity_kwh: config.battery_capacity_kwh,
+            reserve_soc_percent: reserve,
+            target_soc_percent: target,
+            soc_feature: config.soc_feature.clone(),
+            time_feature: config.time_feature.clone(),
+        })
+    }
+
+    /// charges the vehicle at a station until it reaches the target state of charge,
+    /// updating the state in place. a vehicle that arrives at or above the target
+    /// does not charge.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_id`   - the vertex of the charging station
+    /// * `route_index` - the number of route edges traversed before the stop
+    /// * `state`       - the state on arrival, which becomes the state on departure
+    /// * `state_model` - the state model of the search
+    ///
+    /// # Returns
+    ///
+    /// a description of the charging stop
+    pub fn charge(
+        &self,
+        vertex_id: VertexId,
+        route_index: usize,
+        state: &mut [StateVariable],
+        state_model: &StateModel,
+    ) -> Result<ChargingStop, ChargingModelError> {
+        let station = self
+            .stations
+            .get(&vertex_id)
+            .ok_or(ChargingModelError::UnknownStation(vertex_id))?;
+        let arrival_soc = state_model.get_custom_f64(state, &self.soc_feature)?;
+        let departure_soc = arrival_soc.max(self.target_soc_percent);
+        let hours = self.charging_curve.charging_hours(
+            arrival_soc,
+            departure_soc,
+            self.battery_capacity_kwh,
+            station.power_kw,
+        );
+        let dwell_time = TimeUnit::Hours.convert(&Time::new(hours), &TimeUnit::Seconds);
+        state_model.set_custom_f64(state, &self.soc_feature, &departure_soc)?;
+        state_model.add_time(state, &self.time_feature, &dwell_time, &TimeUnit::Seconds)?;
+        Ok(ChargingStop {
+            vertex_id,
+            route_index,
+            arrival_soc,
+            departure_soc,
+            dwell_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::state::{CustomFeatureFormat, StateFeature};
+    use crate::model::unit::AsF64;
+
+    #[test]
+    fn test_charge() {
+        let config: ChargingModelConfig = serde_json::from_value(serde_json::json!({
+            "stations_input_file": "stations.csv",
+            "battery_capacity_kwh": 50.0,
+            "reserve_soc_percent": 10.0,
+            "target_soc_percent": 80.0
+        }))
+        .unwrap();
+        let station = ChargingStation {
+            vertex_id: VertexId(3),
+            power_kw: 100.0,
+        };
+        let model = ChargingModel::new(&config, vec![station]).unwrap();
+        let state_model = StateModel::empty()
+            .extend(vec![
+                (
+                    String::from("time"),
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Minutes,
+                        initial: Time::new(10.0),
+                    },
+                ),
+                (
+                    String::from("battery_state"),
+                    StateFeature::Custom {
+                        r#type: String::from("soc"),
+                        unit: String::from("percent"),
+                        format: CustomFeatureFormat::FloatingPoint {
+                            initial: 20.0.into(),
+                        },
+                    },
+                ),
+            ])
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let stop = model
+            .charge(VertexId(3), 4, &mut state, &state_model)
+            .unwrap();
+
+        // 30 kWh at 100 kW takes 18 minutes
+        assert_eq!(stop.arrival_soc, 20.0);
+        assert_eq!(stop.departure_soc, 80.0);
+        assert!((stop.dwell_time.as_f64() - 1080.0).abs() < 1e-6);
+        let soc = state_model
+            .get_custom_f64(&state, &String::from("battery_state"))
+            .unwrap();
+        assert_eq!(soc, 80.0);
+        let time = state_model
+            .get_time(&state, &String::from("time"), &TimeUnit::Minutes)
+            .unwrap();
+        assert!((time.as_f64() - 28.0).abs() < 1e-6);
+
+        assert!(model
+            .charge(VertexId(0), 4, &mut state, &state_model)
+            .is_err());
+    }
+}
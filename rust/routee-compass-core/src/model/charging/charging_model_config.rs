@@ -0,0 +1,50 @@
+use super::ChargingCurve;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// configuration of the `[charging]` section, which enables inserting charging stops
+/// into routes of battery electric vehicles. for example:
+///
+/// ```toml
+/// [charging]
+/// stations_input_file = "charging_stations.csv"
+/// battery_capacity_kwh = 60.0
+/// reserve_soc_percent = 10.0
+/// target_soc_percent = 80.0
+/// charging_curve = [[0.0, 1.0], [80.0, 1.0], [100.0, 0.25]]
+/// ```
+///
+/// the stations file is a CSV with header `vertex_id,power_kw`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChargingModelConfig {
+    pub stations_input_file: PathBuf,
+    pub battery_capacity_kwh: f64,
+    #[serde(default = "ChargingModelConfig::default_reserve_soc_percent")]
+    pub reserve_soc_percent: f64,
+    #[serde(default = "ChargingModelConfig::default_target_soc_percent")]
+    pub target_soc_percent: f64,
+    #[serde(default)]
+    pub charging_curve: ChargingCurve,
+    #[serde(default = "ChargingModelConfig::default_soc_feature")]
+    pub soc_feature: String,
+    #[serde(default = "ChargingModelConfig::default_time_feature")]
+    pub time_feature: String,
+}
+
+impl ChargingModelConfig {
+    fn default_reserve_soc_percent() -> f64 {
+        10.0
+    }
+
+    fn default_target_soc_percent() -> f64 {
+        80.0
+    }
+
+    fn default_soc_feature() -> String {
+        String::from("battery_state")
+    }
+
+    fn default_time_feature() -> String {
+        String::from("time")
+    }
+}
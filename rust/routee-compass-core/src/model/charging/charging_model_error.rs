@@ -0,0 +1,14 @@
+use crate::model::{network::VertexId, state::StateModelError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ChargingModelError {
+    #[error("failed to build charging model: {0}")]
+    BuildError(String),
+    #[error("vertex {0} is not a charging station")]
+    UnknownStation(VertexId),
+    #[error("charging failed due to state model error: {source}")]
+    StateFailure {
+        #[from]
+        source: StateModelError,
+    },
+}
@@ -0,0 +1,10 @@
+use crate::model::network::VertexId;
+use serde::{Deserialize, Serialize};
+
+/// a charger located at a graph vertex, read from a row of the charging station file
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ChargingStation {
+    pub vertex_id: VertexId,
+    /// the rated power of the charger in kilowatts
+    pub power_kw: f64,
+}
@@ -0,0 +1,17 @@
+use crate::model::{network::VertexId, unit::Time};
+use allocative::Allocative;
+use serde::Serialize;
+
+/// a charging stop inserted into a route
+#[derive(Serialize, Clone, Copy, Debug, Allocative)]
+pub struct ChargingStop {
+    pub vertex_id: VertexId,
+    /// the number of route edges traversed before the stop
+    pub route_index: usize,
+    /// state of charge on arrival, in percent
+    pub arrival_soc: f64,
+    /// state of charge on departure, in percent
+    pub departure_soc: f64,
+    /// time spent charging, in seconds
+    pub dwell_time: Time,
+}
@@ -0,0 +1,13 @@
+mod charging_curve;
+mod charging_model;
+mod charging_model_config;
+mod charging_model_error;
+mod charging_station;
+mod charging_stop;
+
+pub use charging_curve::ChargingCurve;
+pub use charging_model::ChargingModel;
+pub use charging_model_config::ChargingModelConfig;
+pub use charging_model_error::ChargingModelError;
+pub use charging_station::ChargingStation;
+pub use charging_stop::ChargingStop;
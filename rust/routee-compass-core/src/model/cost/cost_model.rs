@@ -1,6 +1,6 @@
 use super::{cost_ops, network::NetworkCostRate, CostAggregation, VehicleCostRate};
 use crate::model::cost::CostModelError;
-use crate::model::network::Edge;
+use crate::model::network::{Edge, EdgeId};
 use crate::model::state::StateModel;
 use crate::model::state::StateVariable;
 use crate::model::unit::Cost;
@@ -11,12 +11,14 @@ use std::sync::Arc;
 /// implementation of a model for calculating Cost from a state transition.
 /// vectorized, where each index in these vectors matches the corresponding index
 /// in the state model.
+#[derive(Clone)]
 pub struct CostModel {
     feature_indices: Vec<(String, usize)>,
     weights: Vec<f64>,
     vehicle_rates: Vec<VehicleCostRate>,
-    network_rates: Vec<NetworkCostRate>,
+    network_rates: Arc<Vec<NetworkCostRate>>,
     cost_aggregation: CostAggregation,
+    edge_penalties: Option<Arc<HashMap<EdgeId, f64>>>,
 }
 
 impl CostModel {
@@ -78,8 +80,35 @@ impl CostModel {
             feature_indices: indices,
             weights,
             vehicle_rates,
-            network_rates,
+            network_rates: Arc::new(network_rates),
             cost_aggregation,
+            edge_penalties: None,
+        })
+    }
+
+    /// creates a copy of this cost model that multiplies the traversal cost of each
+    /// penalized edge by its penalty factor. used to steer searches away from edges
+    /// of previously-found routes. replaces any existing penalties. penalties must be
+    /// finite and at least 1, so that they never reduce the cost of an edge.
+    ///
+    /// # Arguments
+    /// * `edge_penalties` - multiplicative penalty factor for each penalized edge
+    pub fn with_edge_penalties(
+        &self,
+        edge_penalties: Arc<HashMap<EdgeId, f64>>,
+    ) -> Result<CostModel, CostModelError> {
+        if let Some((edge_id, penalty)) = edge_penalties
+            .iter()
+            .find(|(_, penalty)| !penalty.is_finite() || **penalty < 1.0)
+        {
+            return Err(CostModelError::InvalidConfiguration(format!(
+                "edge penalty for edge {} must be a finite value of at least 1, found {}",
+                edge_id, penalty
+            )));
+        }
+        Ok(CostModel {
+            edge_penalties: Some(edge_penalties),
+            ..self.clone()
         })
     }
 
@@ -116,7 +145,13 @@ impl CostModel {
             &self.cost_aggregation,
         )?;
         let total_cost = vehicle_cost + network_cost;
-        let pos_cost = Cost::enforce_strictly_positive(total_cost);
+        let penalty = self
+            .edge_penalties
+            .as_ref()
+            .and_then(|p| p.get(&edge.edge_id))
+            .cloned()
+            .unwrap_or(1.0);
+        let pos_cost = Cost::enforce_strictly_positive(total_cost * penalty);
         Ok(pos_cost)
     }
 
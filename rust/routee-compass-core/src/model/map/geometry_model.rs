@@ -21,12 +21,23 @@ impl GeometryModel {
         Ok(GeometryModel(edges))
     }
 
-    /// use a user-provided enumerated textfile input to load LineString geometries
+    /// use a user-provided enumerated textfile input to load LineString geometries.
+    /// edges appended to the graph after those in the file, such as those of a transit
+    /// layer, are drawn as straight lines between their vertices.
     pub fn new_from_edges(
         geometry_input_file: &String,
         graph: Arc<Graph>,
     ) -> Result<GeometryModel, MapError> {
-        let edges = read_linestrings(geometry_input_file, graph.edges.len())?;
+        let mut edges = read_linestrings(geometry_input_file, graph.edges.len())?;
+        for edge in graph.edges.iter().skip(edges.len()) {
+            let src_v = graph
+                .get_vertex(&edge.src_vertex_id)
+                .map_err(|e| MapError::BuildError(e.to_string()))?;
+            let dst_v = graph
+                .get_vertex(&edge.dst_vertex_id)
+                .map_err(|e| MapError::BuildError(e.to_string()))?;
+            edges.push(geo::line_string![src_v.coordinate.0, dst_v.coordinate.0]);
+        }
         Ok(GeometryModel(edges))
     }
 
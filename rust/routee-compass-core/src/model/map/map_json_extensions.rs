@@ -1,4 +1,4 @@
-use super::{map_error::MapError, map_json_key::MapJsonKey};
+use super::{map_error::MapError, map_json_key::MapJsonKey, snap_candidate::SnapCandidate};
 use crate::model::network::{EdgeId, VertexId};
 use geo;
 
@@ -13,6 +13,10 @@ pub trait MapJsonExtensions {
     fn get_destination_vertex(&self) -> Result<Option<VertexId>, MapError>;
     fn get_origin_edge(&self) -> Result<EdgeId, MapError>;
     fn get_destination_edge(&self) -> Result<Option<EdgeId>, MapError>;
+    fn add_origin_candidates(&mut self, candidates: &[SnapCandidate]) -> Result<(), MapError>;
+    fn add_destination_candidates(&mut self, candidates: &[SnapCandidate]) -> Result<(), MapError>;
+    fn get_origin_candidates(&self) -> Result<Option<Vec<SnapCandidate>>, MapError>;
+    fn get_destination_candidates(&self) -> Result<Option<Vec<SnapCandidate>>, MapError>;
 }
 
 impl MapJsonExtensions for serde_json::Value {
@@ -162,4 +166,51 @@ impl MapJsonExtensions for serde_json::Value {
             )),
         }
     }
+
+    fn add_origin_candidates(&mut self, candidates: &[SnapCandidate]) -> Result<(), MapError> {
+        add_candidates(self, MapJsonKey::OriginCandidates, candidates)
+    }
+
+    fn add_destination_candidates(&mut self, candidates: &[SnapCandidate]) -> Result<(), MapError> {
+        add_candidates(self, MapJsonKey::DestinationCandidates, candidates)
+    }
+
+    fn get_origin_candidates(&self) -> Result<Option<Vec<SnapCandidate>>, MapError> {
+        get_candidates(self, MapJsonKey::OriginCandidates)
+    }
+
+    fn get_destination_candidates(&self) -> Result<Option<Vec<SnapCandidate>>, MapError> {
+        get_candidates(self, MapJsonKey::DestinationCandidates)
+    }
+}
+
+fn add_candidates(
+    query: &mut serde_json::Value,
+    key: MapJsonKey,
+    candidates: &[SnapCandidate],
+) -> Result<(), MapError> {
+    let candidates_json = serde_json::to_value(candidates)
+        .map_err(|e| MapError::InputDeserializingError(key.to_string(), e.to_string()))?;
+    match query {
+        serde_json::Value::Object(map) => {
+            map.insert(key.to_string(), candidates_json);
+            Ok(())
+        }
+        _ => Err(MapError::InputDeserializingError(
+            String::from("<user query>"),
+            String::from("json object"),
+        )),
+    }
+}
+
+fn get_candidates(
+    query: &serde_json::Value,
+    key: MapJsonKey,
+) -> Result<Option<Vec<SnapCandidate>>, MapError> {
+    match query.get(key.to_string()) {
+        None => Ok(None),
+        Some(v) => serde_json::from_value(v.clone()).map(Some).map_err(|_| {
+            MapError::InputDeserializingError(key.to_string(), String::from("candidate list"))
+        }),
+    }
 }
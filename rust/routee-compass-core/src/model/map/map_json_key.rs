@@ -12,6 +12,8 @@ pub enum MapJsonKey {
     DestinationVertex,
     OriginEdge,
     DestinationEdge,
+    OriginCandidates,
+    DestinationCandidates,
 }
 
 impl Display for MapJsonKey {
@@ -26,6 +28,8 @@ impl Display for MapJsonKey {
             I::DestinationVertex => "destination_vertex",
             I::OriginEdge => "origin_edge",
             I::DestinationEdge => "destination_edge",
+            I::OriginCandidates => "origin_candidates",
+            I::DestinationCandidates => "destination_candidates",
         };
         write!(f, "{}", s)
     }
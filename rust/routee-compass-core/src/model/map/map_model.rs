@@ -1,6 +1,7 @@
 use super::map_error::MapError;
 use super::map_model_config::MapModelConfig;
 use super::matching_type::MatchingType;
+use super::snap_candidate::SnapCandidateConfig;
 use super::spatial_index::SpatialIndex;
 use super::{geometry_model::GeometryModel, matching_type::MapInputResult};
 use crate::algorithm::search::SearchInstance;
@@ -13,21 +14,47 @@ pub struct MapModel {
     pub spatial_index: SpatialIndex,
     pub geometry_model: GeometryModel,
     pub queries_without_destinations: bool,
+    pub candidates: Option<SnapCandidateConfig>,
 }
 
 impl MapModel {
     pub fn new(graph: Arc<Graph>, config: MapModelConfig) -> Result<MapModel, MapError> {
+        let (n_vertices, n_edges) = (graph.n_vertices(), graph.n_edges());
+        MapModel::new_for_road_network(graph, config, n_vertices, n_edges)
+    }
+
+    /// builds a map model that only matches queries to the road network of the graph,
+    /// the first `road_vertex_count` vertices and `road_edge_count` edges. vertices and
+    /// edges appended after the road network, such as those of a transit layer, are not
+    /// spatially indexed.
+    pub fn new_for_road_network(
+        graph: Arc<Graph>,
+        config: MapModelConfig,
+        road_vertex_count: usize,
+        road_edge_count: usize,
+    ) -> Result<MapModel, MapError> {
+        let road_vertices = &graph.vertices[..road_vertex_count.min(graph.n_vertices())];
+        let road_edges = &graph.edges[..road_edge_count.min(graph.n_edges())];
         let matching_type = config.get_matching_type()?;
+        let candidates = config.get_candidates();
+        if let Some(c) = candidates {
+            if c.count == 0 || c.cost_per_meter < 0.0 {
+                return Err(MapError::BuildError(format!(
+                    "map model candidates require a positive count and non-negative cost_per_meter, found {:?}",
+                    c
+                )));
+            }
+        }
         match config {
             MapModelConfig::VertexMapModelConfig {
                 tolerance,
                 geometry_input_file,
                 queries_without_destinations,
                 matching_type: _,
+                candidates: _,
             } => {
                 let tol_unpacked = tolerance.map(|t| t.unpack());
-                let spatial_index =
-                    SpatialIndex::new_vertex_oriented(&graph.clone().vertices, tol_unpacked);
+                let spatial_index = SpatialIndex::new_vertex_oriented(road_vertices, tol_unpacked);
                 let geometry_model = match geometry_input_file {
                     None => GeometryModel::new_from_vertices(graph),
                     Some(file) => GeometryModel::new_from_edges(&file, graph.clone()),
@@ -38,6 +65,7 @@ impl MapModel {
                     spatial_index,
                     geometry_model,
                     queries_without_destinations,
+                    candidates,
                 };
                 Ok(map_model)
             }
@@ -46,17 +74,19 @@ impl MapModel {
                 geometry_input_file,
                 queries_without_destinations,
                 matching_type: _,
+                candidates: _,
             } => {
                 let tol_unpacked = tolerance.map(|t| t.unpack());
                 let geometry_model =
                     GeometryModel::new_from_edges(&geometry_input_file, graph.clone())?;
                 let spatial_index =
-                    SpatialIndex::new_edge_oriented(graph.clone(), &geometry_model, tol_unpacked);
+                    SpatialIndex::new_edge_oriented(road_edges, &geometry_model, tol_unpacked);
                 let map_model = MapModel {
                     matching_type,
                     spatial_index,
                     geometry_model,
                     queries_without_destinations,
+                    candidates,
                 };
                 Ok(map_model)
             }
@@ -80,4 +110,28 @@ impl MapModel {
             _ => Ok(()),
         }
     }
+
+    /// matches only the origin fields of a query to the map
+    pub fn match_origin(
+        &self,
+        query: &mut serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<(), MapError> {
+        self.matching_type.process_origin(query, si)
+    }
+
+    /// matches only the destination fields of a query to the map, failing if
+    /// no destination is found on the query
+    pub fn match_destination(
+        &self,
+        query: &mut serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<(), MapError> {
+        match self.matching_type.process_destination(query, si)? {
+            MapInputResult::NotFound => {
+                Err(MapError::DestinationsRequired(self.matching_type.clone()))
+            }
+            MapInputResult::Found => Ok(()),
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use super::{map_error::MapError, matching_type::MatchingType};
+use super::{
+    map_error::MapError, matching_type::MatchingType, snap_candidate::SnapCandidateConfig,
+};
 use crate::model::unit::{Distance, DistanceUnit};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -14,6 +16,8 @@ pub enum MapModelConfig {
         geometry_input_file: Option<String>,
         queries_without_destinations: bool,
         matching_type: Option<Vec<String>>,
+        #[serde(default)]
+        candidates: Option<SnapCandidateConfig>,
     },
     #[serde(rename = "edge")]
     EdgeMapModelConfig {
@@ -22,6 +26,8 @@ pub enum MapModelConfig {
         geometry_input_file: String,
         queries_without_destinations: bool,
         matching_type: Option<Vec<String>>,
+        #[serde(default)]
+        candidates: Option<SnapCandidateConfig>,
     },
 }
 
@@ -33,12 +39,14 @@ impl MapModelConfig {
                 geometry_input_file: _,
                 queries_without_destinations: _,
                 matching_type,
+                candidates: _,
             } => matching_type,
             MapModelConfig::EdgeMapModelConfig {
                 tolerance: _,
                 geometry_input_file: _,
                 queries_without_destinations: _,
                 matching_type,
+                candidates: _,
             } => matching_type,
         };
         match matching_type {
@@ -59,6 +67,16 @@ impl MapModelConfig {
     }
 }
 
+impl MapModelConfig {
+    /// the configuration for matching query coordinates to sets of candidates, if any
+    pub fn get_candidates(&self) -> Option<SnapCandidateConfig> {
+        match self {
+            MapModelConfig::VertexMapModelConfig { candidates, .. } => *candidates,
+            MapModelConfig::EdgeMapModelConfig { candidates, .. } => *candidates,
+        }
+    }
+}
+
 impl Default for MapModelConfig {
     fn default() -> Self {
         MapModelConfig::VertexMapModelConfig {
@@ -66,6 +84,7 @@ impl Default for MapModelConfig {
             geometry_input_file: None,
             queries_without_destinations: true,
             matching_type: Some(MatchingType::names()),
+            candidates: None,
         }
     }
 }
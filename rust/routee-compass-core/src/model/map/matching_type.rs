@@ -1,10 +1,16 @@
 use super::{
-    map_error::MapError, map_json_extensions::MapJsonExtensions,
+    map_error::MapError,
+    map_json_extensions::MapJsonExtensions,
     nearest_search_result::NearestSearchResult,
+    snap_candidate::{SnapCandidate, SnapCandidateConfig},
 };
 use crate::{
     algorithm::search::SearchInstance,
-    model::{frontier::FrontierModel, network::Edge},
+    model::{
+        frontier::FrontierModel,
+        network::Edge,
+        unit::{AsF64, Cost},
+    },
 };
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -136,6 +142,10 @@ impl MatchingType {
                 // iterate through nearest values in the spatial index to this point that
                 // are within our matching tolerance and validate them with the frontier model
                 let src_point = geo::Point(query.get_origin_coordinate()?);
+                if let Some(config) = &si.map_model.candidates {
+                    let candidates = point_candidates(&src_point, true, config, si)?;
+                    query.add_origin_candidates(&candidates)?;
+                }
                 for nearest in si.map_model.spatial_index.nearest_graph_id_iter(&src_point) {
                     match nearest {
                         NearestSearchResult::NearestVertex(vertex_id) => {
@@ -240,6 +250,10 @@ impl MatchingType {
                     Some(coord) => geo::Point(coord),
                     None => return Ok(MapInputResult::NotFound),
                 };
+                if let Some(config) = &si.map_model.candidates {
+                    let candidates = point_candidates(&dst_point, false, config, si)?;
+                    query.add_destination_candidates(&candidates)?;
+                }
 
                 for nearest in si.map_model.spatial_index.nearest_graph_id_iter(&dst_point) {
                     match nearest {
@@ -275,6 +289,77 @@ impl MatchingType {
     }
 }
 
+/// finds the nearest candidate vertices to a query coordinate, up to the configured count.
+/// vertices are accepted if any edge leaving an origin candidate, or entering a destination
+/// candidate, is valid according to the frontier model. nearby edges contribute the vertex
+/// at which a route leaves them (origin) or enters them (destination).
+fn point_candidates(
+    point: &geo::Point<f32>,
+    origin: bool,
+    config: &SnapCandidateConfig,
+    si: &SearchInstance,
+) -> Result<Vec<SnapCandidate>, MapError> {
+    let mut candidates: Vec<SnapCandidate> = vec![];
+    for nearest in si
+        .map_model
+        .spatial_index
+        .nearest_graph_id_distance_iter(point, &si.map_model.geometry_model)
+    {
+        if candidates.len() >= config.count {
+            break;
+        }
+        let (nearest, distance) = nearest?;
+        let vertex_id = match nearest {
+            NearestSearchResult::NearestVertex(vertex_id) => {
+                let edge_ids = if origin {
+                    si.graph.out_edges(&vertex_id)
+                } else {
+                    si.graph.in_edges(&vertex_id)
+                };
+                let mut is_valid = false;
+                for edge_id in edge_ids.iter() {
+                    let edge = si.graph.get_edge(edge_id).map_err(|e| MapError::MapMatchError(format!("while attempting to validate vertex id {} for map matching, the underlying Graph model caused an error: {}", vertex_id, e)))?;
+                    if test_edge(edge, si.frontier_model.clone())? {
+                        is_valid = true;
+                        break;
+                    }
+                }
+                if !is_valid {
+                    continue;
+                }
+                vertex_id
+            }
+            NearestSearchResult::NearestEdge(edge_id) => {
+                let edge = si.graph.get_edge(&edge_id).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge id {} from nearest neighbor search for map matching, the underlying Graph model caused an error: {}", edge_id, e)))?;
+                if !test_edge(edge, si.frontier_model.clone())? {
+                    continue;
+                }
+                if origin {
+                    edge.dst_vertex_id
+                } else {
+                    edge.src_vertex_id
+                }
+            }
+        };
+        if candidates.iter().any(|c| c.vertex_id == vertex_id) {
+            continue;
+        }
+        candidates.push(SnapCandidate {
+            vertex_id,
+            distance,
+            access_cost: Cost::new(distance.as_f64() * config.cost_per_meter),
+        });
+    }
+    if candidates.is_empty() {
+        return Err(MapError::MapMatchError(format!(
+            "attempted to match query coordinate ({}, {}) to map candidates but exhausted all possibilities",
+            point.x(),
+            point.y(),
+        )));
+    }
+    Ok(candidates)
+}
+
 fn test_edge(edge: &Edge, fm: Arc<dyn FrontierModel>) -> Result<bool, MapError> {
     let is_valid = fm.valid_edge(edge).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge id {} for map matching, the underlying FrontierModel caused an error: {}", edge.edge_id, e)))?;
     Ok(is_valid)
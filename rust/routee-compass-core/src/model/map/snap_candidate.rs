@@ -0,0 +1,35 @@
+use crate::model::{
+    network::VertexId,
+    unit::{Cost, Distance},
+};
+use serde::{Deserialize, Serialize};
+
+/// configures map matching of query coordinates to a set of candidate vertices
+/// instead of a single nearest vertex or edge. searches then choose the pair of
+/// origin and destination candidates with the least total cost. for example:
+///
+/// ```toml
+/// [mapping.candidates]
+/// count = 3
+/// cost_per_meter = 0.001
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct SnapCandidateConfig {
+    /// the number of candidates to match for each query coordinate
+    pub count: usize,
+    /// the access cost assigned to each meter between a query coordinate and a candidate
+    #[serde(default)]
+    pub cost_per_meter: f64,
+}
+
+/// a vertex that a query coordinate may be matched to, with the access cost of
+/// snapping the coordinate to the vertex. for edge-oriented map models, an origin
+/// candidate is the destination vertex of a nearby edge and a destination candidate
+/// is the source vertex of a nearby edge.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct SnapCandidate {
+    pub vertex_id: VertexId,
+    /// distance between the query coordinate and the matched map feature, in meters
+    pub distance: Distance,
+    pub access_cost: Cost,
+}
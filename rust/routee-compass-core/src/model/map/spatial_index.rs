@@ -1,12 +1,14 @@
-use std::sync::Arc;
-
 use super::{
     geometry_model::GeometryModel, map_edge_rtree_object::MapEdgeRTreeObject, map_error::MapError,
     map_vertex_rtree_object::MapVertexRTreeObject, nearest_search_result::NearestSearchResult,
+    spatial_index_ops as ops,
 };
-use crate::model::{
-    network::{Graph, Vertex},
-    unit::{Distance, DistanceUnit},
+use crate::{
+    model::{
+        network::{Edge, EdgeId, Vertex},
+        unit::{Distance, DistanceUnit},
+    },
+    util::geo::haversine,
 };
 use geo::Point;
 use rstar::RTree;
@@ -37,16 +39,15 @@ impl SpatialIndex {
     }
 
     /// creates a new instance of the rtree model that is edge-oriented; that is, the
-    /// rtree is built over the edges in the graph, and nearest neighbor searches return
-    /// the edge's destination vertex.
+    /// rtree is built over the provided edges of the graph, and nearest neighbor searches
+    /// return the edge's destination vertex.
     /// - future work: make SearchOrientation set which incident vertex is returned.
     pub fn new_edge_oriented(
-        graph: Arc<Graph>,
+        edges: &[Edge],
         geometry_model: &GeometryModel,
         tolerance: Option<(Distance, DistanceUnit)>,
     ) -> Self {
-        let entries: Vec<MapEdgeRTreeObject> = graph
-            .edges
+        let entries: Vec<MapEdgeRTreeObject> = edges
             .iter()
             .zip(geometry_model.geometries())
             .map(|(e, g)| MapEdgeRTreeObject::new(e, g))
@@ -100,15 +101,84 @@ impl SpatialIndex {
             }
         }
     }
+
+    /// builds an iterator over map features ordered by nearness to the given point, along
+    /// with the distance in meters from the point to each feature. edges are measured to
+    /// their geometry in the geometry model rather than to their bounding box. applies the
+    /// (map-matching) distance tolerance filter.
+    pub fn nearest_graph_id_distance_iter<'a>(
+        &'a self,
+        point: &'a Point<f32>,
+        geometry_model: &'a GeometryModel,
+    ) -> Box<dyn Iterator<Item = Result<(NearestSearchResult, Distance), MapError>> + 'a> {
+        match self {
+            SpatialIndex::VertexOrientedIndex { rtree, tolerance } => {
+                let iter = rtree
+                    .nearest_neighbor_iter_with_distance_2(point)
+                    .filter(|(obj, _)| obj.test_threshold(point, tolerance).unwrap_or(false))
+                    .map(|(next, _)| {
+                        let distance = ops::distance_meters(&next.envelope, point)?;
+                        Ok((NearestSearchResult::NearestVertex(next.vertex_id), distance))
+                    });
+                Box::new(iter)
+            }
+            SpatialIndex::EdgeOrientedIndex { rtree, tolerance } => {
+                // the rtree orders edges by their bounding boxes, which are never farther
+                // than their geometries. edges are held back until no remaining bounding
+                // box is nearer than their geometry, so that they are returned in order.
+                let mut nearest = rtree
+                    .nearest_neighbor_iter_with_distance_2(point)
+                    .filter(|(obj, _)| obj.test_threshold(point, tolerance).unwrap_or(false))
+                    .peekable();
+                let mut pending: Vec<(f32, EdgeId, Point<f32>)> = vec![];
+                let iter = std::iter::from_fn(move || {
+                    loop {
+                        let nearest_pending = pending.iter().map(|(d, _, _)| *d).reduce(f32::min);
+                        match nearest.peek() {
+                            Some((_, envelope_distance_2))
+                                if nearest_pending.is_none_or(|d| *envelope_distance_2 < d) => {}
+                            _ => break,
+                        }
+                        let (next, _) = nearest.next()?;
+                        let closest = match geometry_model
+                            .get(&next.edge_id)
+                            .and_then(|linestring| ops::closest_point(linestring, point))
+                        {
+                            Ok(closest) => closest,
+                            Err(e) => return Some(Err(e)),
+                        };
+                        let distance_2 =
+                            (closest.x() - point.x()).powi(2) + (closest.y() - point.y()).powi(2);
+                        pending.push((distance_2, next.edge_id, closest));
+                    }
+                    let (idx, _) = pending
+                        .iter()
+                        .enumerate()
+                        .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))?;
+                    let (_, edge_id, closest) = pending.swap_remove(idx);
+                    let result = haversine::coord_distance_meters(&closest.0, &point.0)
+                        .map_err(MapError::MapMatchError)
+                        .map(|distance| (NearestSearchResult::NearestEdge(edge_id), distance));
+                    Some(result)
+                });
+                Box::new(iter)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::path::PathBuf;
+    use std::sync::Arc;
 
     use super::*;
     use crate::{
-        model::network::{Vertex, VertexId},
+        algorithm::search::test_util::build_graph,
+        model::{
+            network::{Vertex, VertexId},
+            unit::AsF64,
+        },
         util::fs::read_utils,
     };
     use geo;
@@ -149,4 +219,40 @@ mod test {
             NearestSearchResult::NearestVertex(vertex_id) => assert_eq!(vertex_id, VertexId(2)),
         }
     }
+
+    #[test]
+    fn test_edge_distance_to_geometry() {
+        // the diagonal edge 0 has a bounding box containing the point, but edge 1 passes
+        // closer to the point
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 1.0, 1.0),
+            Vertex::new(2, 0.9, 0.2),
+            Vertex::new(3, 1.5, 0.2),
+        ];
+        let edges = vec![Edge::new(0, 0, 1, 1.0), Edge::new(1, 2, 3, 1.0)];
+        let graph = Arc::new(build_graph(vertices, edges));
+        let geometry_model = GeometryModel::new_from_vertices(graph.clone()).unwrap();
+        let index = SpatialIndex::new_edge_oriented(&graph.edges, &geometry_model, None);
+
+        let point = geo::Point(geo::Coord::from((0.9, 0.1)));
+        let nearest = index
+            .nearest_graph_id_distance_iter(&point, &geometry_model)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let edge_ids = nearest
+            .iter()
+            .map(|(n, _)| match n {
+                NearestSearchResult::NearestEdge(edge_id) => edge_id.0,
+                NearestSearchResult::NearestVertex(_) => panic!("should find an edge!"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![1, 0]);
+        // edge 1 is 0.1 degrees of latitude away, about 11.1 kilometers
+        let (_, distance) = nearest[0];
+        assert!((distance.as_f64() - 11_119.5).abs() < 10.0);
+        // edge 0 is about 0.566 degrees away at its nearest point
+        let (_, distance) = nearest[1];
+        assert!(distance.as_f64() > 60_000.0);
+    }
 }
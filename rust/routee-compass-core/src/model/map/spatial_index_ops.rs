@@ -3,7 +3,7 @@ use crate::{
     model::unit::{Distance, DistanceUnit},
     util::geo::haversine,
 };
-use geo::Point;
+use geo::{Closest, ClosestPoint, LineString, Point};
 use rstar::AABB;
 
 pub fn test_threshold(
@@ -45,3 +45,26 @@ pub fn within_threshold(
         Ok(())
     }
 }
+
+/// the distance in meters between a point and the nearest point of an envelope
+pub fn distance_meters(
+    envelope: &AABB<Point<f32>>,
+    other: &Point<f32>,
+) -> Result<Distance, MapError> {
+    let nearest = envelope.min_point(other);
+    haversine::coord_distance_meters(&nearest.0, &other.0).map_err(MapError::MapMatchError)
+}
+
+/// the point of a linestring nearest to another point
+pub fn closest_point(
+    linestring: &LineString<f32>,
+    other: &Point<f32>,
+) -> Result<Point<f32>, MapError> {
+    match linestring.closest_point(other) {
+        Closest::Intersection(p) | Closest::SinglePoint(p) => Ok(p),
+        Closest::Indeterminate => Err(MapError::MapMatchError(format!(
+            "cannot find the point of linestring {:?} nearest to {:?}",
+            linestring, other
+        ))),
+    }
+}
@@ -1,9 +1,11 @@
 pub mod access;
+pub mod charging;
 pub mod cost;
 pub mod frontier;
 pub mod map;
 pub mod network;
 pub mod state;
 pub mod termination;
+pub mod transit;
 pub mod traversal;
 pub mod unit;
@@ -137,6 +137,69 @@ impl Graph {
 
         Ok(graph)
     }
+
+    /// appends vertices and edges to the Graph, such as those of an additional
+    /// network layer. new ids must continue the consecutive ids of the Graph, and
+    /// new edges may connect to existing vertices.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertices` - vertices to append, with ids beginning at `n_vertices`
+    /// * `edges` - edges to append, with ids beginning at `n_edges`
+    ///
+    /// # Returns
+    ///
+    /// An error if the ids are not consecutive or an edge references a missing vertex.
+    pub fn extend(&mut self, vertices: Vec<Vertex>, edges: Vec<Edge>) -> Result<(), NetworkError> {
+        let n_vertices = self.n_vertices() + vertices.len();
+        for (index, vertex) in vertices.iter().enumerate() {
+            if vertex.vertex_id.0 != self.n_vertices() + index {
+                return Err(NetworkError::DatasetError(format!(
+                    "extending graph with vertex id {} where {} was expected",
+                    vertex.vertex_id,
+                    self.n_vertices() + index
+                )));
+            }
+        }
+        for (index, edge) in edges.iter().enumerate() {
+            if edge.edge_id.0 != self.n_edges() + index {
+                return Err(NetworkError::DatasetError(format!(
+                    "extending graph with edge id {} where {} was expected",
+                    edge.edge_id,
+                    self.n_edges() + index
+                )));
+            }
+            if edge.src_vertex_id.0 >= n_vertices {
+                return Err(NetworkError::VertexNotFound(edge.src_vertex_id));
+            }
+            if edge.dst_vertex_id.0 >= n_vertices {
+                return Err(NetworkError::VertexNotFound(edge.dst_vertex_id));
+            }
+        }
+
+        let mut adj = std::mem::take(&mut self.adj).into_vec();
+        let mut rev = std::mem::take(&mut self.rev).into_vec();
+        adj.resize(n_vertices, CompactOrderedHashMap::empty());
+        rev.resize(n_vertices, CompactOrderedHashMap::empty());
+        for edge in edges.iter() {
+            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
+        }
+        self.adj = adj.into_boxed_slice();
+        self.rev = rev.into_boxed_slice();
+        self.edges = std::mem::take(&mut self.edges)
+            .into_vec()
+            .into_iter()
+            .chain(edges)
+            .collect();
+        self.vertices = std::mem::take(&mut self.vertices)
+            .into_vec()
+            .into_iter()
+            .chain(vertices)
+            .collect();
+        Ok(())
+    }
+
     /// number of edges in the Graph
     pub fn n_edges(&self) -> usize {
         self.edges.len()
@@ -72,6 +72,31 @@ impl CustomFeatureFormat {
         }
     }
 
+    /// creates a copy of this format with the initial value replaced by
+    /// the decoded state variable
+    pub fn with_initial(&self, value: &StateVariable) -> Result<Self, StateModelError> {
+        match self {
+            CustomFeatureFormat::FloatingPoint { initial: _ } => {
+                Ok(CustomFeatureFormat::FloatingPoint {
+                    initial: OrderedFloat(self.decode_f64(value)?),
+                })
+            }
+            CustomFeatureFormat::SignedInteger { initial: _ } => {
+                Ok(CustomFeatureFormat::SignedInteger {
+                    initial: self.decode_i64(value)?,
+                })
+            }
+            CustomFeatureFormat::UnsignedInteger { initial: _ } => {
+                Ok(CustomFeatureFormat::UnsignedInteger {
+                    initial: self.decode_u64(value)?,
+                })
+            }
+            CustomFeatureFormat::Boolean { initial: _ } => Ok(CustomFeatureFormat::Boolean {
+                initial: self.decode_bool(value)?,
+            }),
+        }
+    }
+
     pub fn encode_f64(&self, value: &f64) -> Result<StateVariable, StateModelError> {
         match self {
             CustomFeatureFormat::FloatingPoint { initial: _ } => Ok(StateVariable(*value)),
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 
 /// a state variable unit tracks the domain of a StateVar in a
 /// state vector. if the value represents quantity in distance,
-/// time, or energy, then we have a system of internal unit
+/// time, energy or currency, then we have a system of internal unit
 /// objects which provide conversion arithmetic. if the user
 /// specifies a StateVar has a custom state variable unit, then
 /// they provide a mapping codec and name for the variable, and
@@ -24,6 +24,7 @@ use serde::{Deserialize, Serialize};
 /// state = [
 ///   { distance_unit = "kilometers", initial = 0.0 },
 ///   { time_unit = "minutes", initial = 0.0 },
+///   { currency_unit = "dollars", initial = 0.0 },
 ///   { name = "soc", unit = "percent", format = { type = "floating_point", initial = 0.0 } }
 /// ]
 ///
@@ -46,6 +47,10 @@ pub enum StateFeature {
         energy_unit: unit::EnergyUnit,
         initial: unit::Energy,
     },
+    Currency {
+        currency_unit: unit::CurrencyUnit,
+        initial: unit::Currency,
+    },
     Custom {
         r#type: String,
         unit: String,
@@ -56,7 +61,7 @@ pub enum StateFeature {
 impl PartialEq for StateFeature {
     /// tests equality based on the feature type.
     ///
-    /// for distance|time|energy, it's fine to modify either the unit
+    /// for distance|time|energy|currency, it's fine to modify either the unit
     /// or the initial value as this should not interfere with properly-
     /// implemented TraversalModel, AccessModel, and FrontierModel instances.
     ///
@@ -95,6 +100,16 @@ impl PartialEq for StateFeature {
                     initial: _,
                 },
             ) => true,
+            (
+                StateFeature::Currency {
+                    currency_unit: _,
+                    initial: _,
+                },
+                StateFeature::Currency {
+                    currency_unit: _,
+                    initial: _,
+                },
+            ) => true,
             (
                 StateFeature::Custom {
                     r#type: a_name,
@@ -126,6 +141,10 @@ impl Display for StateFeature {
                 energy_unit,
                 initial,
             } => write!(f, "unit: {}, initial: {}", energy_unit, initial),
+            StateFeature::Currency {
+                currency_unit,
+                initial,
+            } => write!(f, "unit: {}, initial: {}", currency_unit, initial),
             StateFeature::Custom {
                 r#type: name,
                 unit,
@@ -152,6 +171,10 @@ impl StateFeature {
                 energy_unit: _,
                 initial: _,
             } => String::from("energy"),
+            StateFeature::Currency {
+                currency_unit: _,
+                initial: _,
+            } => String::from("currency"),
             StateFeature::Custom {
                 r#type,
                 unit: _,
@@ -174,6 +197,10 @@ impl StateFeature {
                 energy_unit,
                 initial: _,
             } => energy_unit.to_string(),
+            StateFeature::Currency {
+                currency_unit,
+                initial: _,
+            } => currency_unit.to_string(),
             StateFeature::Custom {
                 r#type: _,
                 unit,
@@ -211,6 +238,10 @@ impl StateFeature {
                 energy_unit: _,
                 initial,
             } => Ok((*initial).into()),
+            StateFeature::Currency {
+                currency_unit: _,
+                initial,
+            } => Ok((*initial).into()),
             StateFeature::Custom {
                 r#type: _,
                 unit: _,
@@ -219,6 +250,49 @@ impl StateFeature {
         }
     }
 
+    /// creates a copy of this feature that starts from the provided value
+    pub fn with_initial(&self, value: &StateVariable) -> Result<StateFeature, StateModelError> {
+        match self {
+            StateFeature::Distance {
+                distance_unit,
+                initial: _,
+            } => Ok(StateFeature::Distance {
+                distance_unit: *distance_unit,
+                initial: (*value).into(),
+            }),
+            StateFeature::Time {
+                time_unit,
+                initial: _,
+            } => Ok(StateFeature::Time {
+                time_unit: *time_unit,
+                initial: (*value).into(),
+            }),
+            StateFeature::Energy {
+                energy_unit,
+                initial: _,
+            } => Ok(StateFeature::Energy {
+                energy_unit: *energy_unit,
+                initial: (*value).into(),
+            }),
+            StateFeature::Currency {
+                currency_unit,
+                initial: _,
+            } => Ok(StateFeature::Currency {
+                currency_unit: *currency_unit,
+                initial: (*value).into(),
+            }),
+            StateFeature::Custom {
+                r#type,
+                unit,
+                format,
+            } => Ok(StateFeature::Custom {
+                r#type: r#type.clone(),
+                unit: unit.clone(),
+                format: format.with_initial(value)?,
+            }),
+        }
+    }
+
     pub fn get_distance_unit(&self) -> Result<unit::DistanceUnit, StateModelError> {
         match self {
             StateFeature::Distance {
@@ -258,6 +332,19 @@ impl StateFeature {
         }
     }
 
+    pub fn get_currency_unit(&self) -> Result<unit::CurrencyUnit, StateModelError> {
+        match self {
+            StateFeature::Currency {
+                currency_unit,
+                initial: _,
+            } => Ok(*currency_unit),
+            _ => Err(StateModelError::UnexpectedFeatureUnit(
+                String::from("currency"),
+                self.get_feature_type(),
+            )),
+        }
+    }
+
     pub fn get_custom_feature_format(&self) -> Result<&CustomFeatureFormat, StateModelError> {
         match self {
             StateFeature::Custom {
@@ -5,7 +5,9 @@ use super::{
 };
 use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
 use crate::{
-    model::unit::{Distance, DistanceUnit, Energy, EnergyUnit, Time, TimeUnit},
+    model::unit::{
+        Currency, CurrencyUnit, Distance, DistanceUnit, Energy, EnergyUnit, Time, TimeUnit,
+    },
     util::compact_ordered_hash_map::IndexedEntry,
 };
 use itertools::Itertools;
@@ -116,6 +118,37 @@ impl StateModel {
             .collect::<Result<Vec<_>, _>>()
     }
 
+    /// creates a copy of this state model whose initial state is the provided state.
+    /// used to continue a search from where a previous search ended, such as the
+    /// legs of a multi-stop route.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state vector that becomes the initial state
+    ///
+    /// # Returns
+    ///
+    /// a state model with the same features starting from `state`, or an error
+    /// if the state does not match this model
+    pub fn with_initial_state(
+        &self,
+        state: &[StateVariable],
+    ) -> Result<StateModel, StateModelError> {
+        if state.len() != self.len() {
+            return Err(StateModelError::RuntimeError(format!(
+                "cannot set initial state with {} variables on state model with {} features",
+                state.len(),
+                self.len()
+            )));
+        }
+        let features = self
+            .iter()
+            .zip(state.iter())
+            .map(|((name, feature), value)| Ok((name.clone(), feature.with_initial(value)?)))
+            .collect::<Result<Vec<_>, StateModelError>>()?;
+        Ok(StateModel::new(features))
+    }
+
     /// retrieves a state variable that is expected to have a type of Distance
     ///
     /// # Arguments
@@ -179,6 +212,27 @@ impl StateModel {
         let result = feature.get_energy_unit()?.convert(&value.into(), unit);
         Ok(result)
     }
+    /// retrieves a state variable that is expected to have a type of Currency
+    ///
+    /// # Arguments
+    /// * `state` - state vector to inspect
+    /// * `name`  - feature name to extract
+    /// * `unit`  - feature is converted to this unit before returning
+    ///
+    /// # Returns
+    ///
+    /// feature value in the expected unit type, or an error
+    pub fn get_currency(
+        &self,
+        state: &[StateVariable],
+        name: &String,
+        unit: &CurrencyUnit,
+    ) -> Result<Currency, StateModelError> {
+        let value = self.get_state_variable(state, name)?;
+        let feature = self.get_feature(name)?;
+        let result = feature.get_currency_unit()?.convert(&value.into(), unit);
+        Ok(result)
+    }
     /// retrieves a state variable that is expected to have a type of f64.
     ///
     /// # Arguments
@@ -335,6 +389,19 @@ impl StateModel {
         self.set_energy(state, name, &next_energy, from_unit)
     }
 
+    /// adds a currency value with currency unit to this feature vector
+    pub fn add_currency(
+        &self,
+        state: &mut [StateVariable],
+        name: &String,
+        currency: &Currency,
+        from_unit: &CurrencyUnit,
+    ) -> Result<(), StateModelError> {
+        let prev_currency = self.get_currency(state, name, from_unit)?;
+        let next_currency = prev_currency + *currency;
+        self.set_currency(state, name, &next_currency, from_unit)
+    }
+
     pub fn set_distance(
         &self,
         state: &mut [StateVariable],
@@ -374,6 +441,19 @@ impl StateModel {
         self.update_state(state, name, &value.into(), UpdateOperation::Replace)
     }
 
+    pub fn set_currency(
+        &self,
+        state: &mut [StateVariable],
+        name: &String,
+        currency: &Currency,
+        from_unit: &CurrencyUnit,
+    ) -> Result<(), StateModelError> {
+        let feature = self.get_feature(name)?;
+        let to_unit = feature.get_currency_unit()?;
+        let value = from_unit.convert(currency, &to_unit);
+        self.update_state(state, name, &value.into(), UpdateOperation::Replace)
+    }
+
     pub fn set_custom_f64(
         &self,
         state: &mut [StateVariable],
@@ -467,7 +547,7 @@ impl StateModel {
     }
 
     /// gets a state variable from a state vector by name
-    fn get_state_variable(
+    pub fn get_state_variable(
         &self,
         state: &[StateVariable],
         name: &String,
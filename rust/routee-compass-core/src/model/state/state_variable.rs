@@ -1,4 +1,4 @@
-use crate::model::unit::{AsF64, Distance, Energy, Time};
+use crate::model::unit::{AsF64, Currency, Distance, Energy, Time};
 use allocative::Allocative;
 use derive_more::{Add, Div, Mul, Neg, Sub, Sum};
 use serde::{Deserialize, Serialize};
@@ -50,6 +50,11 @@ impl From<Time> for StateVariable {
         StateVariable(value.as_f64())
     }
 }
+impl From<Currency> for StateVariable {
+    fn from(value: Currency) -> Self {
+        StateVariable(value.as_f64())
+    }
+}
 impl From<Energy> for StateVariable {
     fn from(value: Energy) -> Self {
         StateVariable(value.as_f64())
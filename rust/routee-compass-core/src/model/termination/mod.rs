@@ -1,5 +1,7 @@
+mod termination_behavior;
 mod termination_model;
 mod termination_model_error;
 
+pub use termination_behavior::TerminationBehavior;
 pub use termination_model::TerminationModel;
 pub use termination_model_error::TerminationModelError;
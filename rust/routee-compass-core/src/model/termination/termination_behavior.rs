@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// how a query responds when the termination model interrupts its search
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationBehavior {
+    /// the query fails with an explanation of the termination
+    #[default]
+    Error,
+    /// the query returns the partial search tree along with the route to the vertex
+    /// estimated to be closest to the destination, marked as terminated. only single
+    /// a*-based searches (a*, dijkstra and anytime a*) produce partial results, so apps
+    /// configured with other algorithms fail to build. waypoint, stop order, charging stop
+    /// and snapping candidate queries fail with an error that partial results are not
+    /// supported.
+    PartialResult,
+}
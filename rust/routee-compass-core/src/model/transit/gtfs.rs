@@ -0,0 +1,71 @@
+use super::TransitError;
+use serde::{Deserialize, Serialize};
+
+/// a row of the GTFS `stops.txt` file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GtfsStop {
+    pub stop_id: String,
+    pub stop_lat: f32,
+    pub stop_lon: f32,
+}
+
+/// a row of the GTFS `trips.txt` file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GtfsTrip {
+    pub route_id: String,
+    pub service_id: String,
+    pub trip_id: String,
+}
+
+/// a row of the GTFS `stop_times.txt` file. times may be empty for stops that are
+/// not timepoints, in which case the stop is skipped.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GtfsStopTime {
+    pub trip_id: String,
+    #[serde(default)]
+    pub arrival_time: Option<String>,
+    #[serde(default)]
+    pub departure_time: Option<String>,
+    pub stop_id: String,
+    pub stop_sequence: u32,
+}
+
+/// a row of the GTFS `routes.txt` file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GtfsRoute {
+    pub route_id: String,
+    #[serde(default)]
+    pub route_short_name: Option<String>,
+}
+
+/// parses a GTFS time of `HH:MM:SS` into seconds since midnight of the service day.
+/// hours may exceed 23 for trips that run past midnight.
+pub fn parse_gtfs_time(value: &str) -> Result<f64, TransitError> {
+    let parts = value
+        .trim()
+        .split(':')
+        .map(|part| part.parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TransitError::BuildError(format!("invalid GTFS time {}: {}", value, e)))?;
+    match parts.as_slice() {
+        [h, m, s] if *m < 60 && *s < 60 => Ok((h * 3600 + m * 60 + s) as f64),
+        _ => Err(TransitError::BuildError(format!(
+            "invalid GTFS time {}, expected HH:MM:SS",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gtfs_time() {
+        assert_eq!(parse_gtfs_time("08:15:30").unwrap(), 29_730.0);
+        assert_eq!(parse_gtfs_time(" 7:00:00").unwrap(), 25_200.0);
+        assert_eq!(parse_gtfs_time("25:10:00").unwrap(), 90_600.0);
+        assert!(parse_gtfs_time("08:75:00").is_err());
+        assert!(parse_gtfs_time("8am").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+mod gtfs;
+mod timetable;
+mod transit_access_model;
+mod transit_access_service;
+mod transit_error;
+mod transit_layer;
+mod transit_layer_config;
+mod transit_traversal_model;
+mod transit_traversal_service;
+
+pub use gtfs::{parse_gtfs_time, GtfsRoute, GtfsStop, GtfsStopTime, GtfsTrip};
+pub use timetable::Timetable;
+pub use transit_access_model::TransitAccessModel;
+pub use transit_access_service::TransitAccessService;
+pub use transit_error::TransitError;
+pub use transit_layer::{TransitEdge, TransitLayer};
+pub use transit_layer_config::TransitLayerConfig;
+pub use transit_traversal_model::TransitTraversalModel;
+pub use transit_traversal_service::TransitTraversalService;
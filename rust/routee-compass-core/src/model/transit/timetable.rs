@@ -0,0 +1,115 @@
+const SECONDS_PER_DAY: f64 = 86_400.0;
+/// reaching a stop this close after a departure still makes it, which absorbs
+/// rounding when times pass through state variables of other time units
+const DEPARTURE_TOLERANCE_SECONDS: f64 = 1e-3;
+
+/// the scheduled trips between two consecutive stops of a transit route, repeated
+/// every day. times are seconds since midnight of the service day, and may exceed
+/// one day for trips that run past midnight.
+#[derive(Clone, Debug)]
+pub struct Timetable {
+    departures: Vec<f64>,
+    /// the earliest arrival of any trip departing at or after each departure, which
+    /// keeps the timetable first-in-first-out even when trips overtake each other
+    arrivals: Vec<f64>,
+    /// the number of the trip making each of the earliest arrivals
+    trips: Vec<u64>,
+}
+
+impl Timetable {
+    /// builds a timetable from the (departure, arrival, trip number) of each trip
+    pub fn new(mut trips: Vec<(f64, f64, u64)>) -> Timetable {
+        trips.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let departures = trips.iter().map(|(d, _, _)| *d).collect::<Vec<_>>();
+        let mut arrivals = trips.iter().map(|(_, a, _)| *a).collect::<Vec<_>>();
+        let mut trip_numbers = trips.iter().map(|(_, _, t)| *t).collect::<Vec<_>>();
+        for i in (0..arrivals.len().saturating_sub(1)).rev() {
+            if arrivals[i + 1] < arrivals[i] {
+                arrivals[i] = arrivals[i + 1];
+                trip_numbers[i] = trip_numbers[i + 1];
+            }
+        }
+        Timetable {
+            departures,
+            arrivals,
+            trips: trip_numbers,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.departures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.departures.is_empty()
+    }
+
+    /// finds the next departure when reaching the stop at some time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - seconds since midnight of some day when reaching the stop
+    ///
+    /// # Returns
+    ///
+    /// the next departure time and the earliest arrival at the next stop, in the
+    /// same time base as `time`, or None if the timetable is empty
+    pub fn next(&self, time: f64) -> Option<(f64, f64)> {
+        self.next_trip(time)
+            .map(|(departure, arrival, _)| (departure, arrival))
+    }
+
+    /// finds the next departure when reaching the stop at some time, along with the
+    /// number of the trip making the earliest arrival. see [`Timetable::next`].
+    pub fn next_trip(&self, time: f64) -> Option<(f64, f64, u64)> {
+        let day = (time / SECONDS_PER_DAY).floor();
+        let time_of_day = time - day * SECONDS_PER_DAY;
+        // trips of yesterday's service that run past midnight, then today, then tomorrow
+        [-1.0, 0.0, 1.0]
+            .iter()
+            .filter_map(|offset| {
+                let t = time_of_day - offset * SECONDS_PER_DAY;
+                let index = self
+                    .departures
+                    .partition_point(|d| *d < t - DEPARTURE_TOLERANCE_SECONDS);
+                let base = (day + offset) * SECONDS_PER_DAY;
+                self.departures
+                    .get(index)
+                    .map(|d| (base + d, base + self.arrivals[index], self.trips[index]))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_departure() {
+        // 08:00 -> 08:10, 08:30 -> 08:35, and a late trip 24:30 -> 24:40
+        let timetable = Timetable::new(vec![
+            (30_600.0, 30_900.0, 2),
+            (28_800.0, 29_400.0, 1),
+            (88_200.0, 88_800.0, 3),
+        ]);
+        assert_eq!(timetable.next(28_000.0), Some((28_800.0, 29_400.0)));
+        assert_eq!(timetable.next(29_000.0), Some((30_600.0, 30_900.0)));
+        // after the last trip of the day, the late trip leaves at 00:30
+        assert_eq!(timetable.next(86_000.0), Some((88_200.0, 88_800.0)));
+        // at 00:10 on tuesday, the late trip of monday's service is next
+        let tuesday = SECONDS_PER_DAY + 600.0;
+        assert_eq!(
+            timetable.next(tuesday),
+            Some((SECONDS_PER_DAY + 1_800.0, SECONDS_PER_DAY + 2_400.0))
+        );
+    }
+
+    #[test]
+    fn test_fifo_when_trips_overtake() {
+        // an express leaving later arrives before the local
+        let timetable = Timetable::new(vec![(0.0, 1_000.0, 1), (100.0, 500.0, 2)]);
+        assert_eq!(timetable.next(0.0), Some((0.0, 500.0)));
+        assert_eq!(timetable.next_trip(0.0), Some((0.0, 500.0, 2)));
+    }
+}
@@ -0,0 +1,99 @@
+use super::{TransitEdge, TransitLayer};
+use crate::model::access::{AccessModel, AccessModelError};
+use crate::model::network::{Edge, Vertex};
+use crate::model::state::{CustomFeatureFormat, StateFeature, StateModel, StateVariable};
+use crate::model::unit::{AsF64, Time, TimeUnit};
+use std::sync::Arc;
+
+/// boards transit vehicles. when the next edge is a ride that does not continue the
+/// trip of the vehicle the previous ride edge was on, the boarding penalty and the wait
+/// for the next departure are added to the trip time, the number of boardings is
+/// incremented and the boarded trip is recorded.
+pub struct TransitAccessModel {
+    pub layer: Arc<TransitLayer>,
+    /// seconds since midnight monday at the start of the trip
+    pub departure_seconds: f64,
+    /// time needed to board a vehicle, which is also the minimum transfer time
+    pub boarding_penalty_seconds: f64,
+}
+
+impl TransitAccessModel {
+    const TIME: &'static str = "time";
+    const BOARDINGS: &'static str = "transit_boardings";
+    const TRIP: &'static str = "transit_trip";
+}
+
+impl AccessModel for TransitAccessModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        vec![
+            (
+                String::from(Self::BOARDINGS),
+                StateFeature::Custom {
+                    r#type: String::from("boardings"),
+                    unit: String::from("count"),
+                    format: CustomFeatureFormat::UnsignedInteger { initial: 0 },
+                },
+            ),
+            (
+                String::from(Self::TRIP),
+                StateFeature::Custom {
+                    r#type: String::from("trip"),
+                    unit: String::from("trip number"),
+                    format: CustomFeatureFormat::UnsignedInteger { initial: 0 },
+                },
+            ),
+        ]
+    }
+
+    fn access_edge(
+        &self,
+        traversal: (&Vertex, &Edge, &Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), AccessModelError> {
+        let (_, prev_edge, _, next_edge, _) = traversal;
+        let (route_id, timetable) = match self.layer.get(&next_edge.edge_id) {
+            Some(TransitEdge::Ride {
+                route_id,
+                timetable,
+                ..
+            }) => (route_id, timetable),
+            _ => return Ok(()),
+        };
+        let elapsed = state_model
+            .get_time(state, &Self::TIME.into(), &TimeUnit::Seconds)?
+            .as_f64();
+        let clock = self.departure_seconds + elapsed;
+
+        // staying on board if the next departure continues the trip of the vehicle
+        if let Some(TransitEdge::Ride { .. }) = self.layer.get(&prev_edge.edge_id) {
+            let on_board = state_model.get_custom_u64(state, &Self::TRIP.into())?;
+            if timetable
+                .next_trip(clock)
+                .is_some_and(|(_, _, trip)| trip == on_board)
+            {
+                return Ok(());
+            }
+        }
+
+        let (departure, _, trip) = timetable
+            .next_trip(clock + self.boarding_penalty_seconds)
+            .ok_or_else(|| AccessModelError::RuntimeError {
+                name: String::from("transit"),
+                error: format!(
+                    "transit route {} has no departures on edge {}",
+                    route_id, next_edge.edge_id
+                ),
+            })?;
+        state_model.add_time(
+            state,
+            &Self::TIME.into(),
+            &Time::new(departure - clock),
+            &TimeUnit::Seconds,
+        )?;
+        let boardings = state_model.get_custom_u64(state, &Self::BOARDINGS.into())?;
+        state_model.set_custom_u64(state, &Self::BOARDINGS.into(), &(boardings + 1))?;
+        state_model.set_custom_u64(state, &Self::TRIP.into(), &trip)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use super::{TransitAccessModel, TransitLayer};
+use crate::model::access::{AccessModel, AccessModelError, AccessModelService};
+use crate::model::traversal::default::departure_seconds;
+use std::sync::Arc;
+
+pub struct TransitAccessService {
+    pub layer: Arc<TransitLayer>,
+    pub boarding_penalty_seconds: f64,
+}
+
+impl AccessModelService for TransitAccessService {
+    /// builds a boarding model for the required `departure_time` of the query
+    fn build(&self, query: &serde_json::Value) -> Result<Arc<dyn AccessModel>, AccessModelError> {
+        let departure_time = query
+            .get("departure_time")
+            .ok_or_else(|| {
+                AccessModelError::BuildError(String::from(
+                    "transit access requires a 'departure_time' on the query",
+                ))
+            })?
+            .as_str()
+            .ok_or_else(|| {
+                AccessModelError::BuildError(String::from(
+                    "expected 'departure_time' value to be a string",
+                ))
+            })?;
+        let departure = departure_seconds(departure_time)
+            .map_err(|e| AccessModelError::BuildError(e.to_string()))?;
+        Ok(Arc::new(TransitAccessModel {
+            layer: self.layer.clone(),
+            departure_seconds: departure,
+            boarding_penalty_seconds: self.boarding_penalty_seconds,
+        }))
+    }
+}
@@ -0,0 +1,14 @@
+use crate::model::network::NetworkError;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TransitError {
+    #[error("failed to build transit layer: {0}")]
+    BuildError(String),
+    #[error("failure reading GTFS file {0}: {1}")]
+    GtfsReadError(String, String),
+    #[error("failure extending graph with transit layer: {source}")]
+    NetworkError {
+        #[from]
+        source: NetworkError,
+    },
+}
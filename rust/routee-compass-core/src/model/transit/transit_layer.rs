@@ -0,0 +1,389 @@
+use super::gtfs::{parse_gtfs_time, GtfsRoute, GtfsStop, GtfsStopTime, GtfsTrip};
+use super::{Timetable, TransitError, TransitLayerConfig};
+use crate::model::map::MapVertexRTreeObject;
+use crate::model::network::{Edge, EdgeId, Graph, Vertex};
+use crate::model::unit::AsF64;
+use crate::util::fs::read_utils;
+use crate::util::geo::haversine::haversine_distance_meters;
+use geo::{coord, Point};
+use kdam::Bar;
+use rstar::RTree;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// source stop index, destination stop index and route id of a ride edge
+type RideKey<'a> = (usize, usize, &'a String);
+
+/// the transit role of an edge added by the transit layer
+#[derive(Clone, Debug)]
+pub enum TransitEdge {
+    /// a walking link between a stop and its nearest road vertex
+    Connector,
+    /// a ride between two consecutive stops of a route
+    Ride {
+        route_id: String,
+        /// the route short name, or the route id when no name is known
+        label: String,
+        timetable: Timetable,
+    },
+}
+
+/// a public transit network read from a GTFS feed. stops become vertices appended
+/// after the road vertices of the graph, and edges are appended after the road edges:
+/// connectors in both directions between each stop and its nearest road vertex, and
+/// one ride edge for each pair of consecutive stops served by a route.
+pub struct TransitLayer {
+    /// number of road edges in the graph, which is the id of the first transit edge
+    pub road_edge_count: usize,
+    pub stop_ids: Vec<String>,
+    pub vertices: Vec<Vertex>,
+    pub edges: Vec<Edge>,
+    /// for each edge in `edges`, its transit role
+    pub transit_edges: Vec<TransitEdge>,
+}
+
+impl TransitLayer {
+    /// reads the GTFS files of the configuration and builds the layer on top of a road graph
+    pub fn new(config: &TransitLayerConfig, graph: &Graph) -> Result<TransitLayer, TransitError> {
+        let stops: Box<[GtfsStop]> = read_gtfs(&config.stops_input_file, "transit stops")?;
+        let trips: Box<[GtfsTrip]> = read_gtfs(&config.trips_input_file, "transit trips")?;
+        let stop_times: Box<[GtfsStopTime]> =
+            read_gtfs(&config.stop_times_input_file, "transit stop times")?;
+        let routes: Box<[GtfsRoute]> = match &config.routes_input_file {
+            Some(file) => read_gtfs(file, "transit routes")?,
+            None => Box::new([]),
+        };
+        TransitLayer::from_gtfs(
+            &stops,
+            &trips,
+            &stop_times,
+            &routes,
+            config.service_ids.as_ref(),
+            config.max_connector_distance_meters,
+            graph,
+        )
+    }
+
+    /// builds the layer from GTFS rows
+    ///
+    /// # Arguments
+    ///
+    /// * `stops`       - GTFS stops
+    /// * `trips`       - GTFS trips
+    /// * `stop_times`  - GTFS stop times
+    /// * `routes`      - GTFS routes, used for labels, which may be empty
+    /// * `service_ids` - if provided, only trips of these services are used
+    /// * `max_connector_distance_meters` - stops further than this from the road network are not connected
+    /// * `graph`       - the road graph the layer attaches to
+    pub fn from_gtfs(
+        stops: &[GtfsStop],
+        trips: &[GtfsTrip],
+        stop_times: &[GtfsStopTime],
+        routes: &[GtfsRoute],
+        service_ids: Option<&Vec<String>>,
+        max_connector_distance_meters: f64,
+        graph: &Graph,
+    ) -> Result<TransitLayer, TransitError> {
+        let road_edge_count = graph.n_edges();
+        let road_vertex_count = graph.n_vertices();
+        let mut layer = TransitLayer {
+            road_edge_count,
+            stop_ids: vec![],
+            vertices: vec![],
+            edges: vec![],
+            transit_edges: vec![],
+        };
+
+        // stop vertices, connected to the nearest road vertex
+        let rtree = RTree::bulk_load(
+            graph
+                .vertices
+                .iter()
+                .map(MapVertexRTreeObject::new)
+                .collect(),
+        );
+        let mut stop_vertices: HashMap<&String, usize> = HashMap::new();
+        for (index, stop) in stops.iter().enumerate() {
+            let vertex = Vertex::new(road_vertex_count + index, stop.stop_lon, stop.stop_lat);
+            if stop_vertices.insert(&stop.stop_id, index).is_some() {
+                return Err(TransitError::BuildError(format!(
+                    "stop_id {} is listed more than once",
+                    stop.stop_id
+                )));
+            }
+            let point = Point(coord! {x: vertex.x(), y: vertex.y()});
+            if let Some(nearest) = rtree.nearest_neighbor(&point) {
+                let road_vertex = graph.get_vertex(&nearest.vertex_id)?;
+                let distance = distance_meters(&vertex, road_vertex)?;
+                if distance <= max_connector_distance_meters {
+                    let road_vertex_id = road_vertex.vertex_id.0;
+                    layer.push_edge(
+                        vertex.vertex_id.0,
+                        road_vertex_id,
+                        distance,
+                        TransitEdge::Connector,
+                    );
+                    layer.push_edge(
+                        road_vertex_id,
+                        vertex.vertex_id.0,
+                        distance,
+                        TransitEdge::Connector,
+                    );
+                } else {
+                    log::warn!(
+                        "transit stop {} is {:.0} meters from the road network, beyond the connector limit",
+                        stop.stop_id,
+                        distance
+                    );
+                }
+            }
+            layer.stop_ids.push(stop.stop_id.clone());
+            layer.vertices.push(vertex);
+        }
+
+        // scheduled trips between consecutive stops of each route
+        let route_labels = routes
+            .iter()
+            .filter_map(|r| {
+                r.route_short_name
+                    .as_ref()
+                    .filter(|name| !name.is_empty())
+                    .map(|name| (&r.route_id, name))
+            })
+            .collect::<HashMap<_, _>>();
+        // trips are numbered from 1 in the order of the trips file, leaving 0 for no trip
+        let services = service_ids.map(|ids| ids.iter().collect::<HashSet<_>>());
+        let trip_routes = trips
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| services.as_ref().is_none_or(|s| s.contains(&t.service_id)))
+            .map(|(index, t)| (&t.trip_id, (&t.route_id, index as u64 + 1)))
+            .collect::<HashMap<_, _>>();
+        let mut trip_stop_times: HashMap<&String, Vec<&GtfsStopTime>> = HashMap::new();
+        for stop_time in stop_times.iter() {
+            if trip_routes.contains_key(&stop_time.trip_id) {
+                trip_stop_times
+                    .entry(&stop_time.trip_id)
+                    .or_default()
+                    .push(stop_time);
+            }
+        }
+        let mut rides: BTreeMap<RideKey, Vec<(f64, f64, u64)>> = BTreeMap::new();
+        for (trip_id, mut times) in trip_stop_times.into_iter() {
+            times.sort_by_key(|st| st.stop_sequence);
+            // stops without a scheduled time are not timepoints and are passed through
+            let timepoints = times
+                .into_iter()
+                .filter_map(|st| {
+                    let arrival = st.arrival_time.as_ref().or(st.departure_time.as_ref())?;
+                    let departure = st.departure_time.as_ref().or(st.arrival_time.as_ref())?;
+                    Some((st, arrival, departure))
+                })
+                .collect::<Vec<_>>();
+            for pair in timepoints.windows(2) {
+                let (src, _, departure) = pair[0];
+                let (dst, arrival, _) = pair[1];
+                let src_index = stop_index(&stop_vertices, &src.stop_id)?;
+                let dst_index = stop_index(&stop_vertices, &dst.stop_id)?;
+                let departure = parse_gtfs_time(departure)?;
+                let arrival = parse_gtfs_time(arrival)?;
+                if arrival < departure {
+                    return Err(TransitError::BuildError(format!(
+                        "trip {} arrives at stop {} before departing stop {}",
+                        trip_id, dst.stop_id, src.stop_id
+                    )));
+                }
+                let (route_id, trip_number) = trip_routes[trip_id];
+                rides
+                    .entry((src_index, dst_index, route_id))
+                    .or_default()
+                    .push((departure, arrival, trip_number));
+            }
+        }
+        for ((src_index, dst_index, route_id), trips) in rides.into_iter() {
+            let distance = distance_meters(&layer.vertices[src_index], &layer.vertices[dst_index])?;
+            let label = route_labels.get(route_id).cloned().unwrap_or(route_id);
+            let ride = TransitEdge::Ride {
+                route_id: route_id.clone(),
+                label: label.clone(),
+                timetable: Timetable::new(trips),
+            };
+            layer.push_edge(
+                road_vertex_count + src_index,
+                road_vertex_count + dst_index,
+                distance,
+                ride,
+            );
+        }
+
+        log::info!(
+            "transit layer has {} stops and {} edges",
+            layer.vertices.len(),
+            layer.edges.len()
+        );
+        Ok(layer)
+    }
+
+    /// appends the stop vertices and transit edges of this layer to the road graph
+    pub fn extend_graph(&self, graph: &mut Graph) -> Result<(), TransitError> {
+        graph.extend(self.vertices.clone(), self.edges.clone())?;
+        Ok(())
+    }
+
+    /// the transit role of an edge, or None for road edges
+    pub fn get(&self, edge_id: &EdgeId) -> Option<&TransitEdge> {
+        edge_id
+            .0
+            .checked_sub(self.road_edge_count)
+            .and_then(|index| self.transit_edges.get(index))
+    }
+
+    /// the mode used to travel an edge: `road`, `walk` for connectors, or the label of a transit route
+    pub fn mode(&self, edge_id: &EdgeId) -> &str {
+        match self.get(edge_id) {
+            None => "road",
+            Some(TransitEdge::Connector) => "walk",
+            Some(TransitEdge::Ride { label, .. }) => label,
+        }
+    }
+
+    fn push_edge(&mut self, src: usize, dst: usize, distance: f64, transit_edge: TransitEdge) {
+        let edge_id = self.road_edge_count + self.edges.len();
+        self.edges.push(Edge::new(edge_id, src, dst, distance));
+        self.transit_edges.push(transit_edge);
+    }
+}
+
+fn read_gtfs<T: DeserializeOwned>(file: &str, desc: &str) -> Result<Box<[T]>, TransitError> {
+    read_utils::from_csv(&file, true, Some(Bar::builder().desc(desc)), None)
+        .map_err(|e| TransitError::GtfsReadError(file.to_string(), e.to_string()))
+}
+
+fn stop_index(
+    stop_vertices: &HashMap<&String, usize>,
+    stop_id: &String,
+) -> Result<usize, TransitError> {
+    stop_vertices.get(stop_id).cloned().ok_or_else(|| {
+        TransitError::BuildError(format!("stop time references unknown stop_id {}", stop_id))
+    })
+}
+
+fn distance_meters(src: &Vertex, dst: &Vertex) -> Result<f64, TransitError> {
+    let distance = haversine_distance_meters(src.x(), src.y(), dst.x(), dst.y())
+        .map_err(TransitError::BuildError)?;
+    Ok(distance.as_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::network::VertexId;
+
+    /// two road vertices about 1.1km apart, built by extending an empty graph
+    fn mock_graph() -> Graph {
+        let mut graph = Graph {
+            adj: Box::new([]),
+            rev: Box::new([]),
+            edges: Box::new([]),
+            vertices: Box::new([]),
+        };
+        graph
+            .extend(
+                vec![Vertex::new(0, -105.0, 40.0), Vertex::new(1, -105.0, 40.01)],
+                vec![Edge::new(0, 0, 1, 1112.0), Edge::new(1, 1, 0, 1112.0)],
+            )
+            .unwrap();
+        graph
+    }
+
+    /// a stop beside each road vertex, and a third stop far from the road network
+    fn mock_layer() -> TransitLayer {
+        let graph = mock_graph();
+        let stop = |id: &str, lat: f32| GtfsStop {
+            stop_id: String::from(id),
+            stop_lat: lat,
+            stop_lon: -105.0001,
+        };
+        let stops = vec![stop("a", 40.0), stop("b", 40.01), stop("c", 41.0)];
+        let trip = |id: &str, service_id: &str| GtfsTrip {
+            route_id: String::from("r1"),
+            service_id: String::from(service_id),
+            trip_id: String::from(id),
+        };
+        let trips = vec![
+            trip("t1", "weekday"),
+            trip("t2", "weekday"),
+            trip("t3", "weekend"),
+        ];
+        let stop_time = |trip_id: &str, stop_id: &str, seq: u32, time: &str| GtfsStopTime {
+            trip_id: String::from(trip_id),
+            arrival_time: Some(String::from(time)),
+            departure_time: Some(String::from(time)),
+            stop_id: String::from(stop_id),
+            stop_sequence: seq,
+        };
+        let stop_times = vec![
+            stop_time("t1", "b", 2, "08:05:00"),
+            stop_time("t1", "a", 1, "08:00:00"),
+            stop_time("t2", "a", 1, "08:30:00"),
+            stop_time("t2", "b", 2, "08:35:00"),
+            stop_time("t3", "a", 1, "09:00:00"),
+            stop_time("t3", "b", 2, "09:05:00"),
+        ];
+        let routes = vec![GtfsRoute {
+            route_id: String::from("r1"),
+            route_short_name: Some(String::from("Line 1")),
+        }];
+        let service_ids = vec![String::from("weekday")];
+        TransitLayer::from_gtfs(
+            &stops,
+            &trips,
+            &stop_times,
+            &routes,
+            Some(&service_ids),
+            500.0,
+            &graph,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_layer_from_gtfs() {
+        let layer = mock_layer();
+        // stops a and b have connectors both ways, stop c is too far from the road
+        assert_eq!(layer.vertices.len(), 3);
+        assert_eq!(layer.vertices[0].vertex_id.0, 2);
+        assert_eq!(layer.edges.len(), 5);
+        assert_eq!(layer.edges[0].edge_id.0, 2);
+        assert_eq!(layer.mode(&EdgeId(0)), "road");
+        assert_eq!(layer.mode(&EdgeId(2)), "walk");
+        assert_eq!(layer.mode(&EdgeId(6)), "Line 1");
+
+        // one ride edge from a to b with the two weekday trips
+        let ride = &layer.edges[4];
+        assert_eq!((ride.src_vertex_id.0, ride.dst_vertex_id.0), (2, 3));
+        match layer.get(&ride.edge_id) {
+            Some(TransitEdge::Ride { timetable, .. }) => {
+                assert_eq!(timetable.len(), 2);
+                assert_eq!(timetable.next_trip(28_900.0), Some((30_600.0, 30_900.0, 2)));
+            }
+            other => panic!("expected a ride edge, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extend_graph() {
+        let layer = mock_layer();
+        let mut graph = mock_graph();
+        layer.extend_graph(&mut graph).unwrap();
+        assert_eq!(graph.n_vertices(), 5);
+        assert_eq!(graph.n_edges(), 7);
+        // the road vertex of stop a links to the stop, which links to the ride
+        assert_eq!(graph.out_edges(&VertexId(0)).len(), 2);
+        assert_eq!(graph.out_edges(&VertexId(2)).len(), 2);
+        // ids that do not continue the graph are rejected
+        assert!(graph
+            .extend(vec![Vertex::new(9, 0.0, 0.0)], vec![])
+            .is_err());
+    }
+}
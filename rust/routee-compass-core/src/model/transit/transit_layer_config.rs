@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// configuration of the transit layer, read from the `[graph.transit]` section.
+/// each file is a table of a local GTFS feed.
+///
+/// ```toml
+/// [graph.transit]
+/// stops_input_file = "gtfs/stops.txt"
+/// trips_input_file = "gtfs/trips.txt"
+/// stop_times_input_file = "gtfs/stop_times.txt"
+/// routes_input_file = "gtfs/routes.txt"
+/// service_ids = ["weekday"]
+/// max_connector_distance_meters = 400.0
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TransitLayerConfig {
+    pub stops_input_file: String,
+    pub trips_input_file: String,
+    pub stop_times_input_file: String,
+    /// when provided, rides are labeled by the route short name instead of the route id
+    #[serde(default)]
+    pub routes_input_file: Option<String>,
+    /// when provided, only trips of these services are loaded
+    #[serde(default)]
+    pub service_ids: Option<Vec<String>>,
+    /// stops further than this from the nearest road vertex are not connected to the road network
+    #[serde(default = "TransitLayerConfig::default_max_connector_distance_meters")]
+    pub max_connector_distance_meters: f64,
+}
+
+impl TransitLayerConfig {
+    fn default_max_connector_distance_meters() -> f64 {
+        500.0
+    }
+}
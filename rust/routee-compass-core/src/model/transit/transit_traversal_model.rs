@@ -0,0 +1,263 @@
+use super::{TransitEdge, TransitLayer};
+use crate::model::network::{Edge, Vertex};
+use crate::model::state::{StateFeature, StateModel, StateVariable};
+use crate::model::traversal::{TraversalModel, TraversalModelError};
+use crate::model::unit::{AsF64, Distance, DistanceUnit, Time, TimeUnit};
+use std::sync::Arc;
+
+/// traverses a graph extended by a transit layer. road edges are delegated to the
+/// road model, connectors are walked at a fixed speed, and rides follow the timetable
+/// of the route, adding the wait for the next departure and the time in the vehicle.
+pub struct TransitTraversalModel {
+    pub road_model: Arc<dyn TraversalModel>,
+    pub layer: Arc<TransitLayer>,
+    /// seconds since midnight monday at the start of the trip
+    pub departure_seconds: f64,
+    pub connector_speed_kph: f64,
+}
+
+impl TransitTraversalModel {
+    const TIME: &'static str = "time";
+    const DISTANCE: &'static str = "distance";
+
+    fn add_travel(
+        &self,
+        distance_meters: f64,
+        seconds: f64,
+        state: &mut [StateVariable],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        state_model.add_time(
+            state,
+            &Self::TIME.into(),
+            &Time::new(seconds),
+            &TimeUnit::Seconds,
+        )?;
+        if state_model.contains_key(&Self::DISTANCE.into()) {
+            state_model.add_distance(
+                state,
+                &Self::DISTANCE.into(),
+                &Distance::new(distance_meters),
+                &DistanceUnit::Meters,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TraversalModel for TransitTraversalModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        self.road_model.state_features()
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        match self.layer.get(&edge.edge_id) {
+            None => self
+                .road_model
+                .traverse_edge(trajectory, state, state_model),
+            Some(TransitEdge::Connector) => {
+                let meters = edge.distance.as_f64();
+                let seconds = meters / (self.connector_speed_kph / 3.6);
+                self.add_travel(meters, seconds, state, state_model)
+            }
+            Some(TransitEdge::Ride {
+                label, timetable, ..
+            }) => {
+                let elapsed =
+                    state_model.get_time(state, &Self::TIME.into(), &TimeUnit::Seconds)?;
+                let clock = self.departure_seconds + elapsed.as_f64();
+                let (_, arrival) = timetable.next(clock).ok_or_else(|| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "transit route {} has no departures on edge {}",
+                        label, edge.edge_id
+                    ))
+                })?;
+                self.add_travel(edge.distance.as_f64(), arrival - clock, state, state_model)
+            }
+        }
+    }
+
+    /// transit may be faster than the road model, so the road model estimate could
+    /// overestimate. no estimate is made, which keeps the search admissible.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::access::AccessModel;
+    use crate::model::network::{EdgeId, VertexId};
+    use crate::model::transit::{Timetable, TransitAccessModel};
+
+    /// tracks time and distance but never moves, standing in for a road model
+    struct MockRoadModel;
+
+    impl TraversalModel for MockRoadModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![
+                (
+                    String::from("time"),
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Minutes,
+                        initial: Time::ZERO,
+                    },
+                ),
+                (
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Kilometers,
+                        initial: Distance::ZERO,
+                    },
+                ),
+            ]
+        }
+
+        fn traverse_edge(
+            &self,
+            _trajectory: (&Vertex, &Edge, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    /// one road edge, then a 250m connector to stop 1 and a ride from stop 1 to stop 2
+    /// on trips 1 and 2 leaving at 08:00 and 08:30, five minutes each. trip 1 continues
+    /// to stop 3, and trip 2 runs a branch to stop 4.
+    fn mock_layer() -> TransitLayer {
+        let ride = |trips: Vec<(f64, f64, u64)>| TransitEdge::Ride {
+            route_id: String::from("r1"),
+            label: String::from("Line 1"),
+            timetable: Timetable::new(trips),
+        };
+        TransitLayer {
+            road_edge_count: 1,
+            stop_ids: [1, 2, 3, 4].iter().map(|id| id.to_string()).collect(),
+            vertices: vec![
+                Vertex::new(2, -105.0, 40.0),
+                Vertex::new(3, -105.0, 40.01),
+                Vertex::new(4, -105.0, 40.02),
+                Vertex::new(5, -105.01, 40.01),
+            ],
+            edges: vec![
+                Edge::new(1, 1, 2, 250.0),
+                Edge::new(2, 2, 3, 1112.0),
+                Edge::new(3, 3, 4, 1112.0),
+                Edge::new(4, 3, 5, 850.0),
+            ],
+            transit_edges: vec![
+                TransitEdge::Connector,
+                ride(vec![(28_800.0, 29_100.0, 1), (30_600.0, 30_900.0, 2)]),
+                ride(vec![(29_100.0, 29_400.0, 1)]),
+                ride(vec![(30_900.0, 31_200.0, 2)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_walk_board_and_ride() {
+        let layer = Arc::new(mock_layer());
+        let departure_seconds = 7.0 * 3600.0 + 55.0 * 60.0;
+        let traversal_model = TransitTraversalModel {
+            road_model: Arc::new(MockRoadModel),
+            layer: layer.clone(),
+            departure_seconds,
+            connector_speed_kph: 5.0,
+        };
+        let access_model = TransitAccessModel {
+            layer: layer.clone(),
+            departure_seconds,
+            boarding_penalty_seconds: 60.0,
+        };
+        let mut features = traversal_model.state_features();
+        features.extend(access_model.state_features());
+        let state_model = StateModel::empty().extend(features).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = Vertex::new(0, -105.0, 40.0);
+        let road = Edge::new(0, 0, 1, 100.0);
+        let (connector, ride) = (&layer.edges[0], &layer.edges[1]);
+        let minutes = |state: &[StateVariable]| {
+            state_model
+                .get_time(state, &"time".into(), &TimeUnit::Seconds)
+                .unwrap()
+                .as_f64()
+                / 60.0
+        };
+
+        // walking 250m at 5kph takes three minutes, reaching the stop at 07:58
+        traversal_model
+            .traverse_edge((&v, connector, &v), &mut state, &state_model)
+            .unwrap();
+        assert!((minutes(&state) - 3.0).abs() < 1e-6);
+
+        // boarding takes a minute, then waits for the 08:00 departure
+        access_model
+            .access_edge((&v, &road, &v, ride, &v), &mut state, &state_model)
+            .unwrap();
+        assert!((minutes(&state) - 5.0).abs() < 1e-6);
+        traversal_model
+            .traverse_edge((&v, ride, &v), &mut state, &state_model)
+            .unwrap();
+        assert!((minutes(&state) - 10.0).abs() < 1e-6);
+        let distance = state_model
+            .get_distance(&state, &"distance".into(), &DistanceUnit::Meters)
+            .unwrap();
+        assert!((distance.as_f64() - 1362.0).abs() < 1e-6);
+
+        // staying on board trip 1 to stop 3 is not another boarding
+        let boardings = |state: &[StateVariable]| {
+            state_model
+                .get_custom_u64(state, &"transit_boardings".into())
+                .unwrap()
+        };
+        let (continuation, branch) = (&layer.edges[2], &layer.edges[3]);
+        let mut continued = state.clone();
+        access_model
+            .access_edge(
+                (&v, ride, &v, continuation, &v),
+                &mut continued,
+                &state_model,
+            )
+            .unwrap();
+        assert_eq!(boardings(&continued), 1);
+        assert!((minutes(&continued) - 10.0).abs() < 1e-6);
+
+        // the branch to stop 4 is only served by trip 2, so the same route is boarded
+        // again after waiting for its 08:35 departure
+        access_model
+            .access_edge((&v, ride, &v, branch, &v), &mut state, &state_model)
+            .unwrap();
+        assert_eq!(boardings(&state), 2);
+        assert!((minutes(&state) - 40.0).abs() < 1e-6);
+        let trip = state_model
+            .get_custom_u64(&state, &"transit_trip".into())
+            .unwrap();
+        assert_eq!(trip, 2);
+        assert_eq!(layer.mode(&EdgeId(0)), "road");
+        assert_eq!(layer.mode(&ride.edge_id), "Line 1");
+        assert_eq!(ride.src_vertex_id, VertexId(2));
+    }
+}
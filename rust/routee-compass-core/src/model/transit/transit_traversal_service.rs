@@ -0,0 +1,54 @@
+use super::{TransitLayer, TransitTraversalModel};
+use crate::model::traversal::default::departure_seconds;
+use crate::model::traversal::{TraversalModel, TraversalModelError, TraversalModelService};
+use std::sync::Arc;
+
+/// builds transit models that wrap the road model of each query
+pub struct TransitTraversalService {
+    pub road_model_service: Arc<dyn TraversalModelService>,
+    pub layer: Arc<TransitLayer>,
+    pub connector_speed_kph: f64,
+}
+
+impl TraversalModelService for TransitTraversalService {
+    /// builds the road model of the query. the `departure_time` of the query is required
+    /// to follow the timetables, and the road model must track `time`.
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let road_model = self.road_model_service.build(parameters)?;
+        if !road_model
+            .state_features()
+            .iter()
+            .any(|(name, _)| name == "time")
+        {
+            return Err(TraversalModelError::BuildError(String::from(
+                "transit traversal requires a road model with a 'time' state feature",
+            )));
+        }
+        let departure_time = parameters
+            .get("departure_time")
+            .ok_or_else(|| {
+                TraversalModelError::BuildError(String::from(
+                    "transit traversal requires a 'departure_time' on the query",
+                ))
+            })?
+            .as_str()
+            .ok_or_else(|| {
+                TraversalModelError::BuildError(String::from(
+                    "expected 'departure_time' value to be a string",
+                ))
+            })?;
+        Ok(Arc::new(TransitTraversalModel {
+            road_model,
+            layer: self.layer.clone(),
+            departure_seconds: departure_seconds(departure_time)?,
+            connector_speed_kph: self.connector_speed_kph,
+        }))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        self.road_model_service.state_feature_names()
+    }
+}
@@ -0,0 +1,182 @@
+use crate::model::{
+    network::{Edge, Vertex},
+    state::{StateFeature, StateModel, StateVariable},
+    traversal::{TraversalModel, TraversalModelError, TraversalModelService},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct CombinedTraversalModelService {
+    pub services: Vec<Arc<dyn TraversalModelService>>,
+}
+
+impl CombinedTraversalModelService {
+    /// combines the services, failing if more than one service lists the same state
+    /// feature, which would cause both of their models to update it.
+    pub fn new(
+        services: Vec<Arc<dyn TraversalModelService>>,
+    ) -> Result<CombinedTraversalModelService, TraversalModelError> {
+        let mut owners: HashMap<String, usize> = HashMap::new();
+        for (index, service) in services.iter().enumerate() {
+            for name in service.state_feature_names() {
+                if let Some(owner) = owners.insert(name.clone(), index) {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "combined traversal models {} and {} both provide state feature '{}'",
+                        owner, index, name
+                    )));
+                }
+            }
+        }
+        Ok(CombinedTraversalModelService { services })
+    }
+}
+
+/// a stack of traversal models that share the search state. each edge traversal and
+/// estimate calls every model in order, so a later model may read the features updated
+/// by an earlier model, such as an energy model reading the time of a speed model.
+pub struct CombinedTraversalModel {
+    pub models: Vec<Arc<dyn TraversalModel>>,
+}
+
+impl TraversalModelService for CombinedTraversalModelService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let models = self
+            .services
+            .iter()
+            .map(|m| m.build(query))
+            .collect::<Result<_, _>>()?;
+        Ok(Arc::new(CombinedTraversalModel { models }))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        self.services
+            .iter()
+            .flat_map(|s| s.state_feature_names())
+            .collect()
+    }
+}
+
+impl TraversalModel for CombinedTraversalModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        self.models
+            .iter()
+            .flat_map(|m| m.state_features())
+            .collect()
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        for model in self.models.iter() {
+            model.traverse_edge(trajectory, state, state_model)?;
+        }
+        Ok(())
+    }
+
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        for model in self.models.iter() {
+            model.estimate_traversal(od, state, state_model)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::network::{EdgeId, VertexId};
+    use crate::model::traversal::default::{DistanceTraversalModel, DistanceTraversalService};
+    use crate::model::unit::{Distance, DistanceUnit, Time, TimeUnit};
+    use crate::util::geo::coord::InternalCoord;
+    use geo::coord;
+
+    /// adds a fixed delay to every edge
+    struct DelayModel;
+
+    impl TraversalModel for DelayModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![(
+                String::from("delay"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Seconds,
+                    initial: Time::ZERO,
+                },
+            )]
+        }
+
+        fn traverse_edge(
+            &self,
+            _trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVariable>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            state_model.add_time(state, &"delay".into(), &Time::new(5.0), &TimeUnit::Seconds)?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_traverse_all_models() {
+        let model = CombinedTraversalModel {
+            models: vec![
+                Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+                Arc::new(DelayModel),
+            ],
+        };
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(0),
+            distance: Distance::new(100.0),
+        };
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+        let distance = state_model
+            .get_distance(&state, &"distance".into(), &DistanceUnit::Meters)
+            .unwrap();
+        let delay = state_model
+            .get_time(&state, &"delay".into(), &TimeUnit::Seconds)
+            .unwrap();
+        assert_eq!(distance, Distance::new(100.0));
+        assert_eq!(delay, Time::new(5.0));
+    }
+
+    #[test]
+    fn test_conflicting_features() {
+        let distance = || -> Arc<dyn TraversalModelService> {
+            Arc::new(DistanceTraversalService {
+                distance_unit: DistanceUnit::Meters,
+                precompute_edge_deltas: false,
+            })
+        };
+        let result = CombinedTraversalModelService::new(vec![distance(), distance()]);
+        assert!(result.is_err());
+    }
+}
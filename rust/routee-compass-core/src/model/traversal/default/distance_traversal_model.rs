@@ -4,8 +4,8 @@ use crate::model::state::StateModel;
 use crate::model::state::StateVariable;
 use crate::model::traversal::traversal_model::TraversalModel;
 use crate::model::traversal::traversal_model_error::TraversalModelError;
-use crate::model::unit::DistanceUnit;
 use crate::model::unit::BASE_DISTANCE_UNIT;
+use crate::model::unit::{Distance, DistanceUnit};
 use crate::util::geo::haversine;
 
 /// A simple traversal model that uses the edge distance as the cost of traversal.
@@ -63,8 +63,17 @@ impl TraversalModel for DistanceTraversalModel {
         Ok(())
     }
 
-    /// no additional state features are needed
     fn state_features(&self) -> Vec<(String, StateFeature)> {
-        vec![]
+        vec![(
+            String::from(Self::DISTANCE),
+            StateFeature::Distance {
+                distance_unit: self.distance_unit,
+                initial: Distance::ZERO,
+            },
+        )]
+    }
+
+    fn is_query_invariant(&self) -> bool {
+        true
     }
 }
@@ -1,12 +1,16 @@
+use crate::model::network::Graph;
 use crate::model::traversal::default::DistanceTraversalModel;
 use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::EdgeDeltaTable;
 use crate::model::traversal::TraversalModelError;
 use crate::model::traversal::TraversalModelService;
-use crate::model::unit::DistanceUnit;
+use crate::model::unit::{AsF64, DistanceUnit, BASE_DISTANCE_UNIT};
 use std::sync::Arc;
 
 pub struct DistanceTraversalService {
     pub distance_unit: DistanceUnit,
+    /// if true, edge distances are precomputed in the distance unit when the app is built
+    pub precompute_edge_deltas: bool,
 }
 
 impl TraversalModelService for DistanceTraversalService {
@@ -17,4 +21,25 @@ impl TraversalModelService for DistanceTraversalService {
         let m: Arc<dyn TraversalModel> = Arc::new(DistanceTraversalModel::new(self.distance_unit));
         Ok(m)
     }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        vec![String::from("distance")]
+    }
+
+    fn edge_delta_table(
+        &self,
+        graph: &Graph,
+    ) -> Result<Option<Arc<EdgeDeltaTable>>, TraversalModelError> {
+        if !self.precompute_edge_deltas {
+            return Ok(None);
+        }
+        let features = DistanceTraversalModel::new(self.distance_unit).state_features();
+        let table = EdgeDeltaTable::new(features, graph, |edge, deltas| {
+            deltas[0] = BASE_DISTANCE_UNIT
+                .convert(&edge.distance, &self.distance_unit)
+                .as_f64();
+            Ok(())
+        })?;
+        Ok(Some(Arc::new(table)))
+    }
 }
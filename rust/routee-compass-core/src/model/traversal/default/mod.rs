@@ -1,11 +1,27 @@
+mod combined_traversal_model;
 mod distance_traversal_model;
 mod distance_traversal_service;
+mod speed_overrides;
 mod speed_traversal_engine;
 mod speed_traversal_model;
 mod speed_traversal_service;
+mod time_dependent_speed_engine;
+mod time_dependent_speed_model;
+mod time_dependent_speed_service;
+mod toll_traversal_engine;
+mod toll_traversal_model;
+mod toll_traversal_service;
 
+pub use combined_traversal_model::{CombinedTraversalModel, CombinedTraversalModelService};
 pub use distance_traversal_model::DistanceTraversalModel;
 pub use distance_traversal_service::DistanceTraversalService;
+pub use speed_overrides::SpeedOverrides;
 pub use speed_traversal_engine::SpeedTraversalEngine;
 pub use speed_traversal_model::SpeedTraversalModel;
 pub use speed_traversal_service::SpeedLookupService;
+pub use time_dependent_speed_engine::{departure_seconds, TimeDependentSpeedEngine};
+pub use time_dependent_speed_model::TimeDependentSpeedModel;
+pub use time_dependent_speed_service::TimeDependentSpeedService;
+pub use toll_traversal_engine::{TollRate, TollTraversalEngine};
+pub use toll_traversal_model::TollTraversalModel;
+pub use toll_traversal_service::TollTraversalService;
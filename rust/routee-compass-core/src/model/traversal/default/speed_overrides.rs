@@ -0,0 +1,103 @@
+use crate::model::network::edge_id::EdgeId;
+use crate::model::traversal::TraversalModelError;
+use crate::model::unit::{Speed, SpeedUnit};
+use crate::util::fs::read_utils;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// speeds that replace the speed table for some edges of a single query, such as from
+/// incident or probe speed feeds. overrides are listed inline by edge id, read from a
+/// CSV file with `edge_id,speed` columns, or both, where inline speeds take precedence.
+///
+/// ```json
+/// "speed_overrides": {
+///   "speed_unit": "kilometers_per_hour",
+///   "edges": { "12": 15.0, "40": 5.0 },
+///   "input_file": "incidents.csv"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SpeedOverrides {
+    pub speed_unit: SpeedUnit,
+    #[serde(default)]
+    pub edges: HashMap<EdgeId, f64>,
+    #[serde(default)]
+    pub input_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpeedOverrideRow {
+    edge_id: EdgeId,
+    speed: f64,
+}
+
+impl SpeedOverrides {
+    pub const QUERY_KEY: &'static str = "speed_overrides";
+
+    /// reads the speed overrides of a query, if any
+    pub fn from_query(
+        query: &serde_json::Value,
+    ) -> Result<Option<SpeedOverrides>, TraversalModelError> {
+        match query.get(Self::QUERY_KEY) {
+            None => Ok(None),
+            Some(value) => {
+                let overrides = serde_json::from_value(value.clone()).map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "unable to read query {}: {}",
+                        Self::QUERY_KEY,
+                        e
+                    ))
+                })?;
+                Ok(Some(overrides))
+            }
+        }
+    }
+
+    /// builds the lookup of override speeds by edge in the target speed unit
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_count` - the number of edges in the graph, to validate edge ids
+    /// * `speed_unit` - the unit of the resulting speeds
+    ///
+    /// # Returns
+    ///
+    /// the speed for each overridden edge
+    pub fn build(
+        &self,
+        edge_count: usize,
+        speed_unit: &SpeedUnit,
+    ) -> Result<HashMap<EdgeId, Speed>, TraversalModelError> {
+        let mut speeds: Vec<(EdgeId, f64)> = vec![];
+        if let Some(input_file) = &self.input_file {
+            let rows = read_utils::from_csv::<SpeedOverrideRow>(&input_file, true, None, None)
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "failure reading speed overrides file {}: {}",
+                        input_file, e
+                    ))
+                })?;
+            speeds.extend(rows.iter().map(|row| (row.edge_id, row.speed)));
+        }
+        speeds.extend(self.edges.iter().map(|(edge_id, speed)| (*edge_id, *speed)));
+
+        let mut result = HashMap::new();
+        for (edge_id, speed) in speeds.into_iter() {
+            if edge_id.as_usize() >= edge_count {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed override edge id {} not found in speed table with {} entries",
+                    edge_id, edge_count
+                )));
+            }
+            if speed.is_nan() || speed <= 0.0 {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed override {} for edge id {} must be positive",
+                    speed, edge_id
+                )));
+            }
+            let converted = self.speed_unit.convert(&Speed::new(speed), speed_unit);
+            result.insert(edge_id, converted);
+        }
+        Ok(result)
+    }
+}
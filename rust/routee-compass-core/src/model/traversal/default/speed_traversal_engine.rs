@@ -1,6 +1,7 @@
 use kdam::Bar;
 
-use crate::model::unit::DistanceUnit;
+use crate::model::network::Edge;
+use crate::model::unit::{Distance, DistanceUnit, Time};
 use crate::model::unit::{SpeedUnit, TimeUnit, BASE_DISTANCE_UNIT, BASE_TIME_UNIT};
 use crate::util::fs::read_decoders;
 use crate::{
@@ -49,6 +50,23 @@ impl SpeedTraversalEngine {
         };
         Ok(model)
     }
+
+    /// the time and distance of traversing an edge at some speed, in the units of this engine
+    pub fn edge_time_and_distance(
+        &self,
+        edge: &Edge,
+        speed: &Speed,
+    ) -> Result<(Time, Distance), TraversalModelError> {
+        let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.distance_unit);
+        let time = Time::create(
+            speed,
+            &self.speed_unit,
+            &distance,
+            &self.distance_unit,
+            &self.time_unit,
+        )?;
+        Ok((time, distance))
+    }
 }
 
 pub fn get_max_speed(speed_table: &[Speed]) -> Result<Speed, TraversalModelError> {
@@ -5,18 +5,38 @@ use crate::model::state::StateFeature;
 use crate::model::state::StateModel;
 use crate::model::state::StateVariable;
 use crate::model::traversal::traversal_model::TraversalModel;
-use crate::model::unit::{Distance, Time, BASE_DISTANCE_UNIT};
+use crate::model::unit::{Distance, Time};
 use crate::model::{traversal::traversal_model_error::TraversalModelError, unit::Speed};
 use crate::util::geo::haversine;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub struct SpeedTraversalModel {
     engine: Arc<SpeedTraversalEngine>,
+    overrides: HashMap<EdgeId, Speed>,
+    max_speed: Speed,
 }
 
 impl SpeedTraversalModel {
     pub fn new(engine: Arc<SpeedTraversalEngine>) -> SpeedTraversalModel {
-        SpeedTraversalModel { engine }
+        SpeedTraversalModel::new_with_overrides(engine, HashMap::new())
+    }
+
+    /// builds a model where the overriding speeds replace the speed table for some edges.
+    /// the overriding speeds are in the speed unit of the engine. the estimate uses the
+    /// faster of the speed table max speed and the overriding speeds so it remains admissible.
+    pub fn new_with_overrides(
+        engine: Arc<SpeedTraversalEngine>,
+        overrides: HashMap<EdgeId, Speed>,
+    ) -> SpeedTraversalModel {
+        let max_speed = overrides
+            .values()
+            .fold(engine.max_speed, |acc, speed| acc.max(*speed));
+        SpeedTraversalModel {
+            engine,
+            overrides,
+            max_speed,
+        }
     }
     const DISTANCE: &'static str = "distance";
     const TIME: &'static str = "time";
@@ -30,15 +50,11 @@ impl TraversalModel for SpeedTraversalModel {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (_, edge, _) = trajectory;
-        let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.engine.distance_unit);
-        let speed = get_speed(&self.engine.speed_table, edge.edge_id)?;
-        let edge_time = Time::create(
-            &speed,
-            &self.engine.speed_unit,
-            &distance,
-            &self.engine.distance_unit,
-            &self.engine.time_unit,
-        )?;
+        let speed = match self.overrides.get(&edge.edge_id) {
+            Some(speed) => *speed,
+            None => get_speed(&self.engine.speed_table, edge.edge_id)?,
+        };
+        let (edge_time, distance) = self.engine.edge_time_and_distance(edge, &speed)?;
 
         state_model.add_time(
             state,
@@ -76,7 +92,7 @@ impl TraversalModel for SpeedTraversalModel {
         }
 
         let estimated_time = Time::create(
-            &self.engine.max_speed,
+            &self.max_speed,
             &self.engine.speed_unit,
             &distance,
             &self.engine.distance_unit,
@@ -116,6 +132,11 @@ impl TraversalModel for SpeedTraversalModel {
             ),
         ]
     }
+
+    /// without overrides, every query traverses edges at the speeds of the speed table
+    fn is_query_invariant(&self) -> bool {
+        self.overrides.is_empty()
+    }
 }
 
 /// look up a speed from the speed table
@@ -133,6 +154,7 @@ pub fn get_speed(speed_table: &[Speed], edge_id: EdgeId) -> Result<Speed, Traver
 mod tests {
     use super::*;
     use crate::model::network::{Edge, EdgeId, Vertex, VertexId};
+    use crate::model::traversal::default::SpeedOverrides;
     use crate::model::unit::{Distance, DistanceUnit, SpeedUnit, TimeUnit};
     use crate::util::geo::coord::InternalCoord;
     use geo::coord;
@@ -220,6 +242,44 @@ mod tests {
         approx_eq(state[1].into(), expected, 0.001);
     }
 
+    #[test]
+    fn test_edge_cost_lookup_with_speed_override() {
+        let file = filepath();
+        let engine = SpeedTraversalEngine::new(
+            &file,
+            SpeedUnit::KilometersPerHour,
+            None,
+            Some(TimeUnit::Seconds),
+        )
+        .unwrap();
+        let query = serde_json::json!({
+            "speed_overrides": {
+                "speed_unit": "kilometers_per_hour",
+                "edges": { "0": 100.0 }
+            }
+        });
+        let overrides = SpeedOverrides::from_query(&query)
+            .unwrap()
+            .unwrap()
+            .build(engine.speed_table.len(), &engine.speed_unit)
+            .unwrap();
+        let model = SpeedTraversalModel::new_with_overrides(Arc::new(engine), overrides);
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = mock_vertex();
+        let e1 = mock_edge(0);
+        // 100 meters @ 100kph (overriding 10kph) should take 3.6 seconds
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.to_f64(), 3.6, 0.001);
+        // the override is faster than the speed table, so the estimate must use it
+        assert_eq!(model.max_speed, Speed::new(100.0));
+    }
+
     #[test]
     fn test_edge_cost_lookup_with_milliseconds_time_unit() {
         let file = filepath();
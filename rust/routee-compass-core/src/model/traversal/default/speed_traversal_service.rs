@@ -1,21 +1,66 @@
 use super::{
-    speed_traversal_engine::SpeedTraversalEngine, speed_traversal_model::SpeedTraversalModel,
+    speed_overrides::SpeedOverrides,
+    speed_traversal_engine::SpeedTraversalEngine,
+    speed_traversal_model::{get_speed, SpeedTraversalModel},
 };
+use crate::model::network::Graph;
 use crate::model::traversal::{
     traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
-    traversal_model_service::TraversalModelService,
+    traversal_model_service::TraversalModelService, EdgeDeltaTable,
 };
+use crate::model::unit::AsF64;
 use std::sync::Arc;
 
 pub struct SpeedLookupService {
     pub e: Arc<SpeedTraversalEngine>,
+    /// if true, edge times and distances at the speed table speeds are precomputed
+    /// when the app is built, for use by queries without speed overrides
+    pub precompute_edge_deltas: bool,
 }
 
 impl TraversalModelService for SpeedLookupService {
+    /// builds a model for the query, applying any `speed_overrides` listed on the query
+    /// over the speed table for this query only.
     fn build(
         &self,
-        _parameters: &serde_json::Value,
+        parameters: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
-        Ok(Arc::new(SpeedTraversalModel::new(self.e.clone())))
+        match SpeedOverrides::from_query(parameters)? {
+            None => Ok(Arc::new(SpeedTraversalModel::new(self.e.clone()))),
+            Some(overrides) => {
+                let speeds = overrides.build(self.e.speed_table.len(), &self.e.speed_unit)?;
+                Ok(Arc::new(SpeedTraversalModel::new_with_overrides(
+                    self.e.clone(),
+                    speeds,
+                )))
+            }
+        }
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        SpeedTraversalModel::new(self.e.clone())
+            .state_features()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    fn edge_delta_table(
+        &self,
+        graph: &Graph,
+    ) -> Result<Option<Arc<EdgeDeltaTable>>, TraversalModelError> {
+        if !self.precompute_edge_deltas {
+            return Ok(None);
+        }
+        // same features, in the same order, as the speed traversal model
+        let features = SpeedTraversalModel::new(self.e.clone()).state_features();
+        let table = EdgeDeltaTable::new(features, graph, |edge, deltas| {
+            let speed = get_speed(&self.e.speed_table, edge.edge_id)?;
+            let (time, distance) = self.e.edge_time_and_distance(edge, &speed)?;
+            deltas[0] = time.as_f64();
+            deltas[1] = distance.as_f64();
+            Ok(())
+        })?;
+        Ok(Some(Arc::new(table)))
     }
 }
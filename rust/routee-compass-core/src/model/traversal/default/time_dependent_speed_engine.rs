@@ -0,0 +1,271 @@
+use super::speed_traversal_engine::get_max_speed;
+use crate::model::network::edge_id::EdgeId;
+use crate::model::traversal::TraversalModelError;
+use crate::model::unit::{
+    AsF64, Distance, DistanceUnit, Speed, SpeedUnit, TimeUnit, BASE_DISTANCE_UNIT, BASE_SPEED_UNIT,
+    BASE_TIME_UNIT,
+};
+use crate::util::fs::read_utils;
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Timelike};
+use kdam::Bar;
+use std::path::Path;
+
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+pub const SECONDS_PER_WEEK: f64 = 7.0 * SECONDS_PER_DAY;
+
+/// speed profiles that vary by time of day and day of week, one per edge.
+///
+/// each row of the speed profile file is the comma-delimited profile of one edge, in
+/// edge id order. a profile holds the speed for each time bin, starting at midnight,
+/// and is either
+///   - a single speed, used at all times
+///   - one day of bins, repeated every day of the week
+///   - one week of bins, starting on monday
+///
+/// travel times move through the bins of a profile as an edge is traversed, so that
+/// leaving later never results in arriving earlier (FIFO).
+pub struct TimeDependentSpeedEngine {
+    pub profiles: Box<[Box<[Speed]>]>,
+    pub bin_seconds: f64,
+    pub speed_unit: SpeedUnit,
+    pub time_unit: TimeUnit,
+    pub distance_unit: DistanceUnit,
+    pub max_speed: Speed,
+}
+
+impl TimeDependentSpeedEngine {
+    pub fn new<P: AsRef<Path>>(
+        speed_profile_path: &P,
+        speed_unit: SpeedUnit,
+        bin_minutes: u64,
+        distance_unit_opt: Option<DistanceUnit>,
+        time_unit_opt: Option<TimeUnit>,
+    ) -> Result<TimeDependentSpeedEngine, TraversalModelError> {
+        let profiles: Box<[Box<[Speed]>]> = read_utils::read_raw_file(
+            speed_profile_path,
+            decode_profile,
+            Some(Bar::builder().desc("link speed profiles")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "cannot read {} due to {}",
+                speed_profile_path.as_ref().to_str().unwrap_or_default(),
+                e,
+            ))
+        })?;
+        TimeDependentSpeedEngine::from_profiles(
+            profiles,
+            speed_unit,
+            bin_minutes,
+            distance_unit_opt,
+            time_unit_opt,
+        )
+    }
+
+    pub fn from_profiles(
+        profiles: Box<[Box<[Speed]>]>,
+        speed_unit: SpeedUnit,
+        bin_minutes: u64,
+        distance_unit_opt: Option<DistanceUnit>,
+        time_unit_opt: Option<TimeUnit>,
+    ) -> Result<TimeDependentSpeedEngine, TraversalModelError> {
+        let bin_seconds = (bin_minutes * 60) as f64;
+        if bin_minutes == 0 || SECONDS_PER_DAY % bin_seconds != 0.0 {
+            return Err(TraversalModelError::BuildError(format!(
+                "speed profile bin size of {} minutes must evenly divide a day",
+                bin_minutes
+            )));
+        }
+        let bins_per_day = (SECONDS_PER_DAY / bin_seconds) as usize;
+        for (edge_id, profile) in profiles.iter().enumerate() {
+            let len = profile.len();
+            if len != 1 && len != bins_per_day && len != 7 * bins_per_day {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed profile for edge {} has {} bins, must be 1, {} (daily) or {} (weekly)",
+                    edge_id,
+                    len,
+                    bins_per_day,
+                    7 * bins_per_day
+                )));
+            }
+            if let Some(speed) = profile
+                .iter()
+                .find(|s| !s.as_f64().is_finite() || s.as_f64() < 0.0)
+            {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed profile for edge {} has invalid speed {}, speeds must be finite and non-negative",
+                    edge_id, speed
+                )));
+            }
+            if profile.iter().all(|s| *s == Speed::ZERO) {
+                return Err(TraversalModelError::BuildError(format!(
+                    "speed profile for edge {} has no positive speeds",
+                    edge_id
+                )));
+            }
+        }
+        let max_speed = get_max_speed(&profiles.iter().flatten().cloned().collect::<Vec<_>>())?;
+        Ok(TimeDependentSpeedEngine {
+            profiles,
+            bin_seconds,
+            speed_unit,
+            time_unit: time_unit_opt.unwrap_or(BASE_TIME_UNIT),
+            distance_unit: distance_unit_opt.unwrap_or(BASE_DISTANCE_UNIT),
+            max_speed,
+        })
+    }
+
+    /// computes the time to traverse an edge when entering it at some time of the week.
+    /// the speed changes at each bin boundary crossed along the way. bins with zero speed
+    /// hold the vehicle in place until the next bin.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_id`  - edge to traverse
+    /// * `distance` - edge distance in the base distance unit
+    /// * `start`    - seconds since midnight monday when the edge is entered
+    ///
+    /// # Returns
+    ///
+    /// the travel time in seconds
+    pub fn travel_seconds(
+        &self,
+        edge_id: EdgeId,
+        distance: Distance,
+        start: f64,
+    ) -> Result<f64, TraversalModelError> {
+        let profile = self.profiles.get(edge_id.as_usize()).ok_or_else(|| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "could not find expected index {} in speed profiles",
+                edge_id
+            ))
+        })?;
+        let mut remaining = distance.as_f64();
+        let mut time = start.rem_euclid(SECONDS_PER_WEEK);
+        while remaining > 0.0 {
+            let bin = (time / self.bin_seconds).floor();
+            let bin_end = (bin + 1.0) * self.bin_seconds;
+            let speed = self
+                .speed_unit
+                .convert(&profile[bin as usize % profile.len()], &BASE_SPEED_UNIT)
+                .as_f64();
+            let bin_distance = speed * (bin_end - time);
+            if bin_distance >= remaining {
+                time += remaining / speed;
+                remaining = 0.0;
+            } else {
+                time = bin_end;
+                remaining -= bin_distance;
+            }
+        }
+        Ok(time - start.rem_euclid(SECONDS_PER_WEEK))
+    }
+}
+
+/// reads the departure time of a query as seconds since midnight monday. accepts a date
+/// and time such as `2024-06-03T08:15:00`, or a time of day such as `08:15` which is
+/// treated as a monday.
+pub fn departure_seconds(departure_time: &str) -> Result<f64, TraversalModelError> {
+    let (days, time) = match departure_time.parse::<NaiveDateTime>() {
+        Ok(datetime) => (datetime.weekday().num_days_from_monday(), datetime.time()),
+        Err(_) => {
+            let time = departure_time
+                .parse::<NaiveTime>()
+                .or_else(|_| NaiveTime::parse_from_str(departure_time, "%H:%M"))
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "unable to parse departure_time {}: {}",
+                        departure_time, e
+                    ))
+                })?;
+            (0, time)
+        }
+    };
+    Ok(days as f64 * SECONDS_PER_DAY + time.num_seconds_from_midnight() as f64)
+}
+
+fn decode_profile(idx: usize, row: String) -> Result<Box<[Speed]>, std::io::Error> {
+    row.split(',')
+        .map(|value| {
+            value.trim().parse::<Speed>().map_err(|e| {
+                let msg = format!("failure decoding speed profile row {} due to: {}", idx, e);
+                std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a single edge whose speed rises from 1 m/s to 10 m/s at 00:15
+    fn mock_engine() -> TimeDependentSpeedEngine {
+        let mut profile = vec![Speed::new(10.0); 96];
+        profile[0] = Speed::new(1.0);
+        TimeDependentSpeedEngine::from_profiles(
+            vec![profile.into_boxed_slice()].into_boxed_slice(),
+            SpeedUnit::MetersPerSecond,
+            15,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_travel_time_crosses_bins() {
+        let engine = mock_engine();
+        // 100m at 1 m/s from 00:14:00 covers 60m, then 40m at 10 m/s takes 4s
+        let seconds = engine
+            .travel_seconds(EdgeId(0), Distance::new(100.0), 840.0)
+            .unwrap();
+        assert_eq!(seconds, 64.0);
+    }
+
+    #[test]
+    fn test_fifo() {
+        let engine = mock_engine();
+        let mut last_arrival = 0.0;
+        for start in (0..1200).step_by(10) {
+            let start = start as f64;
+            let arrival = start
+                + engine
+                    .travel_seconds(EdgeId(0), Distance::new(1000.0), start)
+                    .unwrap();
+            assert!(
+                arrival >= last_arrival,
+                "arrival moved earlier at {}",
+                start
+            );
+            last_arrival = arrival;
+        }
+    }
+
+    #[test]
+    fn test_invalid_speeds() {
+        for speed in [-1.0, f64::NAN, f64::INFINITY] {
+            let profile = vec![Speed::new(10.0), Speed::new(speed)];
+            let result = TimeDependentSpeedEngine::from_profiles(
+                vec![profile.into_boxed_slice()].into_boxed_slice(),
+                SpeedUnit::MetersPerSecond,
+                720,
+                None,
+                None,
+            );
+            assert!(result.is_err(), "speed {} should be rejected", speed);
+        }
+    }
+
+    #[test]
+    fn test_departure_seconds() {
+        // 2024-06-05 is a wednesday
+        assert_eq!(
+            departure_seconds("2024-06-05T08:15:00").unwrap(),
+            2.0 * SECONDS_PER_DAY + 29_700.0
+        );
+        assert_eq!(departure_seconds("08:15").unwrap(), 29_700.0);
+        assert!(departure_seconds("morning").is_err());
+    }
+}
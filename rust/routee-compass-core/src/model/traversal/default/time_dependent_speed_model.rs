@@ -0,0 +1,192 @@
+use super::time_dependent_speed_engine::TimeDependentSpeedEngine;
+use crate::model::network::{Edge, Vertex};
+use crate::model::state::StateFeature;
+use crate::model::state::StateModel;
+use crate::model::state::StateVariable;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::unit::{AsF64, Distance, Time, TimeUnit, BASE_DISTANCE_UNIT};
+use crate::util::geo::haversine;
+use std::sync::Arc;
+
+/// traverses edges at the speed of their profile at the time each edge is entered,
+/// which is the query departure time plus the accumulated time of the search state.
+pub struct TimeDependentSpeedModel {
+    engine: Arc<TimeDependentSpeedEngine>,
+    departure_seconds: f64,
+}
+
+impl TimeDependentSpeedModel {
+    pub fn new(
+        engine: Arc<TimeDependentSpeedEngine>,
+        departure_seconds: f64,
+    ) -> TimeDependentSpeedModel {
+        TimeDependentSpeedModel {
+            engine,
+            departure_seconds,
+        }
+    }
+    const DISTANCE: &'static str = "distance";
+    const TIME: &'static str = "time";
+}
+
+impl TraversalModel for TimeDependentSpeedModel {
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let elapsed = state_model.get_time(state, &Self::TIME.into(), &TimeUnit::Seconds)?;
+        let seconds = self.engine.travel_seconds(
+            edge.edge_id,
+            edge.distance,
+            self.departure_seconds + elapsed.as_f64(),
+        )?;
+        let edge_time = TimeUnit::Seconds.convert(&Time::new(seconds), &self.engine.time_unit);
+        let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.engine.distance_unit);
+
+        state_model.add_time(
+            state,
+            &Self::TIME.into(),
+            &edge_time,
+            &self.engine.time_unit,
+        )?;
+        state_model.add_distance(
+            state,
+            &Self::DISTANCE.into(),
+            &distance,
+            &self.engine.distance_unit,
+        )?;
+        Ok(())
+    }
+
+    /// estimates with the fastest speed of any profile, which never overestimates
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (src, dst) = od;
+        let distance =
+            haversine::coord_distance(&src.coordinate, &dst.coordinate, self.engine.distance_unit)
+                .map_err(|e| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "could not compute haversine distance between {} and {}: {}",
+                        src, dst, e
+                    ))
+                })?;
+
+        if distance == Distance::ZERO {
+            return Ok(());
+        }
+
+        let estimated_time = Time::create(
+            &self.engine.max_speed,
+            &self.engine.speed_unit,
+            &distance,
+            &self.engine.distance_unit,
+            &self.engine.time_unit,
+        )?;
+        state_model.add_time(
+            state,
+            &Self::TIME.into(),
+            &estimated_time,
+            &self.engine.time_unit,
+        )?;
+        state_model.add_distance(
+            state,
+            &Self::DISTANCE.into(),
+            &distance,
+            &self.engine.distance_unit,
+        )?;
+
+        Ok(())
+    }
+
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        vec![
+            (
+                String::from(Self::TIME),
+                StateFeature::Time {
+                    time_unit: self.engine.time_unit,
+                    initial: Time::ZERO,
+                },
+            ),
+            (
+                String::from(Self::DISTANCE),
+                StateFeature::Distance {
+                    distance_unit: self.engine.distance_unit,
+                    initial: Distance::ZERO,
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::network::{EdgeId, VertexId};
+    use crate::model::unit::{DistanceUnit, Speed, SpeedUnit};
+    use crate::util::geo::coord::InternalCoord;
+    use geo::coord;
+
+    #[test]
+    fn test_speed_depends_on_elapsed_time() {
+        // 10 m/s until 08:00, then 5 m/s
+        let mut profile = vec![Speed::new(10.0); 24];
+        for speed in profile.iter_mut().skip(8) {
+            *speed = Speed::new(5.0);
+        }
+        let engine = TimeDependentSpeedEngine::from_profiles(
+            vec![profile.into_boxed_slice()].into_boxed_slice(),
+            SpeedUnit::MetersPerSecond,
+            60,
+            Some(DistanceUnit::Meters),
+            Some(TimeUnit::Seconds),
+        )
+        .unwrap();
+        let model = TimeDependentSpeedModel::new(Arc::new(engine), 7.0 * 3600.0);
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(0),
+            distance: Distance::new(1000.0),
+        };
+
+        // departing at 07:00 the edge takes 100 seconds
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        assert_eq!(time.as_f64(), 100.0);
+
+        // one hour into the trip the same edge takes 200 seconds
+        state_model
+            .set_time(
+                &mut state,
+                &"time".into(),
+                &Time::new(3600.0),
+                &TimeUnit::Seconds,
+            )
+            .unwrap();
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        assert_eq!(time.as_f64(), 3800.0);
+    }
+}
@@ -0,0 +1,48 @@
+use super::{
+    time_dependent_speed_engine::{departure_seconds, TimeDependentSpeedEngine},
+    time_dependent_speed_model::TimeDependentSpeedModel,
+};
+use crate::model::traversal::{
+    traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
+    traversal_model_service::TraversalModelService,
+};
+use std::sync::Arc;
+
+pub struct TimeDependentSpeedService {
+    pub e: Arc<TimeDependentSpeedEngine>,
+}
+
+impl TraversalModelService for TimeDependentSpeedService {
+    /// builds a model for the `departure_time` of the query
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let departure_time = parameters
+            .get("departure_time")
+            .ok_or_else(|| {
+                TraversalModelError::BuildError(String::from(
+                    "time-dependent speed model requires a 'departure_time' on the query",
+                ))
+            })?
+            .as_str()
+            .ok_or_else(|| {
+                TraversalModelError::BuildError(String::from(
+                    "expected 'departure_time' value to be a string",
+                ))
+            })?;
+        let departure_seconds = departure_seconds(departure_time)?;
+        Ok(Arc::new(TimeDependentSpeedModel::new(
+            self.e.clone(),
+            departure_seconds,
+        )))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        TimeDependentSpeedModel::new(self.e.clone(), 0.0)
+            .state_features()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
@@ -0,0 +1,215 @@
+use super::time_dependent_speed_engine::SECONDS_PER_DAY;
+use crate::model::network::edge_id::EdgeId;
+use crate::model::traversal::TraversalModelError;
+use crate::model::unit::{Currency, CurrencyUnit};
+use crate::util::fs::read_utils;
+use chrono::{NaiveTime, Timelike};
+use kdam::Bar;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// a row of the toll file. the vehicle class and time window are optional, where
+/// an empty value applies to every vehicle class or all day.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct TollRow {
+    edge_id: EdgeId,
+    toll: f64,
+    #[serde(default)]
+    vehicle_class: Option<String>,
+    #[serde(default)]
+    start_time: Option<String>,
+    #[serde(default)]
+    end_time: Option<String>,
+}
+
+/// the toll charged on an edge for a vehicle class during a daily time window
+#[derive(Clone, Debug)]
+pub struct TollRate {
+    pub toll: Currency,
+    pub vehicle_class: Option<String>,
+    /// seconds since midnight of the start and end of the window. a window where the
+    /// start is after the end wraps around midnight.
+    pub window: Option<(f64, f64)>,
+}
+
+impl TollRate {
+    fn applies(&self, vehicle_class: Option<&String>, time_of_day: Option<f64>) -> bool {
+        let class_applies = match &self.vehicle_class {
+            None => true,
+            Some(class) => vehicle_class == Some(class),
+        };
+        let time_applies = match (self.window, time_of_day) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some((start, end)), Some(t)) if start <= end => start <= t && t < end,
+            (Some((start, end)), Some(t)) => t >= start || t < end,
+        };
+        class_applies && time_applies
+    }
+}
+
+/// per-edge tolls, read from a CSV file with columns
+/// `edge_id,toll,vehicle_class,start_time,end_time`. times of day are written as `HH:MM`.
+/// edges missing from the file have no toll.
+pub struct TollTraversalEngine {
+    pub tolls: HashMap<EdgeId, Vec<TollRate>>,
+    pub currency_unit: CurrencyUnit,
+    /// true if any toll depends on the time of day
+    pub has_time_windows: bool,
+}
+
+impl TollTraversalEngine {
+    pub fn new<P: AsRef<Path>>(
+        toll_input_file: &P,
+        currency_unit: CurrencyUnit,
+    ) -> Result<TollTraversalEngine, TraversalModelError> {
+        let rows: Box<[TollRow]> = read_utils::from_csv(
+            &toll_input_file,
+            true,
+            Some(Bar::builder().desc("edge tolls")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failure reading toll file {}: {}",
+                toll_input_file.as_ref().to_str().unwrap_or_default(),
+                e
+            ))
+        })?;
+        let mut tolls: HashMap<EdgeId, Vec<TollRate>> = HashMap::new();
+        for row in rows.iter() {
+            if row.toll.is_nan() || row.toll < 0.0 {
+                return Err(TraversalModelError::BuildError(format!(
+                    "toll {} for edge id {} must be non-negative",
+                    row.toll, row.edge_id
+                )));
+            }
+            let window = match (&row.start_time, &row.end_time) {
+                (None, None) => None,
+                (Some(start), Some(end)) => {
+                    Some((parse_time_of_day(start)?, parse_time_of_day(end)?))
+                }
+                _ => {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "toll for edge id {} must have both a start_time and end_time or neither",
+                        row.edge_id
+                    )))
+                }
+            };
+            let rate = TollRate {
+                toll: Currency::new(row.toll),
+                vehicle_class: row.vehicle_class.clone(),
+                window,
+            };
+            tolls.entry(row.edge_id).or_default().push(rate);
+        }
+        Ok(TollTraversalEngine::from_tolls(tolls, currency_unit))
+    }
+
+    pub fn from_tolls(
+        tolls: HashMap<EdgeId, Vec<TollRate>>,
+        currency_unit: CurrencyUnit,
+    ) -> TollTraversalEngine {
+        let has_time_windows = tolls.values().flatten().any(|t| t.window.is_some());
+        TollTraversalEngine {
+            tolls,
+            currency_unit,
+            has_time_windows,
+        }
+    }
+
+    /// finds the toll for an edge. tolls listed for the vehicle class are preferred over
+    /// tolls for every vehicle class, and otherwise the first applicable toll is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_id`       - edge being traversed
+    /// * `vehicle_class` - vehicle class of the query, if any
+    /// * `seconds`       - seconds since midnight monday when the edge is entered, if known
+    ///
+    /// # Returns
+    ///
+    /// the toll, which is zero if none applies
+    pub fn get_toll(
+        &self,
+        edge_id: &EdgeId,
+        vehicle_class: Option<&String>,
+        seconds: Option<f64>,
+    ) -> Currency {
+        let rates = match self.tolls.get(edge_id) {
+            None => return Currency::ZERO,
+            Some(rates) => rates,
+        };
+        let time_of_day = seconds.map(|s| s.rem_euclid(SECONDS_PER_DAY));
+        let applicable = rates
+            .iter()
+            .filter(|r| r.applies(vehicle_class, time_of_day))
+            .collect::<Vec<_>>();
+        applicable
+            .iter()
+            .find(|r| r.vehicle_class.is_some())
+            .or_else(|| applicable.first())
+            .map(|r| r.toll)
+            .unwrap_or(Currency::ZERO)
+    }
+}
+
+fn parse_time_of_day(value: &str) -> Result<f64, TraversalModelError> {
+    let time = NaiveTime::parse_from_str(value, "%H:%M").map_err(|e| {
+        TraversalModelError::BuildError(format!(
+            "unable to parse toll time of day {}, expected HH:MM: {}",
+            value, e
+        ))
+    })?;
+    Ok(time.num_seconds_from_midnight() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_engine() -> TollTraversalEngine {
+        // the peak toll is listed before the all-day toll
+        let rates = vec![
+            TollRate {
+                toll: Currency::new(5.0),
+                vehicle_class: None,
+                window: Some((7.0 * 3600.0, 9.0 * 3600.0)),
+            },
+            TollRate {
+                toll: Currency::new(2.0),
+                vehicle_class: None,
+                window: None,
+            },
+            TollRate {
+                toll: Currency::new(10.0),
+                vehicle_class: Some(String::from("truck")),
+                window: None,
+            },
+        ];
+        TollTraversalEngine::from_tolls(HashMap::from([(EdgeId(0), rates)]), CurrencyUnit::Dollars)
+    }
+
+    #[test]
+    fn test_toll_by_time_and_class() {
+        let engine = mock_engine();
+        let truck = String::from("truck");
+        let eight_am = Some(8.0 * 3600.0);
+        let noon_tuesday = Some(SECONDS_PER_DAY + 12.0 * 3600.0);
+        assert_eq!(
+            engine.get_toll(&EdgeId(0), None, eight_am),
+            Currency::new(5.0)
+        );
+        assert_eq!(
+            engine.get_toll(&EdgeId(0), None, noon_tuesday),
+            Currency::new(2.0)
+        );
+        assert_eq!(
+            engine.get_toll(&EdgeId(0), Some(&truck), eight_am),
+            Currency::new(10.0)
+        );
+        assert_eq!(engine.get_toll(&EdgeId(1), None, eight_am), Currency::ZERO);
+        assert!(engine.has_time_windows);
+    }
+}
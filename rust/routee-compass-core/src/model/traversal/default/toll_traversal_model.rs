@@ -0,0 +1,154 @@
+use super::toll_traversal_engine::TollTraversalEngine;
+use crate::model::network::{Edge, Vertex};
+use crate::model::state::StateFeature;
+use crate::model::state::StateModel;
+use crate::model::state::StateVariable;
+use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::unit::{AsF64, Currency, TimeUnit};
+use std::sync::Arc;
+
+/// accumulates the tolls charged on each edge into a `toll` state feature. when the
+/// query has a departure time, the time of day an edge is entered is the departure time
+/// plus the accumulated time of the search state, which is provided by another model,
+/// such as a speed model combined with this one.
+pub struct TollTraversalModel {
+    engine: Arc<TollTraversalEngine>,
+    vehicle_class: Option<String>,
+    departure_seconds: Option<f64>,
+}
+
+impl TollTraversalModel {
+    pub fn new(
+        engine: Arc<TollTraversalEngine>,
+        vehicle_class: Option<String>,
+        departure_seconds: Option<f64>,
+    ) -> TollTraversalModel {
+        TollTraversalModel {
+            engine,
+            vehicle_class,
+            departure_seconds,
+        }
+    }
+    const TOLL: &'static str = "toll";
+    const TIME: &'static str = "time";
+}
+
+impl TraversalModel for TollTraversalModel {
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let seconds = match self.departure_seconds {
+            None => None,
+            Some(departure) => {
+                let elapsed =
+                    state_model.get_time(state, &Self::TIME.into(), &TimeUnit::Seconds)?;
+                Some(departure + elapsed.as_f64())
+            }
+        };
+        let toll = self
+            .engine
+            .get_toll(&edge.edge_id, self.vehicle_class.as_ref(), seconds);
+        state_model.add_currency(state, &Self::TOLL.into(), &toll, &self.engine.currency_unit)?;
+        Ok(())
+    }
+
+    /// tolls are not estimated, as most paths may avoid them
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        vec![(
+            String::from(Self::TOLL),
+            StateFeature::Currency {
+                currency_unit: self.engine.currency_unit,
+                initial: Currency::ZERO,
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::toll_traversal_engine::TollRate;
+    use super::*;
+    use crate::model::network::{EdgeId, VertexId};
+    use crate::model::unit::{CurrencyUnit, Distance, Time};
+    use crate::util::geo::coord::InternalCoord;
+    use geo::coord;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_toll_at_time_of_day() {
+        // $3 from 07:00 to 09:00, otherwise $1
+        let rates = vec![
+            TollRate {
+                toll: Currency::new(3.0),
+                vehicle_class: None,
+                window: Some((7.0 * 3600.0, 9.0 * 3600.0)),
+            },
+            TollRate {
+                toll: Currency::new(1.0),
+                vehicle_class: None,
+                window: None,
+            },
+        ];
+        let engine = TollTraversalEngine::from_tolls(
+            HashMap::from([(EdgeId(0), rates)]),
+            CurrencyUnit::Dollars,
+        );
+        let model = TollTraversalModel::new(Arc::new(engine), None, Some(6.5 * 3600.0));
+        let mut features = model.state_features();
+        features.push((
+            String::from("time"),
+            StateFeature::Time {
+                time_unit: TimeUnit::Seconds,
+                initial: Time::ZERO,
+            },
+        ));
+        let state_model = StateModel::empty().extend(features).unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(0),
+            distance: Distance::new(100.0),
+        };
+
+        // entering at 06:30 charges the off-peak toll
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+
+        // one hour later the peak toll applies
+        state_model
+            .set_time(
+                &mut state,
+                &"time".into(),
+                &Time::new(3600.0),
+                &TimeUnit::Seconds,
+            )
+            .unwrap();
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+        let toll = state_model
+            .get_currency(&state, &"toll".into(), &CurrencyUnit::Cents)
+            .unwrap();
+        assert_eq!(toll, Currency::new(400.0));
+    }
+}
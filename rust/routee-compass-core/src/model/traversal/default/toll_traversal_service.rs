@@ -0,0 +1,65 @@
+use super::{
+    time_dependent_speed_engine::departure_seconds, toll_traversal_engine::TollTraversalEngine,
+    toll_traversal_model::TollTraversalModel,
+};
+use crate::model::traversal::{
+    traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
+    traversal_model_service::TraversalModelService,
+};
+use std::sync::Arc;
+
+pub struct TollTraversalService {
+    pub e: Arc<TollTraversalEngine>,
+}
+
+impl TraversalModelService for TollTraversalService {
+    /// builds a model for the optional `vehicle_class` and `departure_time` of the query.
+    /// a departure time is required when tolls vary by time of day.
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let vehicle_class = match parameters.get("vehicle_class") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .ok_or_else(|| {
+                        TraversalModelError::BuildError(String::from(
+                            "expected 'vehicle_class' value to be a string",
+                        ))
+                    })?
+                    .to_string(),
+            ),
+        };
+        let departure = match parameters.get("departure_time") {
+            None if self.e.has_time_windows => {
+                return Err(TraversalModelError::BuildError(String::from(
+                    "toll model with time of day tolls requires a 'departure_time' on the query",
+                )))
+            }
+            None => None,
+            Some(value) => {
+                let departure_time = value.as_str().ok_or_else(|| {
+                    TraversalModelError::BuildError(String::from(
+                        "expected 'departure_time' value to be a string",
+                    ))
+                })?;
+                Some(departure_seconds(departure_time)?)
+            }
+        };
+        Ok(Arc::new(TollTraversalModel::new(
+            self.e.clone(),
+            vehicle_class,
+            departure,
+        )))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        TollTraversalModel::new(self.e.clone(), None, None)
+            .state_features()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
@@ -0,0 +1,217 @@
+use super::TraversalModelError;
+use crate::model::network::{Edge, EdgeId, Graph};
+use crate::model::state::{StateFeature, StateModel, StateVariable};
+use std::sync::Arc;
+
+/// per-edge state deltas of a traversal model whose edge traversals do not depend on
+/// the query, precomputed into a dense array when the app is built. searches apply
+/// these deltas directly instead of calling the traversal model on each edge.
+#[derive(Debug)]
+pub struct EdgeDeltaTable {
+    /// the features updated by the model, with the unit of each delta
+    features: Vec<(String, StateFeature)>,
+    /// deltas of each edge in edge id order, with one entry per feature
+    deltas: Box<[f64]>,
+}
+
+impl EdgeDeltaTable {
+    /// computes the deltas of every edge in the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - the features updated by the model, where the unit of each feature is the unit of its deltas
+    /// * `graph`    - the graph whose edges are precomputed
+    /// * `op`       - writes the deltas of one edge, in the order of `features`
+    ///
+    /// # Returns
+    ///
+    /// the table of deltas, or the first error of `op`
+    pub fn new<F>(
+        features: Vec<(String, StateFeature)>,
+        graph: &Graph,
+        op: F,
+    ) -> Result<EdgeDeltaTable, TraversalModelError>
+    where
+        F: Fn(&Edge, &mut [f64]) -> Result<(), TraversalModelError>,
+    {
+        let n_features = features.len();
+        let mut deltas = vec![0.0; graph.n_edges() * n_features].into_boxed_slice();
+        if n_features > 0 {
+            for (edge, edge_deltas) in graph.edges.iter().zip(deltas.chunks_mut(n_features)) {
+                op(edge, edge_deltas)?;
+            }
+        }
+        Ok(EdgeDeltaTable { features, deltas })
+    }
+
+    /// binds this table to the state model of a query. the deltas can only be added
+    /// to state features of the same type and unit, otherwise None is returned and
+    /// searches should call the traversal model instead.
+    pub fn bind(self: &Arc<Self>, state_model: &StateModel) -> Option<EdgeDeltaCache> {
+        let state_indices = self
+            .features
+            .iter()
+            .map(|(name, feature)| {
+                let (index, (_, state_feature)) = state_model
+                    .indexed_iter()
+                    .find(|(_, (state_name, _))| *state_name == name)?;
+                let matches = state_feature.get_feature_type() == feature.get_feature_type()
+                    && state_feature.get_feature_unit_name() == feature.get_feature_unit_name();
+                if !matches {
+                    log::debug!(
+                        "precomputed deltas for {} stored in {} do not match state feature {}",
+                        name,
+                        feature.get_feature_unit_name(),
+                        state_feature
+                    );
+                }
+                matches.then_some(index)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(EdgeDeltaCache {
+            table: self.clone(),
+            state_indices,
+        })
+    }
+}
+
+/// an [`EdgeDeltaTable`] bound to the state vector of a query
+#[derive(Clone, Debug)]
+pub struct EdgeDeltaCache {
+    table: Arc<EdgeDeltaTable>,
+    /// for each feature of the table, its index in the state vector
+    state_indices: Vec<usize>,
+}
+
+impl EdgeDeltaCache {
+    /// adds the precomputed deltas of an edge to the state
+    pub fn apply(
+        &self,
+        edge_id: &EdgeId,
+        state: &mut [StateVariable],
+    ) -> Result<(), TraversalModelError> {
+        let n_features = self.state_indices.len();
+        let start = edge_id.0 * n_features;
+        let deltas = self
+            .table
+            .deltas
+            .get(start..start + n_features)
+            .ok_or_else(|| {
+                TraversalModelError::InternalError(format!(
+                    "edge {} missing from precomputed edge deltas",
+                    edge_id
+                ))
+            })?;
+        let n_state = state.len();
+        for (index, delta) in self.state_indices.iter().zip(deltas) {
+            let variable = state.get_mut(*index).ok_or_else(|| {
+                TraversalModelError::InternalError(format!(
+                    "state index {} out of range for state vector with {} entries",
+                    index, n_state
+                ))
+            })?;
+            variable.0 += delta;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::network::{Graph, Vertex};
+    use crate::model::traversal::default::{SpeedLookupService, SpeedTraversalEngine};
+    use crate::model::traversal::TraversalModelService;
+    use crate::model::unit::{AsF64, Distance, DistanceUnit, Speed, SpeedUnit, Time, TimeUnit};
+
+    fn mock_graph() -> Graph {
+        let mut graph = Graph {
+            adj: Box::new([]),
+            rev: Box::new([]),
+            edges: Box::new([]),
+            vertices: Box::new([]),
+        };
+        graph
+            .extend(
+                vec![Vertex::new(0, -105.0, 40.0), Vertex::new(1, -105.0, 40.01)],
+                vec![Edge::new(0, 0, 1, 1000.0), Edge::new(1, 1, 0, 2500.0)],
+            )
+            .unwrap();
+        graph
+    }
+
+    fn mock_service() -> SpeedLookupService {
+        let engine = SpeedTraversalEngine {
+            speed_table: Box::new([Speed::new(60.0), Speed::new(30.0)]),
+            speed_unit: SpeedUnit::KilometersPerHour,
+            time_unit: TimeUnit::Minutes,
+            distance_unit: DistanceUnit::Kilometers,
+            max_speed: Speed::new(60.0),
+        };
+        SpeedLookupService {
+            e: Arc::new(engine),
+            precompute_edge_deltas: true,
+        }
+    }
+
+    #[test]
+    fn test_cache_matches_traversal_model() {
+        let graph = mock_graph();
+        let service = mock_service();
+        let table = service.edge_delta_table(&graph).unwrap().unwrap();
+        let model = service.build(&serde_json::json!({})).unwrap();
+        assert!(model.is_query_invariant());
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let cache = table.bind(&state_model).unwrap();
+
+        let mut cached = state_model.initial_state().unwrap();
+        let mut traversed = state_model.initial_state().unwrap();
+        for edge in graph.edges.iter() {
+            let (src, _, dst) = graph.edge_triplet(&edge.edge_id).unwrap();
+            cache.apply(&edge.edge_id, &mut cached).unwrap();
+            model
+                .traverse_edge((src, edge, dst), &mut traversed, &state_model)
+                .unwrap();
+        }
+        assert_eq!(cached, traversed);
+        // 1km at 60kph and 2.5km at 30kph
+        let time = state_model
+            .get_time(&cached, &"time".into(), &TimeUnit::Minutes)
+            .unwrap();
+        assert!((time - Time::new(6.0)).as_f64().abs() < 1e-9);
+        assert!(cache.apply(&EdgeId(2), &mut cached).is_err());
+    }
+
+    #[test]
+    fn test_cache_not_used_when_query_differs() {
+        let graph = mock_graph();
+        let service = mock_service();
+        let table = service.edge_delta_table(&graph).unwrap().unwrap();
+
+        // a state model storing distance in another unit
+        let state_model = StateModel::new(vec![
+            (
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                },
+            ),
+            (
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Miles,
+                    initial: Distance::ZERO,
+                },
+            ),
+        ]);
+        assert!(table.bind(&state_model).is_none());
+
+        // speed overrides change the traversal of the query
+        let query = serde_json::json!({
+            "speed_overrides": {"speed_unit": "kilometers_per_hour", "edges": {"0": 10.0}}
+        });
+        let model = service.build(&query).unwrap();
+        assert!(!model.is_query_invariant());
+    }
+}
@@ -1,10 +1,12 @@
 pub mod default;
+mod edge_delta_table;
 mod traversal_model;
 mod traversal_model_builder;
 mod traversal_model_error;
 mod traversal_model_service;
 mod traversal_result;
 
+pub use edge_delta_table::{EdgeDeltaCache, EdgeDeltaTable};
 pub use traversal_model::TraversalModel;
 pub use traversal_model_builder::TraversalModelBuilder;
 pub use traversal_model_error::TraversalModelError;
@@ -56,4 +56,11 @@ pub trait TraversalModel: Send + Sync {
         state: &mut Vec<StateVariable>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
+
+    /// true if this model traverses each edge the same way regardless of the query,
+    /// such as when the query has no overrides. searches may then apply the deltas
+    /// precomputed by the [`super::TraversalModelService`] instead of calling this model.
+    fn is_query_invariant(&self) -> bool {
+        false
+    }
 }
@@ -1,4 +1,8 @@
-use super::{traversal_model::TraversalModel, traversal_model_error::TraversalModelError};
+use super::{
+    edge_delta_table::EdgeDeltaTable, traversal_model::TraversalModel,
+    traversal_model_error::TraversalModelError,
+};
+use crate::model::network::Graph;
 use std::sync::Arc;
 
 /// A [`TraversalModelService`] is a persistent builder of [TraversalModel] instances.
@@ -29,4 +33,34 @@ pub trait TraversalModelService: Send + Sync {
         &self,
         query: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError>;
+
+    /// Lists the names of the state features provided by every [TraversalModel] this
+    /// service builds, for checking model configurations when the app is built. Features
+    /// that depend on the query, such as those of a vehicle, are not listed. By default,
+    /// no features are listed.
+    ///
+    /// [TraversalModel]: compass_core::model::traversal::traversal_model::TraversalModel
+    fn state_feature_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Precomputes the state deltas of each edge in the graph when this service is
+    /// configured to, for use by queries whose [TraversalModel] is query-invariant.
+    /// Called once when the app is built. By default, nothing is precomputed.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the graph the app searches
+    ///
+    /// # Returns
+    ///
+    /// The table of per-edge deltas, None if this service does not precompute, or an error
+    ///
+    /// [TraversalModel]: compass_core::model::traversal::traversal_model::TraversalModel
+    fn edge_delta_table(
+        &self,
+        _graph: &Graph,
+    ) -> Result<Option<Arc<EdgeDeltaTable>>, TraversalModelError> {
+        Ok(None)
+    }
 }
@@ -85,6 +85,11 @@ impl From<Energy> for Cost {
         Cost::new(value.as_f64())
     }
 }
+impl From<Currency> for Cost {
+    fn from(value: Currency) -> Self {
+        Cost::new(value.as_f64())
+    }
+}
 impl From<Speed> for Cost {
     fn from(value: Speed) -> Self {
         Cost::new(value.as_f64())
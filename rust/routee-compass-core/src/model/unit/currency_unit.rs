@@ -0,0 +1,58 @@
+use super::Currency;
+use crate::util::serde::serde_ops::string_deserialize;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrencyUnit {
+    Dollars,
+    Cents,
+}
+
+impl CurrencyUnit {
+    pub fn convert(&self, value: &Currency, target: &CurrencyUnit) -> Currency {
+        use CurrencyUnit as C;
+        match (self, target) {
+            (C::Dollars, C::Dollars) => *value,
+            (C::Dollars, C::Cents) => *value * 100.0,
+            (C::Cents, C::Dollars) => *value / 100.0,
+            (C::Cents, C::Cents) => *value,
+        }
+    }
+}
+
+impl std::fmt::Display for CurrencyUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = serde_json::to_string(self)
+            .map_err(|_| std::fmt::Error)?
+            .replace('\"', "");
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for CurrencyUnit {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        string_deserialize(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Currency;
+    use super::CurrencyUnit as C;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            C::Dollars.convert(&Currency::new(1.5), &C::Cents),
+            Currency::new(150.0)
+        );
+        assert_eq!(
+            C::Cents.convert(&Currency::new(150.0), &C::Dollars),
+            Currency::new(1.5)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 mod as_f64;
 pub mod builders;
 mod cost;
+mod currency;
+mod currency_unit;
 mod distance;
 mod distance_unit;
 mod energy;
@@ -21,6 +23,8 @@ mod weight_unit;
 pub use as_f64::AsF64;
 pub use builders::{BASE_DISTANCE_UNIT, BASE_SPEED_UNIT, BASE_TIME_UNIT};
 pub use cost::{Cost, ReverseCost};
+pub use currency::Currency;
+pub use currency_unit::CurrencyUnit;
 pub use distance::Distance;
 pub use distance_unit::DistanceUnit;
 pub use energy::Energy;
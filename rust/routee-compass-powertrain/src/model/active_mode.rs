@@ -0,0 +1,264 @@
+use routee_compass_core::model::traversal::TraversalModelError;
+use serde::{Deserialize, Serialize};
+
+const GRAVITY: f64 = 9.81;
+const AIR_DENSITY: f64 = 1.225;
+const JOULES_PER_KILOCALORIE: f64 = 4184.0;
+/// speed of tobler's hiking function on flat ground, in kilometers per hour
+const TOBLER_FLAT_SPEED_KPH: f64 = 5.0368;
+/// upper bound when solving for cycling speed, in meters per second
+const MAX_SOLVER_SPEED: f64 = 50.0;
+
+/// a walking or cycling traveler. speeds respond to the grade of each edge, and the
+/// parameters may be tuned per query.
+///
+/// ```toml
+/// [traversal.active_mode]
+/// mode = "bike"
+/// rider_power_watts = 150.0
+/// max_speed_kph = 35.0
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ActiveMode {
+    /// walking speed follows tobler's hiking function, scaled to the walking speed on
+    /// flat ground. metabolic energy follows the ACSM walking equation.
+    Walk {
+        #[serde(default = "ActiveMode::default_flat_speed_kph")]
+        flat_speed_kph: f64,
+        #[serde(default = "ActiveMode::default_body_mass_kg")]
+        body_mass_kg: f64,
+    },
+    /// cycling speed is the steady-state speed where rider power balances gravity,
+    /// rolling resistance and aerodynamic drag. speeds are capped at a comfortable
+    /// descending speed, and below the minimum speed the rider walks the bike.
+    Bike {
+        #[serde(default = "ActiveMode::default_rider_power_watts")]
+        rider_power_watts: f64,
+        #[serde(default = "ActiveMode::default_total_mass_kg")]
+        total_mass_kg: f64,
+        #[serde(default = "ActiveMode::default_rolling_resistance")]
+        rolling_resistance: f64,
+        #[serde(default = "ActiveMode::default_drag_area_m2")]
+        drag_area_m2: f64,
+        #[serde(default = "ActiveMode::default_max_speed_kph")]
+        max_speed_kph: f64,
+        #[serde(default = "ActiveMode::default_min_speed_kph")]
+        min_speed_kph: f64,
+        /// fraction of metabolic energy delivered to the pedals
+        #[serde(default = "ActiveMode::default_efficiency")]
+        efficiency: f64,
+    },
+}
+
+impl ActiveMode {
+    fn default_flat_speed_kph() -> f64 {
+        5.0
+    }
+    fn default_body_mass_kg() -> f64 {
+        75.0
+    }
+    fn default_rider_power_watts() -> f64 {
+        120.0
+    }
+    fn default_total_mass_kg() -> f64 {
+        90.0
+    }
+    fn default_rolling_resistance() -> f64 {
+        0.005
+    }
+    fn default_drag_area_m2() -> f64 {
+        0.5
+    }
+    fn default_max_speed_kph() -> f64 {
+        40.0
+    }
+    fn default_min_speed_kph() -> f64 {
+        4.0
+    }
+    fn default_efficiency() -> f64 {
+        0.24
+    }
+
+    /// checks that all parameters are positive and that speed limits are ordered
+    pub fn validate(&self) -> Result<(), TraversalModelError> {
+        let (values, limits) = match self {
+            ActiveMode::Walk {
+                flat_speed_kph,
+                body_mass_kg,
+            } => (vec![*flat_speed_kph, *body_mass_kg], None),
+            ActiveMode::Bike {
+                rider_power_watts,
+                total_mass_kg,
+                rolling_resistance,
+                drag_area_m2,
+                max_speed_kph,
+                min_speed_kph,
+                efficiency,
+            } => (
+                vec![
+                    *rider_power_watts,
+                    *total_mass_kg,
+                    *rolling_resistance,
+                    *drag_area_m2,
+                    *max_speed_kph,
+                    *min_speed_kph,
+                    *efficiency,
+                ],
+                Some((*min_speed_kph, *max_speed_kph)),
+            ),
+        };
+        if values.iter().any(|v| v.is_nan() || *v <= 0.0) {
+            return Err(TraversalModelError::BuildError(format!(
+                "active mode parameters must be positive, found {:?}",
+                self
+            )));
+        }
+        if let Some((min, max)) = limits {
+            if min > max {
+                return Err(TraversalModelError::BuildError(format!(
+                    "active mode min_speed_kph {} exceeds max_speed_kph {}",
+                    min, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// travel speed on an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `grade` - edge grade as a decimal
+    ///
+    /// # Returns
+    ///
+    /// speed in meters per second
+    pub fn speed(&self, grade: f64) -> f64 {
+        match self {
+            ActiveMode::Walk { flat_speed_kph, .. } => {
+                let kph = 6.0 * (-3.5 * (grade + 0.05).abs()).exp();
+                kph * flat_speed_kph / TOBLER_FLAT_SPEED_KPH / 3.6
+            }
+            ActiveMode::Bike {
+                rider_power_watts,
+                max_speed_kph,
+                min_speed_kph,
+                ..
+            } => {
+                // bisection on the power balance, which has one positive root
+                let (mut low, mut high) = (0.0, MAX_SOLVER_SPEED);
+                for _ in 0..60 {
+                    let mid = (low + high) / 2.0;
+                    if self.bike_power(grade, mid) < *rider_power_watts {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                low.clamp(min_speed_kph / 3.6, max_speed_kph / 3.6)
+            }
+        }
+    }
+
+    /// the fastest speed at any grade, in meters per second
+    pub fn max_speed(&self) -> f64 {
+        match self {
+            ActiveMode::Walk { flat_speed_kph, .. } => {
+                6.0 * flat_speed_kph / TOBLER_FLAT_SPEED_KPH / 3.6
+            }
+            ActiveMode::Bike { max_speed_kph, .. } => max_speed_kph / 3.6,
+        }
+    }
+
+    /// metabolic energy spent traveling on an edge
+    ///
+    /// # Arguments
+    ///
+    /// * `grade`   - edge grade as a decimal
+    /// * `speed`   - travel speed in meters per second
+    /// * `seconds` - travel time in seconds
+    ///
+    /// # Returns
+    ///
+    /// metabolic energy in kilocalories
+    pub fn metabolic_energy(&self, grade: f64, speed: f64, seconds: f64) -> f64 {
+        match self {
+            ActiveMode::Walk { body_mass_kg, .. } => {
+                // ACSM walking equation, oxygen uptake in ml/kg/min, ~5 kcal per liter
+                let meters_per_minute = speed * 60.0;
+                let vo2 = 3.5 + 0.1 * meters_per_minute + 1.8 * meters_per_minute * grade.max(0.0);
+                vo2 * body_mass_kg / 1000.0 * 5.0 * seconds / 60.0
+            }
+            ActiveMode::Bike {
+                rider_power_watts,
+                efficiency,
+                ..
+            } => {
+                let power = self.bike_power(grade, speed).clamp(0.0, *rider_power_watts);
+                power * seconds / efficiency / JOULES_PER_KILOCALORIE
+            }
+        }
+    }
+
+    /// power required to hold a cycling speed on a grade, in watts. negative on
+    /// descents where gravity alone exceeds the resistance.
+    fn bike_power(&self, grade: f64, speed: f64) -> f64 {
+        match self {
+            ActiveMode::Walk { .. } => 0.0,
+            ActiveMode::Bike {
+                total_mass_kg,
+                rolling_resistance,
+                drag_area_m2,
+                ..
+            } => {
+                let angle = grade.atan();
+                let gravity = total_mass_kg * GRAVITY * angle.sin();
+                let rolling = total_mass_kg * GRAVITY * rolling_resistance * angle.cos();
+                let drag = 0.5 * AIR_DENSITY * drag_area_m2 * speed * speed;
+                speed * (gravity + rolling + drag)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bike() -> ActiveMode {
+        serde_json::from_value(serde_json::json!({"mode": "bike"})).unwrap()
+    }
+
+    #[test]
+    fn test_walking_speed_peaks_on_gentle_descent() {
+        let walk: ActiveMode = serde_json::from_value(serde_json::json!({"mode": "walk"})).unwrap();
+        assert!((walk.speed(0.0) * 3.6 - 5.0).abs() < 0.01);
+        assert!(walk.speed(-0.05) > walk.speed(0.0));
+        assert!(walk.speed(0.1) < walk.speed(0.0));
+        assert!((walk.speed(-0.05) - walk.max_speed()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bike_speed_from_power() {
+        let bike = bike();
+        let flat = bike.speed(0.0);
+        // the rider's power is balanced by the resistance at the solved speed
+        assert!((bike.bike_power(0.0, flat) - 120.0).abs() < 1e-3);
+        assert!(bike.speed(0.05) < flat);
+        // steep descents are capped at the comfortable speed, steep climbs are walked
+        assert_eq!(bike.speed(-0.15), 40.0 / 3.6);
+        assert_eq!(bike.speed(0.3), 4.0 / 3.6);
+    }
+
+    #[test]
+    fn test_metabolic_energy() {
+        let bike = bike();
+        let flat = bike.speed(0.0);
+        // one hour at 120 watts and 24% efficiency
+        let kcal = bike.metabolic_energy(0.0, flat, 3600.0);
+        assert!((kcal - 120.0 * 3600.0 / 0.24 / 4184.0).abs() < 0.1);
+        // coasting downhill costs nothing
+        assert_eq!(bike.metabolic_energy(-0.15, 40.0 / 3.6, 60.0), 0.0);
+    }
+}
@@ -0,0 +1,75 @@
+use super::active_mode::ActiveMode;
+use super::active_mode_traversal_model::ActiveModeTraversalModel;
+use routee_compass_core::model::traversal::{
+    TraversalModel, TraversalModelError, TraversalModelService,
+};
+use routee_compass_core::model::unit::*;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct ActiveModeService {
+    pub mode: ActiveMode,
+    pub grade_table: Arc<Option<Box<[Grade]>>>,
+    pub grade_table_grade_unit: GradeUnit,
+    pub time_unit: TimeUnit,
+    pub distance_unit: DistanceUnit,
+    /// if true, the model accumulates a `metabolic_energy` feature in kilocalories
+    pub metabolic_energy: bool,
+}
+
+impl ActiveModeService {
+    pub const QUERY_KEY: &'static str = "active_mode";
+
+    /// applies the query `active_mode` parameters, such as rider power or comfort limits,
+    /// over the configured active mode
+    fn query_mode(&self, query: &serde_json::Value) -> Result<ActiveMode, TraversalModelError> {
+        let overrides = match query.get(Self::QUERY_KEY) {
+            None => return Ok(self.mode.clone()),
+            Some(serde_json::Value::Object(overrides)) => overrides,
+            Some(other) => {
+                return Err(TraversalModelError::BuildError(format!(
+                    "expected query {} to be an object, found {}",
+                    Self::QUERY_KEY,
+                    other
+                )))
+            }
+        };
+        let mut mode = serde_json::to_value(&self.mode).map_err(|e| {
+            TraversalModelError::BuildError(format!("failure encoding active mode: {}", e))
+        })?;
+        if let Some(params) = mode.as_object_mut() {
+            for (key, value) in overrides.iter() {
+                params.insert(key.clone(), value.clone());
+            }
+        }
+        let mode: ActiveMode = serde_json::from_value(mode).map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "unable to read query {}: {}",
+                Self::QUERY_KEY,
+                e
+            ))
+        })?;
+        mode.validate()?;
+        Ok(mode)
+    }
+}
+
+impl TraversalModelService for ActiveModeService {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let mode = self.query_mode(parameters)?;
+        let arc_self = Arc::new(self.clone());
+        Ok(Arc::new(ActiveModeTraversalModel::new(arc_self, mode)))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        let arc_self = Arc::new(self.clone());
+        ActiveModeTraversalModel::new(arc_self, self.mode.clone())
+            .state_features()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
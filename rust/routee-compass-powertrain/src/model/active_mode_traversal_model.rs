@@ -0,0 +1,182 @@
+use super::active_mode::ActiveMode;
+use super::active_mode_service::ActiveModeService;
+use super::energy_model_ops::get_grade;
+use routee_compass_core::model::network::{Edge, Vertex};
+use routee_compass_core::model::state::StateModel;
+use routee_compass_core::model::state::StateVariable;
+use routee_compass_core::model::state::{CustomFeatureFormat, StateFeature};
+use routee_compass_core::model::traversal::TraversalModel;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::unit::*;
+use routee_compass_core::util::geo::haversine;
+use std::sync::Arc;
+
+/// traverses edges by walking or cycling at a speed that depends on the edge grade
+pub struct ActiveModeTraversalModel {
+    pub service: Arc<ActiveModeService>,
+    pub mode: ActiveMode,
+}
+
+impl ActiveModeTraversalModel {
+    pub fn new(service: Arc<ActiveModeService>, mode: ActiveMode) -> ActiveModeTraversalModel {
+        ActiveModeTraversalModel { service, mode }
+    }
+    const TIME: &'static str = "time";
+    const DISTANCE: &'static str = "distance";
+    const METABOLIC_ENERGY: &'static str = "metabolic_energy";
+
+    fn add_time_and_distance(
+        &self,
+        seconds: f64,
+        distance: Distance,
+        state: &mut [StateVariable],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let time = TimeUnit::Seconds.convert(&Time::new(seconds), &self.service.time_unit);
+        let distance = BASE_DISTANCE_UNIT.convert(&distance, &self.service.distance_unit);
+        state_model.add_time(state, &Self::TIME.into(), &time, &self.service.time_unit)?;
+        state_model.add_distance(
+            state,
+            &Self::DISTANCE.into(),
+            &distance,
+            &self.service.distance_unit,
+        )?;
+        Ok(())
+    }
+}
+
+impl TraversalModel for ActiveModeTraversalModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        let mut features = vec![
+            (
+                String::from(Self::TIME),
+                StateFeature::Time {
+                    time_unit: self.service.time_unit,
+                    initial: Time::ZERO,
+                },
+            ),
+            (
+                String::from(Self::DISTANCE),
+                StateFeature::Distance {
+                    distance_unit: self.service.distance_unit,
+                    initial: Distance::ZERO,
+                },
+            ),
+        ];
+        if self.service.metabolic_energy {
+            features.push((
+                String::from(Self::METABOLIC_ENERGY),
+                StateFeature::Custom {
+                    r#type: String::from("metabolic_energy"),
+                    unit: String::from("kilocalories"),
+                    format: CustomFeatureFormat::default(),
+                },
+            ));
+        }
+        features
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let grade = get_grade(&self.service.grade_table, edge.edge_id)?;
+        let grade = self
+            .service
+            .grade_table_grade_unit
+            .convert(&grade, &GradeUnit::Decimal)
+            .as_f64();
+        let speed = self.mode.speed(grade);
+        let seconds = edge.distance.as_f64() / speed;
+        self.add_time_and_distance(seconds, edge.distance, state, state_model)?;
+
+        if self.service.metabolic_energy {
+            let name = String::from(Self::METABOLIC_ENERGY);
+            let kcal = self.mode.metabolic_energy(grade, speed, seconds);
+            let prev = state_model.get_custom_f64(state, &name)?;
+            state_model.set_custom_f64(state, &name, &(prev + kcal))?;
+        }
+        Ok(())
+    }
+
+    /// estimates time at the fastest speed of the mode and no metabolic energy,
+    /// which never overestimates
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (src, dst) = od;
+        let distance =
+            haversine::coord_distance(&src.coordinate, &dst.coordinate, BASE_DISTANCE_UNIT)
+                .map_err(|e| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "could not compute haversine distance between {} and {}: {}",
+                        src, dst, e
+                    ))
+                })?;
+        if distance == Distance::ZERO {
+            return Ok(());
+        }
+        let seconds = distance.as_f64() / self.mode.max_speed();
+        self.add_time_and_distance(seconds, distance, state, state_model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::coord;
+    use routee_compass_core::model::network::{EdgeId, VertexId};
+    use routee_compass_core::model::traversal::TraversalModelService;
+    use routee_compass_core::util::geo::coord::InternalCoord;
+
+    #[test]
+    fn test_uphill_walk_is_slower() {
+        let mode: ActiveMode = serde_json::from_value(serde_json::json!({"mode": "walk"})).unwrap();
+        let service = ActiveModeService {
+            mode,
+            grade_table: Arc::new(Some(Box::new([Grade::new(0.0), Grade::new(10.0)]))),
+            grade_table_grade_unit: GradeUnit::Percent,
+            time_unit: TimeUnit::Seconds,
+            distance_unit: DistanceUnit::Meters,
+            metabolic_energy: true,
+        };
+        let query = serde_json::json!({"active_mode": {"flat_speed_kph": 3.6}});
+        let model = service.build(&query).unwrap();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let edge = |edge_id: usize| Edge {
+            edge_id: EdgeId(edge_id),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(0),
+            distance: Distance::new(100.0),
+        };
+        let traverse = |edge: Edge| {
+            let mut state = state_model.initial_state().unwrap();
+            model
+                .traverse_edge((&v, &edge, &v), &mut state, &state_model)
+                .unwrap();
+            let time = state_model
+                .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+                .unwrap();
+            let kcal = state_model
+                .get_custom_f64(&state, &"metabolic_energy".into())
+                .unwrap();
+            (time.as_f64(), kcal)
+        };
+        // the query walking speed of 1 m/s covers the flat edge in 100 seconds
+        let (flat_time, flat_kcal) = traverse(edge(0));
+        let (uphill_time, uphill_kcal) = traverse(edge(1));
+        assert!((flat_time - 100.0).abs() < 0.1);
+        assert!(uphill_time > flat_time);
+        assert!(uphill_kcal > flat_kcal);
+    }
+}
@@ -0,0 +1,282 @@
+use kdam::Bar;
+use routee_compass_core::model::network::Edge;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::unit::{AsF64, Distance, DistanceUnit, Grade, BASE_DISTANCE_UNIT};
+use routee_compass_core::util::fs::read_utils;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// configuration to derive edge grades from vertex elevations instead of a grade table.
+/// elevations are read either from a file with one elevation per vertex, in vertex id
+/// order, or from a column of the vertex list. edges may optionally list intermediate
+/// elevation samples, evenly spaced between their vertices, which refine the elevation
+/// gain of long edges.
+///
+/// ```toml
+/// [traversal.elevation]
+/// edge_input_file = "edges-compass.csv.gz"
+/// vertex_input_file = "vertices-compass.csv.gz"
+/// elevation_column = "elevation"
+/// elevation_unit = "meters"
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ElevationConfig {
+    /// edge list of the graph, which provides the vertices and distance of each edge
+    pub edge_input_file: String,
+    /// file with one elevation per line, in vertex id order
+    #[serde(default)]
+    pub vertex_elevation_input_file: Option<String>,
+    /// vertex list of the graph with an elevation column
+    #[serde(default)]
+    pub vertex_input_file: Option<String>,
+    /// name of the elevation column of the vertex list
+    #[serde(default = "ElevationConfig::default_elevation_column")]
+    pub elevation_column: String,
+    /// file with one row per edge of comma-delimited intermediate elevations, where an
+    /// empty row has no samples
+    #[serde(default)]
+    pub edge_elevation_samples_input_file: Option<String>,
+    /// unit of all elevations, defaults to meters
+    #[serde(default)]
+    pub elevation_unit: Option<DistanceUnit>,
+}
+
+/// grade and elevation gain of each edge, derived from elevations
+pub struct EdgeElevation {
+    /// net elevation change over edge distance, as a decimal grade
+    pub grades: Box<[Grade]>,
+    /// sum of the elevation increases along each edge, in the base distance unit
+    pub elevation_gain: Box<[Distance]>,
+}
+
+impl ElevationConfig {
+    fn default_elevation_column() -> String {
+        String::from("elevation")
+    }
+
+    /// reads the elevation files and derives the grade and elevation gain of each edge
+    pub fn build(&self) -> Result<EdgeElevation, TraversalModelError> {
+        let elevation_unit = self.elevation_unit.unwrap_or(BASE_DISTANCE_UNIT);
+        let vertex_elevations = match (&self.vertex_elevation_input_file, &self.vertex_input_file)
+        {
+            (Some(file), None) => read_utils::read_raw_file(
+                file,
+                decode_elevation,
+                Some(Bar::builder().desc("vertex elevations")),
+                None,
+            )
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failure reading vertex elevations {} due to {}",
+                    file, e
+                ))
+            })?,
+            (None, Some(file)) => {
+                read_elevation_column(file, &self.elevation_column)?
+            }
+            _ => {
+                return Err(TraversalModelError::BuildError(String::from(
+                    "elevation requires exactly one of vertex_elevation_input_file or vertex_input_file",
+                )))
+            }
+        };
+        let edges: Box<[Edge]> = read_utils::from_csv(
+            &self.edge_input_file,
+            true,
+            Some(Bar::builder().desc("edge elevations")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failure reading edges {} due to {}",
+                self.edge_input_file, e
+            ))
+        })?;
+        let samples = match &self.edge_elevation_samples_input_file {
+            None => None,
+            Some(file) => Some(
+                read_utils::read_raw_file(file, decode_samples, None, None).map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "failure reading edge elevation samples {} due to {}",
+                        file, e
+                    ))
+                })?,
+            ),
+        };
+        let to_base = |e: f64| {
+            elevation_unit
+                .convert(&Distance::new(e), &BASE_DISTANCE_UNIT)
+                .as_f64()
+        };
+        let vertex_elevations = vertex_elevations
+            .iter()
+            .map(|e| to_base(*e))
+            .collect::<Vec<_>>();
+        let samples = samples.map(|rows| {
+            rows.iter()
+                .map(|row| row.iter().map(|e| to_base(*e)).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        });
+        edge_elevation(&edges, &vertex_elevations, samples.as_deref())
+    }
+}
+
+/// derives the grade and elevation gain of each edge. grade is the net elevation change
+/// over the edge distance. elevation gain sums the increases between the source vertex,
+/// each intermediate sample and the destination vertex.
+///
+/// # Arguments
+///
+/// * `edges`             - edges of the graph, in edge id order
+/// * `vertex_elevations` - elevation of each vertex in the base distance unit
+/// * `samples`           - optional intermediate elevations of each edge in the base distance unit
+///
+/// # Returns
+///
+/// the grade and elevation gain of each edge
+pub fn edge_elevation(
+    edges: &[Edge],
+    vertex_elevations: &[f64],
+    samples: Option<&[Vec<f64>]>,
+) -> Result<EdgeElevation, TraversalModelError> {
+    if let Some(samples) = samples {
+        if samples.len() != edges.len() {
+            return Err(TraversalModelError::BuildError(format!(
+                "edge elevation samples has {} rows but there are {} edges",
+                samples.len(),
+                edges.len()
+            )));
+        }
+    }
+    let mut grades = Vec::with_capacity(edges.len());
+    let mut elevation_gain = Vec::with_capacity(edges.len());
+    for (index, edge) in edges.iter().enumerate() {
+        let elevation = |vertex_id: usize| {
+            vertex_elevations.get(vertex_id).cloned().ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "missing elevation for vertex {} of edge {}",
+                    vertex_id, edge.edge_id
+                ))
+            })
+        };
+        let src = elevation(edge.src_vertex_id.0)?;
+        let dst = elevation(edge.dst_vertex_id.0)?;
+        let distance = edge.distance.as_f64();
+        let grade = if distance > 0.0 {
+            (dst - src) / distance
+        } else {
+            0.0
+        };
+        let mut profile = vec![src];
+        if let Some(samples) = samples {
+            profile.extend(samples[index].iter());
+        }
+        profile.push(dst);
+        let gain: f64 = profile
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).max(0.0))
+            .sum();
+        grades.push(Grade::new(grade));
+        elevation_gain.push(Distance::new(gain));
+    }
+    Ok(EdgeElevation {
+        grades: grades.into_boxed_slice(),
+        elevation_gain: elevation_gain.into_boxed_slice(),
+    })
+}
+
+fn read_elevation_column(file: &str, column: &str) -> Result<Box<[f64]>, TraversalModelError> {
+    let rows: Box<[HashMap<String, String>]> = read_utils::from_csv(
+        &file,
+        true,
+        Some(Bar::builder().desc("vertex elevations")),
+        None,
+    )
+    .map_err(|e| {
+        TraversalModelError::BuildError(format!(
+            "failure reading vertex elevations {} due to {}",
+            file, e
+        ))
+    })?;
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let value = row.get(column).ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "vertex file {} missing column {}",
+                    file, column
+                ))
+            })?;
+            value.parse::<f64>().map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "invalid elevation {} in row {} of {}: {}",
+                    value, index, file, e
+                ))
+            })
+        })
+        .collect()
+}
+
+fn decode_elevation(idx: usize, row: String) -> Result<f64, std::io::Error> {
+    row.trim().parse::<f64>().map_err(|e| {
+        let msg = format!("failure decoding elevation row {} due to: {}", idx, e);
+        std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+    })
+}
+
+fn decode_samples(idx: usize, row: String) -> Result<Vec<f64>, std::io::Error> {
+    row.split(',')
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value.parse::<f64>().map_err(|e| {
+                let msg = format!(
+                    "failure decoding elevation samples row {} due to: {}",
+                    idx, e
+                );
+                std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::network::{EdgeId, VertexId};
+
+    fn mock_edges() -> Vec<Edge> {
+        vec![
+            Edge {
+                edge_id: EdgeId(0),
+                src_vertex_id: VertexId(0),
+                dst_vertex_id: VertexId(1),
+                distance: Distance::new(100.0),
+            },
+            Edge {
+                edge_id: EdgeId(1),
+                src_vertex_id: VertexId(1),
+                dst_vertex_id: VertexId(0),
+                distance: Distance::new(100.0),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_grade_from_vertex_elevations() {
+        let result = edge_elevation(&mock_edges(), &[10.0, 15.0], None).unwrap();
+        assert_eq!(result.grades[0], Grade::new(0.05));
+        assert_eq!(result.grades[1], Grade::new(-0.05));
+        assert_eq!(result.elevation_gain[0], Distance::new(5.0));
+        assert_eq!(result.elevation_gain[1], Distance::ZERO);
+    }
+
+    #[test]
+    fn test_elevation_gain_from_samples() {
+        // the downhill edge climbs a 3 meter bump along the way
+        let samples = vec![vec![], vec![18.0, 12.0]];
+        let result = edge_elevation(&mock_edges(), &[10.0, 15.0], Some(&samples)).unwrap();
+        assert_eq!(result.grades[1], Grade::new(-0.05));
+        assert_eq!(result.elevation_gain[1], Distance::new(3.0));
+    }
+}
@@ -0,0 +1,207 @@
+use kdam::Bar;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::unit::EnergyUnit;
+use routee_compass_core::util::fs::read_utils;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// configuration of the emission factors used to convert the energy consumed on each
+/// edge into emissions, in grams. liquid fuel factors combine tailpipe and upstream
+/// (well-to-tank) emissions. electricity emits only upstream at the grid, where the
+/// carbon intensity may be read from a file that varies by region and hour of day.
+///
+/// ```toml
+/// [traversal.emission_factors]
+/// grid_intensity_input_file = "grid-intensity.csv"
+/// [traversal.emission_factors.liquid]
+/// energy_unit = "gallons_gasoline"
+/// co2e = 8887.0
+/// upstream_co2e = 2300.0
+/// nox = 0.9
+/// [traversal.emission_factors.electric]
+/// energy_unit = "kilowatt_hours"
+/// co2e = 390.0
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EmissionFactorsConfig {
+    #[serde(default)]
+    pub liquid: Option<EmissionFactor>,
+    #[serde(default)]
+    pub electric: Option<EmissionFactor>,
+    /// file with columns `region,hour,co2e`, giving grams CO2e per kilowatt hour of
+    /// electricity. an empty hour applies to the whole day.
+    #[serde(default)]
+    pub grid_intensity_input_file: Option<String>,
+}
+
+/// grams of each pollutant emitted per unit of energy
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EmissionFactor {
+    pub energy_unit: EnergyUnit,
+    pub co2e: f64,
+    #[serde(default)]
+    pub upstream_co2e: f64,
+    #[serde(default)]
+    pub nox: Option<f64>,
+    #[serde(default)]
+    pub pm: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GridIntensityRow {
+    region: String,
+    #[serde(default)]
+    hour: Option<u32>,
+    co2e: f64,
+}
+
+/// grid carbon intensity of a region, in grams CO2e per kilowatt hour
+#[derive(Clone, Debug, Default)]
+pub struct GridIntensity {
+    pub daily: Option<f64>,
+    pub hourly: HashMap<u32, f64>,
+}
+
+impl GridIntensity {
+    /// the intensity during an hour of day, if known, falling back to the daily intensity
+    pub fn get(&self, hour: Option<u32>) -> Option<f64> {
+        hour.and_then(|h| self.hourly.get(&h).cloned())
+            .or(self.daily)
+    }
+
+    /// the lowest intensity at any time of day
+    pub fn min(&self) -> Option<f64> {
+        self.hourly
+            .values()
+            .cloned()
+            .chain(self.daily)
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+/// emission factors ready for use by the emissions traversal model
+#[derive(Clone, Debug)]
+pub struct EmissionFactors {
+    pub liquid: Option<EmissionFactor>,
+    pub electric: Option<EmissionFactor>,
+    pub grid_intensity: HashMap<String, GridIntensity>,
+}
+
+impl EmissionFactors {
+    /// true if any factor lists NOx emissions
+    pub fn has_nox(&self) -> bool {
+        self.factors().any(|f| f.nox.is_some())
+    }
+
+    /// true if any factor lists particulate matter emissions
+    pub fn has_pm(&self) -> bool {
+        self.factors().any(|f| f.pm.is_some())
+    }
+
+    fn factors(&self) -> impl Iterator<Item = &EmissionFactor> {
+        self.liquid.iter().chain(self.electric.iter())
+    }
+}
+
+impl EmissionFactorsConfig {
+    /// validates the factors and reads the grid intensity file, if any
+    pub fn build(&self) -> Result<EmissionFactors, TraversalModelError> {
+        if self.liquid.is_none() && self.electric.is_none() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "emission factors require a liquid or electric factor",
+            )));
+        }
+        for factor in self.liquid.iter().chain(self.electric.iter()) {
+            let values = [
+                Some(factor.co2e),
+                Some(factor.upstream_co2e),
+                factor.nox,
+                factor.pm,
+            ];
+            if values.iter().flatten().any(|v| v.is_nan() || *v < 0.0) {
+                return Err(TraversalModelError::BuildError(format!(
+                    "emission factors for {} must be non-negative",
+                    factor.energy_unit
+                )));
+            }
+        }
+        let grid_intensity = match &self.grid_intensity_input_file {
+            None => HashMap::new(),
+            Some(file) => {
+                if self.electric.is_none() {
+                    return Err(TraversalModelError::BuildError(String::from(
+                        "grid intensity requires an electric emission factor",
+                    )));
+                }
+                let rows: Box<[GridIntensityRow]> = read_utils::from_csv(
+                    &file,
+                    true,
+                    Some(Bar::builder().desc("grid intensity")),
+                    None,
+                )
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "failure reading grid intensity {} due to {}",
+                        file, e
+                    ))
+                })?;
+                grid_intensity_by_region(&rows)?
+            }
+        };
+        Ok(EmissionFactors {
+            liquid: self.liquid.clone(),
+            electric: self.electric.clone(),
+            grid_intensity,
+        })
+    }
+}
+
+fn grid_intensity_by_region(
+    rows: &[GridIntensityRow],
+) -> Result<HashMap<String, GridIntensity>, TraversalModelError> {
+    let mut result: HashMap<String, GridIntensity> = HashMap::new();
+    for row in rows.iter() {
+        if row.co2e.is_nan() || row.co2e < 0.0 {
+            return Err(TraversalModelError::BuildError(format!(
+                "grid intensity {} for region {} must be non-negative",
+                row.co2e, row.region
+            )));
+        }
+        let intensity = result.entry(row.region.clone()).or_default();
+        match row.hour {
+            None => intensity.daily = Some(row.co2e),
+            Some(hour) if hour < 24 => {
+                intensity.hourly.insert(hour, row.co2e);
+            }
+            Some(hour) => {
+                return Err(TraversalModelError::BuildError(format!(
+                    "grid intensity hour {} for region {} must be in [0, 23]",
+                    hour, row.region
+                )))
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_intensity_by_hour() {
+        let row = |hour: Option<u32>, co2e: f64| GridIntensityRow {
+            region: String::from("west"),
+            hour,
+            co2e,
+        };
+        let rows = vec![row(None, 300.0), row(Some(12), 150.0)];
+        let grid = grid_intensity_by_region(&rows).unwrap();
+        let west = grid.get("west").unwrap();
+        assert_eq!(west.get(Some(12)), Some(150.0));
+        assert_eq!(west.get(Some(20)), Some(300.0));
+        assert_eq!(west.get(None), Some(300.0));
+        assert_eq!(west.min(), Some(150.0));
+        assert!(grid_intensity_by_region(&[row(Some(24), 100.0)]).is_err());
+    }
+}
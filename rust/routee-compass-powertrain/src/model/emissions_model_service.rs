@@ -0,0 +1,71 @@
+use super::emission_factors::EmissionFactors;
+use super::emissions_traversal_model::EmissionsTraversalModel;
+use routee_compass_core::model::traversal::default::departure_seconds;
+use routee_compass_core::model::traversal::{
+    TraversalModel, TraversalModelError, TraversalModelService,
+};
+use std::sync::Arc;
+
+/// builds emissions models that wrap the energy model of each query
+pub struct EmissionsModelService {
+    pub energy_model_service: Arc<dyn TraversalModelService>,
+    pub emission_factors: Arc<EmissionFactors>,
+}
+
+impl TraversalModelService for EmissionsModelService {
+    /// builds the energy model of the query along with the optional `grid_region` and
+    /// `departure_time` used to look up the grid intensity of electricity
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let energy_model = self.energy_model_service.build(parameters)?;
+        let grid_region = match parameters.get("grid_region") {
+            None => None,
+            Some(value) => {
+                let region = value.as_str().ok_or_else(|| {
+                    TraversalModelError::BuildError(String::from(
+                        "expected 'grid_region' value to be a string",
+                    ))
+                })?;
+                let intensity = self
+                    .emission_factors
+                    .grid_intensity
+                    .get(region)
+                    .ok_or_else(|| {
+                        TraversalModelError::BuildError(format!("unknown grid_region {}", region))
+                    })?;
+                Some(intensity.clone())
+            }
+        };
+        let departure = match parameters.get("departure_time") {
+            None => None,
+            Some(value) => {
+                let departure_time = value.as_str().ok_or_else(|| {
+                    TraversalModelError::BuildError(String::from(
+                        "expected 'departure_time' value to be a string",
+                    ))
+                })?;
+                Some(departure_seconds(departure_time)?)
+            }
+        };
+        Ok(Arc::new(EmissionsTraversalModel::new(
+            energy_model,
+            self.emission_factors.clone(),
+            grid_region,
+            departure,
+        )))
+    }
+
+    fn state_feature_names(&self) -> Vec<String> {
+        let mut names = self.energy_model_service.state_feature_names();
+        names.push(String::from(EmissionsTraversalModel::CO2E));
+        if self.emission_factors.has_nox() {
+            names.push(String::from(EmissionsTraversalModel::NOX));
+        }
+        if self.emission_factors.has_pm() {
+            names.push(String::from(EmissionsTraversalModel::PM));
+        }
+        names
+    }
+}
@@ -0,0 +1,317 @@
+use super::emission_factors::{EmissionFactors, GridIntensity};
+use routee_compass_core::model::network::{Edge, Vertex};
+use routee_compass_core::model::state::StateModel;
+use routee_compass_core::model::state::StateVariable;
+use routee_compass_core::model::state::{CustomFeatureFormat, StateFeature};
+use routee_compass_core::model::traversal::TraversalModel;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::unit::{AsF64, EnergyUnit, TimeUnit};
+use std::sync::Arc;
+
+/// wraps an energy traversal model, converting the liquid and electric energy consumed
+/// on each edge into emissions. CO2e is always tracked, while NOx and particulate matter
+/// are tracked when an emission factor lists them. all emissions are in grams.
+///
+/// electricity uses the grid intensity of the query region when one is given. with a
+/// departure time, the intensity is taken at the hour of day the edge is entered.
+pub struct EmissionsTraversalModel {
+    energy_model: Arc<dyn TraversalModel>,
+    emission_factors: Arc<EmissionFactors>,
+    grid_intensity: Option<GridIntensity>,
+    departure_seconds: Option<f64>,
+}
+
+/// grams of each pollutant
+#[derive(Default)]
+struct Emissions {
+    co2e: f64,
+    nox: f64,
+    pm: f64,
+}
+
+impl EmissionsTraversalModel {
+    pub fn new(
+        energy_model: Arc<dyn TraversalModel>,
+        emission_factors: Arc<EmissionFactors>,
+        grid_intensity: Option<GridIntensity>,
+        departure_seconds: Option<f64>,
+    ) -> EmissionsTraversalModel {
+        EmissionsTraversalModel {
+            energy_model,
+            emission_factors,
+            grid_intensity,
+            departure_seconds,
+        }
+    }
+
+    pub(crate) const CO2E: &'static str = "co2e";
+    pub(crate) const NOX: &'static str = "nox";
+    pub(crate) const PM: &'static str = "pm";
+    const ENERGY_LIQUID: &'static str = "energy_liquid";
+    const ENERGY_ELECTRIC: &'static str = "energy_electric";
+    const TIME: &'static str = "time";
+
+    /// computes the emissions of the energy consumed between two states
+    ///
+    /// # Arguments
+    ///
+    /// * `prev`           - state before the energy model was applied
+    /// * `state`          - state after the energy model was applied
+    /// * `state_model`    - state model of the search
+    /// * `grid_intensity` - grams CO2e per kilowatt hour of electricity, if known
+    fn emissions(
+        &self,
+        prev: &[StateVariable],
+        state: &[StateVariable],
+        state_model: &StateModel,
+        grid_intensity: Option<f64>,
+    ) -> Result<Emissions, TraversalModelError> {
+        let mut emissions = Emissions::default();
+        let energy_delta = |name: &str, unit: &EnergyUnit| -> Result<f64, TraversalModelError> {
+            let name = String::from(name);
+            let before = state_model.get_energy(prev, &name, unit)?;
+            let after = state_model.get_energy(state, &name, unit)?;
+            Ok((after - before).as_f64())
+        };
+        let liquid = self
+            .emission_factors
+            .liquid
+            .as_ref()
+            .filter(|_| state_model.contains_key(&Self::ENERGY_LIQUID.into()));
+        if let Some(factor) = liquid {
+            let delta = energy_delta(Self::ENERGY_LIQUID, &factor.energy_unit)?;
+            emissions.co2e += delta * (factor.co2e + factor.upstream_co2e);
+            emissions.nox += delta * factor.nox.unwrap_or_default();
+            emissions.pm += delta * factor.pm.unwrap_or_default();
+        }
+        let electric = self
+            .emission_factors
+            .electric
+            .as_ref()
+            .filter(|_| state_model.contains_key(&Self::ENERGY_ELECTRIC.into()));
+        if let Some(factor) = electric {
+            let delta = energy_delta(Self::ENERGY_ELECTRIC, &factor.energy_unit)?;
+            emissions.co2e += match grid_intensity {
+                Some(intensity) => {
+                    energy_delta(Self::ENERGY_ELECTRIC, &EnergyUnit::KilowattHours)? * intensity
+                }
+                None => delta * (factor.co2e + factor.upstream_co2e),
+            };
+            emissions.nox += delta * factor.nox.unwrap_or_default();
+            emissions.pm += delta * factor.pm.unwrap_or_default();
+        }
+        Ok(emissions)
+    }
+
+    fn add_emissions(
+        &self,
+        emissions: Emissions,
+        state: &mut [StateVariable],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let mut features = vec![(Self::CO2E, emissions.co2e)];
+        if self.emission_factors.has_nox() {
+            features.push((Self::NOX, emissions.nox));
+        }
+        if self.emission_factors.has_pm() {
+            features.push((Self::PM, emissions.pm));
+        }
+        for (name, value) in features.into_iter() {
+            let name = String::from(name);
+            let prev = state_model.get_custom_f64(state, &name)?;
+            state_model.set_custom_f64(state, &name, &(prev + value))?;
+        }
+        Ok(())
+    }
+
+    fn emissions_feature() -> StateFeature {
+        StateFeature::Custom {
+            r#type: String::from("emissions"),
+            unit: String::from("grams"),
+            format: CustomFeatureFormat::default(),
+        }
+    }
+}
+
+impl TraversalModel for EmissionsTraversalModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        let mut features = self.energy_model.state_features();
+        features.push((String::from(Self::CO2E), Self::emissions_feature()));
+        if self.emission_factors.has_nox() {
+            features.push((String::from(Self::NOX), Self::emissions_feature()));
+        }
+        if self.emission_factors.has_pm() {
+            features.push((String::from(Self::PM), Self::emissions_feature()));
+        }
+        features
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let prev = state.to_vec();
+        self.energy_model
+            .traverse_edge(trajectory, state, state_model)?;
+
+        // grid intensity at the hour of day the edge was entered
+        let grid_intensity = match (&self.grid_intensity, self.departure_seconds) {
+            (None, _) => None,
+            (Some(grid), None) => grid.get(None),
+            (Some(grid), Some(departure)) => {
+                let elapsed =
+                    state_model.get_time(&prev, &Self::TIME.into(), &TimeUnit::Seconds)?;
+                let hour = ((departure + elapsed.as_f64()) / 3600.0).floor() as u64 % 24;
+                grid.get(Some(hour as u32))
+            }
+        };
+        let emissions = self.emissions(&prev, state, state_model, grid_intensity)?;
+        self.add_emissions(emissions, state, state_model)
+    }
+
+    /// estimates with the best case energy of the energy model and the lowest grid
+    /// intensity of the day, which never overestimates
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVariable>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let prev = state.to_vec();
+        self.energy_model
+            .estimate_traversal(od, state, state_model)?;
+        let grid_intensity = self.grid_intensity.as_ref().and_then(|g| g.min());
+        let emissions = self.emissions(&prev, state, state_model, grid_intensity)?;
+        self.add_emissions(emissions, state, state_model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::emission_factors::EmissionFactor;
+    use super::*;
+    use geo::coord;
+    use routee_compass_core::model::network::{EdgeId, VertexId};
+    use routee_compass_core::model::unit::{Distance, Energy, Time};
+    use routee_compass_core::util::geo::coord::InternalCoord;
+    use std::collections::HashMap;
+
+    /// consumes a tenth of a gallon of gasoline and 1 kWh on each edge, taking one hour
+    struct MockEnergyModel;
+
+    impl TraversalModel for MockEnergyModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![
+                (
+                    String::from("energy_liquid"),
+                    StateFeature::Energy {
+                        energy_unit: EnergyUnit::GallonsGasoline,
+                        initial: Energy::ZERO,
+                    },
+                ),
+                (
+                    String::from("energy_electric"),
+                    StateFeature::Energy {
+                        energy_unit: EnergyUnit::KilowattHours,
+                        initial: Energy::ZERO,
+                    },
+                ),
+                (
+                    String::from("time"),
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Hours,
+                        initial: Time::ZERO,
+                    },
+                ),
+            ]
+        }
+
+        fn traverse_edge(
+            &self,
+            _trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVariable>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            state_model.add_energy(
+                state,
+                &"energy_liquid".into(),
+                &Energy::new(0.1),
+                &EnergyUnit::GallonsGasoline,
+            )?;
+            state_model.add_energy(
+                state,
+                &"energy_electric".into(),
+                &Energy::new(1.0),
+                &EnergyUnit::KilowattHours,
+            )?;
+            state_model.add_time(state, &"time".into(), &Time::new(1.0), &TimeUnit::Hours)?;
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVariable>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_emissions_from_energy() {
+        let factors = EmissionFactors {
+            liquid: Some(EmissionFactor {
+                energy_unit: EnergyUnit::GallonsGasoline,
+                co2e: 9000.0,
+                upstream_co2e: 1000.0,
+                nox: Some(2.0),
+                pm: None,
+            }),
+            electric: Some(EmissionFactor {
+                energy_unit: EnergyUnit::KilowattHours,
+                co2e: 400.0,
+                upstream_co2e: 0.0,
+                nox: None,
+                pm: None,
+            }),
+            grid_intensity: HashMap::new(),
+        };
+        // the grid is cleaner during the 09:00 hour
+        let grid = GridIntensity {
+            daily: Some(300.0),
+            hourly: HashMap::from([(9, 100.0)]),
+        };
+        let model = EmissionsTraversalModel::new(
+            Arc::new(MockEnergyModel),
+            Arc::new(factors),
+            Some(grid),
+            Some(8.0 * 3600.0),
+        );
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(0),
+            distance: Distance::new(100.0),
+        };
+        let mut state = state_model.initial_state().unwrap();
+        for _ in 0..2 {
+            model
+                .traverse_edge((&v, &e, &v), &mut state, &state_model)
+                .unwrap();
+        }
+        // 2 x 1000g from gasoline, plus 300g then 100g from electricity
+        let co2e = state_model.get_custom_f64(&state, &"co2e".into()).unwrap();
+        let nox = state_model.get_custom_f64(&state, &"nox".into()).unwrap();
+        assert!((co2e - 2400.0).abs() < 1e-6);
+        assert!((nox - 0.4).abs() < 1e-6);
+        assert!(!state_model.contains_key(&"pm".into()));
+    }
+}
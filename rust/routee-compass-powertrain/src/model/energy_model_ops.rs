@@ -1,10 +1,55 @@
+use super::elevation::EdgeElevation;
+use kdam::Bar;
 use routee_compass_core::model::{
-    access::default::turn_delays::EdgeHeading, network::edge_id::EdgeId,
-    traversal::TraversalModelError, unit::Grade,
+    access::default::turn_delays::EdgeHeading,
+    network::edge_id::EdgeId,
+    traversal::TraversalModelError,
+    unit::{Grade, GradeUnit},
 };
+use routee_compass_core::util::fs::{read_decoders, read_utils};
+use std::path::Path;
 
 pub const ZERO_ENERGY: f64 = 1e-9;
 
+/// reads the grade of each edge, either from a grade table file or from grades derived
+/// from vertex elevations, in the given grade unit. without either, there is no grade
+/// table and all edges are flat.
+pub fn read_grade_table<P: AsRef<Path>>(
+    grade_table_path_option: &Option<P>,
+    grade_table_grade_unit: GradeUnit,
+    edge_elevation_option: Option<&EdgeElevation>,
+) -> Result<Option<Box<[Grade]>>, TraversalModelError> {
+    match (grade_table_path_option, edge_elevation_option) {
+        (Some(_), Some(_)) => Err(TraversalModelError::BuildError(String::from(
+            "grades come from either a grade table or elevations, not both",
+        ))),
+        (None, Some(edge_elevation)) => Ok(Some(
+            edge_elevation
+                .grades
+                .iter()
+                .map(|g| GradeUnit::Decimal.convert(g, &grade_table_grade_unit))
+                .collect(),
+        )),
+        (Some(gtp), None) => {
+            let grades = read_utils::read_raw_file(
+                gtp,
+                read_decoders::default,
+                Some(Bar::builder().desc("link grades")),
+                None,
+            )
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failure reading grade table {} due to {}",
+                    gtp.as_ref().to_str().unwrap_or_default(),
+                    e
+                ))
+            })?;
+            Ok(Some(grades))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
 /// look up the grade from the grade table
 pub fn get_grade(
     grade_table: &Option<Box<[Grade]>>,
@@ -1,14 +1,11 @@
+use super::elevation::EdgeElevation;
 use super::energy_traversal_model::EnergyTraversalModel;
 use super::vehicle::VehicleType;
-use kdam::Bar;
 use routee_compass_core::model::traversal::{
     TraversalModel, TraversalModelError, TraversalModelService,
 };
 use routee_compass_core::model::unit::*;
-use routee_compass_core::util::fs::read_decoders;
-use routee_compass_core::util::fs::read_utils;
 use std::collections::HashMap;
-use std::path::Path;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -17,6 +14,8 @@ pub struct EnergyModelService {
     pub time_model_speed_unit: SpeedUnit,
     pub grade_table: Arc<Option<Box<[Grade]>>>,
     pub grade_table_grade_unit: GradeUnit,
+    /// elevation gain of each edge in the base distance unit, when derived from elevations
+    pub elevation_gain_table: Arc<Option<Box<[Distance]>>>,
     pub time_unit: TimeUnit,
     pub distance_unit: DistanceUnit,
     pub vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
@@ -24,11 +23,12 @@ pub struct EnergyModelService {
 
 impl EnergyModelService {
     #[allow(clippy::too_many_arguments)]
-    pub fn new<P: AsRef<Path>>(
+    pub fn new(
         time_model_service: Arc<dyn TraversalModelService>,
         time_model_speed_unit: SpeedUnit,
-        grade_table_path_option: &Option<P>,
+        grade_table: Arc<Option<Box<[Grade]>>>,
         grade_table_grade_unit: GradeUnit,
+        edge_elevation_option: Option<EdgeElevation>,
         output_time_unit_option: Option<TimeUnit>,
         output_distance_unit_option: Option<DistanceUnit>,
         vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
@@ -36,30 +36,14 @@ impl EnergyModelService {
         let output_time_unit = output_time_unit_option.unwrap_or(BASE_TIME_UNIT);
         let output_distance_unit = output_distance_unit_option.unwrap_or(BASE_DISTANCE_UNIT);
 
-        let grade_table: Arc<Option<Box<[Grade]>>> = match grade_table_path_option {
-            Some(gtp) => Arc::new(Some(
-                read_utils::read_raw_file(
-                    gtp,
-                    read_decoders::default,
-                    Some(Bar::builder().desc("link grades")),
-                    None,
-                )
-                .map_err(|e| {
-                    TraversalModelError::BuildError(format!(
-                        "failure reading grade table {} due to {}",
-                        gtp.as_ref().to_str().unwrap_or_default(),
-                        e
-                    ))
-                })?,
-            )),
-            None => Arc::new(None),
-        };
+        let elevation_gain_table = Arc::new(edge_elevation_option.map(|e| e.elevation_gain));
 
         Ok(EnergyModelService {
             time_model_service,
             time_model_speed_unit,
             grade_table,
             grade_table_grade_unit,
+            elevation_gain_table,
             time_unit: output_time_unit,
             distance_unit: output_distance_unit,
             vehicle_library,
@@ -76,4 +60,13 @@ impl TraversalModelService for EnergyModelService {
         let model = EnergyTraversalModel::new(arc_self, parameters)?;
         Ok(Arc::new(model))
     }
+
+    /// lists the features of the time model, as vehicle features depend on the query
+    fn state_feature_names(&self) -> Vec<String> {
+        let mut names = self.time_model_service.state_feature_names();
+        if self.elevation_gain_table.is_some() {
+            names.push(String::from(EnergyTraversalModel::ELEVATION_GAIN));
+        }
+        names
+    }
 }
@@ -18,10 +18,20 @@ pub struct EnergyTraversalModel {
 }
 
 impl TraversalModel for EnergyTraversalModel {
-    /// inject the state features required by the VehicleType
+    /// inject the state features required by the VehicleType, along with elevation
+    /// gain when the grades are derived from elevations
     fn state_features(&self) -> Vec<(String, StateFeature)> {
         let mut features = self.vehicle.state_features();
         features.extend(self.time_model.state_features());
+        if self.energy_model_service.elevation_gain_table.is_some() {
+            features.push((
+                String::from(Self::ELEVATION_GAIN),
+                StateFeature::Distance {
+                    distance_unit: self.energy_model_service.distance_unit,
+                    initial: Distance::ZERO,
+                },
+            ));
+        }
         features
     }
 
@@ -76,6 +86,25 @@ impl TraversalModel for EnergyTraversalModel {
             state_model,
         )?;
 
+        if let Some(elevation_gain_table) = self.energy_model_service.elevation_gain_table.as_ref()
+        {
+            let elevation_gain = elevation_gain_table
+                .get(edge.edge_id.as_usize())
+                .ok_or_else(|| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "missing index {} from elevation gain table",
+                        edge.edge_id
+                    ))
+                })?;
+            state_model.add_distance(
+                state,
+                &Self::ELEVATION_GAIN.into(),
+                &BASE_DISTANCE_UNIT
+                    .convert(elevation_gain, &self.energy_model_service.distance_unit),
+                &self.energy_model_service.distance_unit,
+            )?;
+        }
+
         Ok(())
     }
 
@@ -115,6 +144,7 @@ impl TraversalModel for EnergyTraversalModel {
 
 impl EnergyTraversalModel {
     const TIME: &'static str = "time";
+    pub(crate) const ELEVATION_GAIN: &'static str = "elevation_gain";
 
     pub fn new(
         energy_model_service: Arc<EnergyModelService>,
@@ -160,7 +190,8 @@ impl EnergyTraversalModel {
 mod tests {
     use super::*;
     use crate::model::{
-        prediction::load_prediction_model, prediction::ModelType, vehicle::default::ICE,
+        energy_model_ops::read_grade_table, prediction::load_prediction_model,
+        prediction::ModelType, vehicle::default::ICE,
     };
     use geo::coord;
     use routee_compass_core::{
@@ -224,17 +255,21 @@ mod tests {
             SpeedTraversalEngine::new(&speed_file_path, SpeedUnit::KilometersPerHour, None, None)
                 .unwrap(),
         );
-        let time_service = SpeedLookupService { e: time_engine };
+        let time_service = SpeedLookupService {
+            e: time_engine,
+            precompute_edge_deltas: false,
+        };
 
         let service = EnergyModelService::new(
             Arc::new(time_service),
             SpeedUnit::MilesPerHour,
             // &speed_file_path,
-            &Some(grade_file_path),
+            Arc::new(read_grade_table(&Some(grade_file_path), GradeUnit::Millis, None).unwrap()),
             // SpeedUnit::KilometersPerHour,
             GradeUnit::Millis,
             None,
             None,
+            None,
             model_library,
         )
         .unwrap();
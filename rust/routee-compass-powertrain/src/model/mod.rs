@@ -1,3 +1,10 @@
+pub mod active_mode;
+pub mod active_mode_service;
+pub mod active_mode_traversal_model;
+pub mod elevation;
+pub mod emission_factors;
+pub mod emissions_model_service;
+pub mod emissions_traversal_model;
 pub mod energy_model_ops;
 pub mod energy_model_service;
 pub mod energy_traversal_model;
@@ -8,15 +8,17 @@ use crate::{
         compass::{
             compass_input_field::CompassInputField,
             model::{
+                charging_model_builder::ChargingModelBuilder,
                 cost_model::cost_model_builder::CostModelBuilder,
                 termination_model_builder::TerminationModelBuilder,
+                transit_model_builder::TransitModelBuilder,
             },
             CompassAppError,
         },
         search::{SearchApp, SearchAppResult},
     },
     plugin::{
-        input::{input_plugin_ops as in_ops, InputPlugin},
+        input::{input_plugin_ops as in_ops, InputField, InputPlugin},
         output::{output_plugin_ops as out_ops, OutputPlugin},
     },
 };
@@ -26,7 +28,9 @@ use itertools::{Either, Itertools};
 use kdam::{Bar, BarExt};
 use rayon::{current_num_threads, prelude::*};
 use routee_compass_core::algorithm::search::{SearchAlgorithm, SearchInstance};
-use routee_compass_core::config::{CompassConfigurationField, ConfigJsonExtensions};
+use routee_compass_core::config::{
+    CompassConfigurationError, CompassConfigurationField, ConfigJsonExtensions,
+};
 use routee_compass_core::model::map::{MapModel, MapModelConfig};
 use routee_compass_core::model::network::Graph;
 use routee_compass_core::model::state::StateModel;
@@ -38,6 +42,9 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+/// key of the optional transit layer within the graph configuration
+const TRANSIT: &str = "transit";
+
 /// Instance of RouteE Compass as an application.
 /// When constructed, it holds
 ///   - the core search application which performs parallel path search
@@ -144,11 +151,58 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             None => Arc::new(StateModel::empty()),
         };
 
+        // build graph
+        let graph_start = Local::now();
+        let graph_params =
+            config_json.get_config_section(CompassConfigurationField::Graph, &"TOML")?;
+        let mut graph = Graph::try_from(&graph_params)?;
+        let (road_vertex_count, road_edge_count) = (graph.n_vertices(), graph.n_edges());
+
+        // build optional transit layer, which extends the graph and registers the transit
+        // traversal model, access model and output plugin
+        let transit_builder = match graph_params.get(TRANSIT) {
+            Some(transit_json) => {
+                // transit edges are only traversed by the transit traversal model, and
+                // vehicles are only boarded by the transit access model
+                for field in [
+                    CompassConfigurationField::Traversal,
+                    CompassConfigurationField::Access,
+                ] {
+                    let section = field.to_str();
+                    let model_type = config_json
+                        .get_config_section(field, &"TOML")?
+                        .get_config_string(&"type", &section)?;
+                    if model_type != TRANSIT {
+                        return Err(CompassConfigurationError::UserConfigurationError(format!(
+                            "graph.transit requires {} type '{}', found '{}'",
+                            section, TRANSIT, model_type
+                        ))
+                        .into());
+                    }
+                }
+                let layer = TransitModelBuilder::build(transit_json, &mut graph)?;
+                let mut transit_builder = builder.clone();
+                transit_builder.add_transit_layer(layer);
+                Some(transit_builder)
+            }
+            None => None,
+        };
+        let builder = transit_builder.as_ref().unwrap_or(builder);
+        let graph = Arc::new(graph);
+        let graph_duration = (Local::now() - graph_start)
+            .to_std()
+            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
+        log::info!(
+            "finished reading graph with duration {}",
+            graph_duration.hhmmss()
+        );
+
         // build traversal model
         let traversal_start = Local::now();
         let traversal_params =
             config_json.get_config_section(CompassConfigurationField::Traversal, &"TOML")?;
         let traversal_model_service = builder.build_traversal_model_service(&traversal_params)?;
+        let edge_delta_table = traversal_model_service.edge_delta_table(&graph)?;
         let traversal_duration = (Local::now() - traversal_start)
             .to_std()
             .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
@@ -194,19 +248,14 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
         let termination_model_json =
             config_json.get_config_section(CompassConfigurationField::Termination, &"TOML")?;
         let termination_model = TerminationModelBuilder::build(&termination_model_json, None)?;
+        let termination_behavior =
+            TerminationModelBuilder::build_behavior(&termination_model_json, &search_algorithm)?;
 
-        // build graph
-        let graph_start = Local::now();
-        let graph_params =
-            config_json.get_config_section(CompassConfigurationField::Graph, &"TOML")?;
-        let graph = Arc::new(Graph::try_from(&graph_params)?);
-        let graph_duration = (Local::now() - graph_start)
-            .to_std()
-            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
-        log::info!(
-            "finished reading graph with duration {}",
-            graph_duration.hhmmss()
-        );
+        // build optional charging model
+        let charging_model = match config_json.get(CompassConfigurationField::Charging.to_str()) {
+            Some(charging_json) => Some(ChargingModelBuilder::build(charging_json)?),
+            None => None,
+        };
 
         let graph_bytes = allocative::size_of_unique_allocated_data(&graph);
         log::info!("graph size: {} GB", graph_bytes as f64 / 1e9);
@@ -215,7 +264,13 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
         let map_model_json = config_json.get(CompassConfigurationField::MapModel.to_str());
         let map_model_config =
             MapModelConfig::try_from(map_model_json).map_err(CompassAppError::BuildFailure)?;
-        let map_model = Arc::new(MapModel::new(graph.clone(), map_model_config).map_err(|e| {
+        let map_model = MapModel::new_for_road_network(
+            graph.clone(),
+            map_model_config,
+            road_vertex_count,
+            road_edge_count,
+        );
+        let map_model = Arc::new(map_model.map_err(|e| {
             CompassAppError::BuildFailure(format!("unable to load MapModel from config: {}", e))
         })?);
         let map_dur = to_std(Local::now() - map_start)?;
@@ -258,10 +313,14 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             map_model,
             state_model,
             traversal_model_service,
+            traversal_params,
+            edge_delta_table,
             access_model_service,
             cost_model_service,
             frontier_model_service,
             termination_model,
+            termination_behavior,
+            charging_model,
         ));
 
         // build plugins
@@ -491,6 +550,7 @@ where
 /// It is assumed that all pre-processing from InputPlugins have been applied.
 /// This function runs a vertex-oriented search and feeds the result into the
 /// OutputPlugins for post-processing, returning the result as JSON.
+/// Queries with an origin list are run as matrix queries.
 ///
 /// # Arguments
 ///
@@ -504,6 +564,15 @@ pub fn run_single_query(
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
 ) -> Result<serde_json::Value, CompassAppError> {
+    // queries listing origins or destinations are matrix queries, which fail when
+    // either list is missing or malformed
+    let is_matrix_query = [InputField::OriginList, InputField::DestinationList]
+        .iter()
+        .any(|field| query.get(field.to_str()).is_some());
+    if is_matrix_query {
+        let matrix_result = search_app.run_matrix(query);
+        return Ok(out_ops::create_matrix_output(query, &matrix_result));
+    }
     let search_result = search_app.run(query);
     let output = apply_output_processing(query, search_result, search_app, output_plugins);
     Ok(output)
@@ -609,6 +678,7 @@ pub fn apply_output_processing(
 #[cfg(test)]
 mod tests {
     use super::CompassApp;
+    use crate::app::compass::CompassAppBuilder;
     use crate::app::compass::CompassAppError;
     use routee_compass_core::config::CompassConfigurationError;
     use std::path::PathBuf;
@@ -622,38 +692,7 @@ mod tests {
         // eprintln!("cwd           : {}", cwd_str);
         // eprintln!("Cargo.toml dir: {}", env!("CARGO_MANIFEST_DIR"));
 
-        // rust runs test and debug at different locations, which breaks the URLs
-        // written in the referenced TOML files. here's a quick fix
-        // turnaround that doesn't leak into anyone's VS Code settings.json files
-        // see https://github.com/rust-lang/rust-analyzer/issues/4705 for discussion
-        let conf_file_test = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("src")
-            .join("app")
-            .join("compass")
-            .join("test")
-            .join("speeds_test")
-            .join("speeds_test.toml");
-
-        let conf_file_debug = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("src")
-            .join("app")
-            .join("compass")
-            .join("test")
-            .join("speeds_test")
-            .join("speeds_debug.toml");
-
-        let app = match CompassApp::try_from(conf_file_test.as_path()) {
-            Ok(a) => Ok(a),
-            Err(CompassAppError::CompassConfigurationError(
-                CompassConfigurationError::FileNormalizationNotFound(_key, _f1, _f2),
-            )) => {
-                // could just be the run location, depending on the environment/runner/IDE
-                // try the alternative configuration that runs from the root directory
-                CompassApp::try_from(conf_file_debug.as_path())
-            }
-            Err(other) => panic!("{}", other),
-        }
-        .unwrap();
+        let app = build_speeds_test_app();
         let query = serde_json::json!({
             "origin_vertex": 0,
             "destination_vertex": 2
@@ -670,6 +709,442 @@ mod tests {
         assert_eq!(path_0, &expected_path);
     }
 
+    #[test]
+    fn test_speeds_reverse() {
+        let app = build_speeds_test_app();
+        let forward = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let reverse = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "direction": "reverse"
+        });
+        let mut queries = vec![forward, reverse];
+        let result = app.run(&mut queries, None).unwrap();
+        assert_eq!(result.len(), 2, "expected two results");
+        let fwd_route = result[0].get("route").unwrap();
+        let rev_route = result[1].get("route").unwrap();
+
+        // the reverse search is re-oriented to match the forward route and its state
+        let expected_path = serde_json::json!(vec![0, 2]);
+        assert_eq!(rev_route.get("path").unwrap(), &expected_path);
+        assert_eq!(
+            rev_route.get("traversal_summary").unwrap(),
+            fwd_route.get("traversal_summary").unwrap()
+        );
+    }
+
+    /// loads the test app configured by `{name}_test.toml` in the test directory `dir`,
+    /// appending `extra_toml` to the configuration. rust runs test and debug at different
+    /// locations, which breaks the URLs written in the referenced TOML files, so when the
+    /// files are not found, the alternative `{name}_debug.toml` configuration that runs
+    /// from the root directory is used instead. see
+    /// https://github.com/rust-lang/rust-analyzer/issues/4705 for discussion
+    fn try_load_test_app(
+        dir: &str,
+        name: &str,
+        extra_toml: &str,
+    ) -> Result<CompassApp, CompassAppError> {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join(dir);
+        let build = |conf_file: PathBuf| {
+            let config_string = std::fs::read_to_string(&conf_file).unwrap() + extra_toml;
+            CompassApp::try_from_config_toml_string(
+                config_string,
+                conf_file.to_string_lossy().to_string(),
+                &CompassAppBuilder::default(),
+            )
+        };
+        match build(test_dir.join(format!("{}_test.toml", name))) {
+            Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::FileNormalizationNotFound(_key, _f1, _f2),
+            )) => build(test_dir.join(format!("{}_debug.toml", name))),
+            other => other,
+        }
+    }
+
+    /// loads the test app configured by `{name}_test.toml` in the test directory `dir`.
+    /// see [`try_load_test_app`].
+    fn load_test_app(dir: &str, name: &str) -> CompassApp {
+        try_load_test_app(dir, name, "").unwrap()
+    }
+
+    /// loads the speeds test app from either the test or debug run location
+    fn build_speeds_test_app() -> CompassApp {
+        load_test_app("speeds_test", "speeds")
+    }
+
+    #[test]
+    fn test_speeds_matrix() {
+        let app = build_speeds_test_app();
+        let query = serde_json::json!({
+            "origin_list": [{ "vertex": 0 }, { "vertex": 1 }],
+            "destination_list": [{ "vertex": 2 }, { "vertex": 0 }],
+            "include_routes": true
+        });
+        let mut queries = vec![query];
+        let result = app.run(&mut queries, None).unwrap();
+        assert_eq!(result.len(), 1, "expected one result");
+        let matrix = result[0].get("matrix").unwrap();
+
+        // vertex 0 cannot be reached from vertex 1
+        let expected_routes = serde_json::json!([[[0, 2], []], [[2], null]]);
+        assert_eq!(matrix.get("route").unwrap(), &expected_routes);
+        let cost = matrix.get("cost").unwrap();
+        assert_eq!(cost[0][1], serde_json::json!(0.0));
+        assert!(cost[1][1].is_null());
+        assert!(cost[0][0].as_f64().unwrap() > cost[1][0].as_f64().unwrap());
+    }
+
+    #[test]
+    fn test_speeds_matrix_invalid_lists() {
+        let app = build_speeds_test_app();
+        let malformed = serde_json::json!({
+            "origin_list": { "vertex": 0 },
+            "destination_list": [{ "vertex": 2 }]
+        });
+        let missing_origins = serde_json::json!({
+            "destination_list": [{ "vertex": 2 }]
+        });
+        let mut queries = vec![malformed, missing_origins];
+        let result = app.run(&mut queries, None).unwrap();
+        assert_eq!(result.len(), 2, "expected two results");
+        for response in result.iter() {
+            assert!(response.get("matrix").is_none());
+            assert!(response.get("route").is_none());
+            let error = response.get("error").unwrap().as_str().unwrap();
+            assert!(error.contains("origin_list"), "unexpected error: {}", error);
+        }
+    }
+
+    #[test]
+    fn test_speeds_candidates_unsupported_queries() {
+        let mapping = "\n[mapping]\ntype = \"vertex\"\nqueries_without_destinations = true\ncandidates = { count = 2, cost_per_meter = 1.0 }\n";
+        let app = try_load_test_app("speeds_test", "speeds", mapping).unwrap();
+        let route = serde_json::json!({
+            "origin_x": -105.1683038,
+            "origin_y": 39.7379033,
+            "destination_x": -111.9095014,
+            "destination_y": 40.7607176
+        });
+        let mut reverse = route.clone();
+        reverse["direction"] = serde_json::json!("reverse");
+        let mut waypoints = route.clone();
+        waypoints["waypoints"] = serde_json::json!([{ "vertex": 1 }]);
+        let matrix = serde_json::json!({
+            "origin_list": [{ "x": -105.1683038, "y": 39.7379033 }],
+            "destination_list": [{ "vertex": 2 }]
+        });
+        let result = app.run(&mut [route], None).unwrap();
+        assert!(result[0].get("route").is_some());
+        for (query, query_type) in [
+            (reverse, "reverse"),
+            (waypoints, "waypoint"),
+            (matrix, "matrix"),
+        ] {
+            let result = app.run(&mut [query], None).unwrap();
+            let error = result[0].get("error").unwrap().as_str().unwrap();
+            let expected = format!("{} queries do not support snapping candidates", query_type);
+            assert!(error.contains(&expected), "unexpected error: {}", error);
+        }
+    }
+
+    #[test]
+    fn test_speeds_waypoints() {
+        let app = build_speeds_test_app();
+        let first_leg = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 1
+        });
+        let direct = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let waypoints = serde_json::json!({
+            "origin_vertex": 0,
+            "waypoints": [{ "vertex": 1 }],
+            "destination_vertex": 2
+        });
+        // results are not guaranteed to follow query order, so run each query separately
+        let result = [first_leg, direct, waypoints]
+            .into_iter()
+            .map(|query| app.run(&mut [query], None).unwrap().remove(0))
+            .collect::<Vec<_>>();
+        let first_leg_route = result[0].get("route").unwrap();
+        let direct_route = result[1].get("route").unwrap();
+        let route = result[2].get("route").unwrap();
+
+        assert_eq!(route.get("path").unwrap(), &serde_json::json!(vec![0, 2]));
+        assert_eq!(
+            result[2].get("route_leg_edges"),
+            Some(&serde_json::json!([1, 1]))
+        );
+
+        // the second leg continues from the state at the end of the first leg
+        let legs = route.get("legs").unwrap().as_array().unwrap();
+        assert_eq!(legs.len(), 2);
+        assert_eq!(legs[0].get("start_index"), Some(&serde_json::json!(0)));
+        assert_eq!(legs[1].get("start_index"), Some(&serde_json::json!(1)));
+        assert_eq!(
+            legs[0].get("traversal_summary").unwrap(),
+            first_leg_route.get("traversal_summary").unwrap()
+        );
+        assert_eq!(
+            legs[1].get("traversal_summary").unwrap(),
+            direct_route.get("traversal_summary").unwrap()
+        );
+        assert_eq!(
+            route.get("traversal_summary").unwrap(),
+            direct_route.get("traversal_summary").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_speeds_stop_order() {
+        let app = build_speeds_test_app();
+        // the network is one-way from vertex 0 to 1 to 2, so only one order visits all stops
+        let query = serde_json::json!({
+            "stops": [{ "vertex": 2 }, { "vertex": 0 }, { "vertex": 1 }]
+        });
+        let result = app.run(&mut [query], None).unwrap();
+        assert_eq!(result.len(), 1, "expected one result");
+        assert_eq!(
+            result[0].get("stop_order"),
+            Some(&serde_json::json!([1, 2, 0]))
+        );
+        let route = result[0].get("route").unwrap();
+        assert_eq!(route.get("path").unwrap(), &serde_json::json!(vec![0, 2]));
+        assert_eq!(route.get("legs").unwrap().as_array().unwrap().len(), 2);
+    }
+
+    const PARTIAL_RESULT_TERMINATION: &str =
+        "\n[termination]\ntype = \"iterations\"\nlimit = 1\nbehavior = \"partial_result\"\n";
+
+    /// loads the speeds test app with a termination model that interrupts searches
+    /// after their first iteration and returns partial results
+    fn build_speeds_partial_result_app() -> CompassApp {
+        try_load_test_app("speeds_test", "speeds", PARTIAL_RESULT_TERMINATION).unwrap()
+    }
+
+    #[test]
+    fn test_speeds_partial_result() {
+        let app = build_speeds_partial_result_app();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let result = app.run(&mut [query], None).unwrap().remove(0);
+        assert_eq!(result.get("terminated"), Some(&serde_json::json!(true)));
+        assert!(result.get("termination_explanation").is_some());
+        // after one iteration, the destination has only been reached by the direct edge,
+        // which is not the time-optimal path [0, 2]
+        let route = result.get("route").unwrap();
+        assert_eq!(route.get("path").unwrap(), &serde_json::json!(vec![1]));
+
+        // queries composed of several searches fail instead of returning one leg's tree
+        let waypoints = serde_json::json!({
+            "origin_vertex": 0,
+            "waypoints": [{ "vertex": 1 }],
+            "destination_vertex": 2
+        });
+        let result = app.run(&mut [waypoints], None).unwrap().remove(0);
+        assert!(result.get("terminated").is_none());
+        let error = result.get("error").unwrap().as_str().unwrap();
+        assert!(error.contains("query terminated"));
+        assert!(error.contains("partial results are not supported for waypoint queries"));
+    }
+
+    #[test]
+    fn test_partial_result_unsupported_algorithm() {
+        let config = format!(
+            "{}\n[algorithm]\ntype = \"bidirectional_a*\"\n",
+            PARTIAL_RESULT_TERMINATION
+        );
+        let error = try_load_test_app("speeds_test", "speeds", &config)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("partial_result"));
+    }
+
+    /// loads the charging test app, a line of six 50 kilometer edges with charging
+    /// stations at the five interior vertices, from either the test or debug run location
+    fn build_charging_test_app() -> CompassApp {
+        load_test_app("charging_test", "charging")
+    }
+
+    /// runs a charging stop query along the line from vertex 0 to the destination
+    fn run_charging_query(
+        app: &CompassApp,
+        destination: usize,
+        starting_soc_percent: f64,
+    ) -> serde_json::Value {
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": destination,
+            "model_name": "2017_CHEVROLET_Bolt",
+            "starting_soc_percent": starting_soc_percent,
+            "insert_charging_stops": true
+        });
+        app.run(&mut [query], None).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_charging_reserve() {
+        let app = build_charging_test_app();
+        // each edge uses about 20% of the battery, so the vehicle reaches vertex 2 just
+        // above the 10% reserve and must charge to continue to vertex 3. both stations
+        // are reachable with the same driving time, but the charge at vertex 1 is shorter.
+        let direct = run_charging_query(&app, 2, 50.0);
+        assert!(direct.get("charging_stops").is_none());
+        let result = run_charging_query(&app, 3, 50.0);
+        let route = result.get("route").unwrap();
+        assert_eq!(
+            route.get("path").unwrap(),
+            &serde_json::json!(vec![0, 1, 2])
+        );
+        let stops = result.get("charging_stops").unwrap().as_array().unwrap();
+        assert_eq!(stops.len(), 1);
+        assert_eq!(stops[0].get("vertex_id"), Some(&serde_json::json!(1)));
+        assert_eq!(
+            stops[0].get("departure_soc"),
+            Some(&serde_json::json!(80.0))
+        );
+        let arrival_soc = stops[0].get("arrival_soc").unwrap().as_f64().unwrap();
+        assert!(arrival_soc >= 10.0);
+        let final_soc = route
+            .get("traversal_summary")
+            .and_then(|s| s.get("battery_state"))
+            .and_then(|s| s.as_f64())
+            .unwrap();
+        assert!(final_soc >= 10.0);
+    }
+
+    #[test]
+    fn test_charging_multiple_stops() {
+        let app = build_charging_test_app();
+        // a charge to 80% covers three edges, so the vehicle charges until it can
+        // reach the destination
+        let result = run_charging_query(&app, 6, 50.0);
+        let stops = result.get("charging_stops").unwrap().as_array().unwrap();
+        let stop_vertices = stops
+            .iter()
+            .map(|s| s.get("vertex_id").unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(stop_vertices, vec![1, 2, 3]);
+        let route = result.get("route").unwrap();
+        assert_eq!(route.get("legs").unwrap().as_array().unwrap().len(), 4);
+
+        // time spent charging counts toward the route, which drives 3 hours
+        let dwell_hours = stops
+            .iter()
+            .map(|s| s.get("dwell_time").unwrap().as_f64().unwrap() / 3600.0)
+            .sum::<f64>();
+        assert!(dwell_hours > 0.0);
+        let time = route
+            .get("traversal_summary")
+            .and_then(|s| s.get("time"))
+            .and_then(|t| t.as_f64())
+            .unwrap();
+        assert!((time - (3.0 + dwell_hours)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_charging_no_reachable_station() {
+        let app = build_charging_test_app();
+        // at 20% the vehicle cannot reach the first station without falling below reserve
+        let result = run_charging_query(&app, 3, 20.0);
+        assert!(result.get("route").is_none());
+        let error = result.get("error").unwrap().as_str().unwrap();
+        assert!(error.contains("no reachable charging station"));
+    }
+
+    #[test]
+    fn test_combined_traversal_conflicting_features() {
+        // both models provide a distance feature
+        let config = serde_json::json!({
+            "type": "combined",
+            "traversal_models": [
+                { "type": "distance", "distance_unit": "meters" },
+                {
+                    "type": "speed_table",
+                    "speed_table_input_file": "src/app/compass/test/speeds_test/test_edge_speeds.csv",
+                    "speed_unit": "kilometers_per_hour"
+                }
+            ]
+        });
+        let result = CompassAppBuilder::default().build_traversal_model_service(&config);
+        match result {
+            Err(e) => assert!(e
+                .to_string()
+                .contains("both provide state feature 'distance'")),
+            Ok(_) => panic!("expected conflicting distance features to fail the build"),
+        }
+    }
+
+    #[test]
+    fn test_transit_requires_transit_traversal() {
+        let result = try_load_test_app("transit_test", "transit_speed_table", "");
+        match result {
+            Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(msg),
+            )) => assert!(msg.contains("requires traversal type 'transit'")),
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("expected graph.transit with a speed_table traversal to fail"),
+        }
+    }
+
+    #[test]
+    fn test_transit_requires_transit_access() {
+        // the transit test app uses the default access model unless one is appended
+        let result = try_load_test_app("transit_test", "transit", "");
+        match result {
+            Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(msg),
+            )) => assert!(msg.contains("requires access type 'transit'")),
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("expected graph.transit without transit access to fail"),
+        }
+    }
+
+    #[test]
+    fn test_transit_walk_board_and_ride() {
+        let access = "\n[access]\ntype = \"transit\"\nboarding_penalty_seconds = 60.0\n";
+        let app = try_load_test_app("transit_test", "transit", access).unwrap();
+        // stops a and b sit on road vertices 0 and 1. trip t1 rides from a to b between
+        // 08:00 and 08:30, beating the 94 minute drive on road edge 0.
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 1,
+            "departure_time": "07:55"
+        });
+        let result = app.run(&mut [query], None).unwrap().remove(0);
+        let route = result.get("route").unwrap();
+
+        // the stop vertices and transit edges are appended after the three road edges:
+        // connectors 3 and 4 at stop a, connectors 5 and 6 at stop b, and ride edge 7
+        assert_eq!(route.get("path").unwrap(), &serde_json::json!([4, 7, 5]));
+        let summary = route.get("traversal_summary").unwrap();
+        let boardings = summary.get("transit_boardings").unwrap().as_f64();
+        assert_eq!(boardings, Some(1.0));
+        // a minute to board, a four minute wait for the 08:00 departure, and the ride
+        let seconds = summary.get("time").unwrap().as_f64().unwrap();
+        assert!((seconds - 35.0 * 60.0).abs() < 1e-6);
+        assert_eq!(
+            result.get("route_modes").unwrap(),
+            &serde_json::json!([
+                { "mode": "walk", "start_index": 0, "end_index": 1 },
+                { "mode": "r1", "start_index": 1, "end_index": 2 },
+                { "mode": "walk", "start_index": 2, "end_index": 3 }
+            ])
+        );
+    }
+
     // #[test]
     // fn test_energy() {
     //     // rust runs test and debug at different locations, which breaks the URLs
@@ -8,8 +8,8 @@ use crate::plugin::{
     },
     output::{
         default::{
-            summary::SummaryOutputPluginBuilder, traversal::TraversalPluginBuilder,
-            uuid::UUIDOutputPluginBuilder,
+            summary::SummaryOutputPluginBuilder, transit::TransitOutputPluginBuilder,
+            traversal::TraversalPluginBuilder, uuid::UUIDOutputPluginBuilder,
         },
         OutputPlugin,
     },
@@ -18,6 +18,7 @@ use crate::{
     app::compass::model::{
         access_model::{
             combined_access_model_builder::CombinedAccessModelBuilder,
+            transit_access_builder::TransitAccessBuilder,
             turn_delay_access_model_builder::TurnDelayAccessModelBuilder,
         },
         frontier_model::{
@@ -28,8 +29,15 @@ use crate::{
             vehicle_restrictions::vehicle_restriction_builder::VehicleRestrictionBuilder,
         },
         traversal_model::{
+            active_mode_builder::ActiveModeBuilder,
+            combined_traversal_builder::CombinedTraversalBuilder,
             distance_traversal_builder::DistanceTraversalBuilder,
-            energy_model_builder::EnergyModelBuilder, speed_lookup_builder::SpeedLookupBuilder,
+            emissions_model_builder::EmissionsModelBuilder,
+            energy_model_builder::EnergyModelBuilder, grade_table_cache::GradeTableCache,
+            speed_lookup_builder::SpeedLookupBuilder,
+            time_dependent_speed_builder::TimeDependentSpeedBuilder,
+            toll_traversal_builder::TollTraversalBuilder,
+            transit_traversal_builder::TransitTraversalBuilder,
         },
     },
     plugin::{input::InputPluginBuilder, output::OutputPluginBuilder},
@@ -41,6 +49,7 @@ use routee_compass_core::config::{
 use routee_compass_core::model::{
     access::{default::NoAccessModel, AccessModelBuilder, AccessModelService},
     frontier::{FrontierModelBuilder, FrontierModelService},
+    transit::TransitLayer,
     traversal::{TraversalModelBuilder, TraversalModelService},
 };
 use std::{collections::HashMap, rc::Rc, sync::Arc};
@@ -69,6 +78,7 @@ use super::CompassComponentError;
 /// * `input_plugin_builders` - a mapping of InputPlugin `type` names to builders
 /// * `output_plugin_builders` - a mapping of OutputPlugin `type` names to builders
 ///
+#[derive(Clone)]
 pub struct CompassAppBuilder {
     pub traversal_model_builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
     pub access_model_builders: HashMap<String, Rc<dyn AccessModelBuilder>>,
@@ -118,6 +128,23 @@ impl CompassAppBuilder {
         let _ = self.output_plugin_builders.insert(name, builder);
     }
 
+    /// registers the `transit` traversal model, access model and output plugin for a
+    /// transit layer that has been appended to the graph. the transit traversal model
+    /// wraps one of the traversal models already registered for travel on road edges.
+    pub fn add_transit_layer(&mut self, layer: Arc<TransitLayer>) {
+        let transit_tm = Rc::new(TransitTraversalBuilder {
+            layer: layer.clone(),
+            builders: self.traversal_model_builders.clone(),
+        });
+        let transit_am = Rc::new(TransitAccessBuilder {
+            layer: layer.clone(),
+        });
+        let transit_plugin = Rc::new(TransitOutputPluginBuilder { layer });
+        self.add_traversal_model(String::from("transit"), transit_tm);
+        self.add_access_model(String::from("transit"), transit_am);
+        self.add_output_plugin(String::from("transit"), transit_plugin);
+    }
+
     /// builds a traversal model with the specified type name with the provided
     /// traversal model configuration JSON
     pub fn build_traversal_model_service(
@@ -255,13 +282,49 @@ impl Default for CompassAppBuilder {
         // Traversal model builders
         let dist: Rc<dyn TraversalModelBuilder> = Rc::new(DistanceTraversalBuilder {});
         let speed: Rc<dyn TraversalModelBuilder> = Rc::new(SpeedLookupBuilder {});
+        let time_dependent_speed: Rc<dyn TraversalModelBuilder> =
+            Rc::new(TimeDependentSpeedBuilder {});
+        let toll: Rc<dyn TraversalModelBuilder> = Rc::new(TollTraversalBuilder {});
+        let grade_tables = Rc::new(GradeTableCache::default());
+        let active_mode: Rc<dyn TraversalModelBuilder> = Rc::new(ActiveModeBuilder {
+            grade_tables: grade_tables.clone(),
+        });
         let energy: Rc<dyn TraversalModelBuilder> = Rc::new(EnergyModelBuilder::new(
-            HashMap::from([(String::from("speed_table"), speed.clone())]),
+            HashMap::from([
+                (String::from("speed_table"), speed.clone()),
+                (
+                    String::from("time_dependent_speed"),
+                    time_dependent_speed.clone(),
+                ),
+            ]),
+            grade_tables,
+        ));
+        let emissions: Rc<dyn TraversalModelBuilder> = Rc::new(EmissionsModelBuilder::new(
+            HashMap::from([(String::from("energy_model"), energy.clone())]),
         ));
+        let combined_tm: Rc<dyn TraversalModelBuilder> = Rc::new(CombinedTraversalBuilder {
+            builders: HashMap::from([
+                (String::from("distance"), dist.clone()),
+                (String::from("speed_table"), speed.clone()),
+                (
+                    String::from("time_dependent_speed"),
+                    time_dependent_speed.clone(),
+                ),
+                (String::from("energy_model"), energy.clone()),
+                (String::from("emissions"), emissions.clone()),
+                (String::from("toll"), toll.clone()),
+                (String::from("active_mode"), active_mode.clone()),
+            ]),
+        });
         let tm_builders: HashMap<String, Rc<dyn TraversalModelBuilder>> = HashMap::from([
             (String::from("distance"), dist),
             (String::from("speed_table"), speed),
+            (String::from("time_dependent_speed"), time_dependent_speed),
             (String::from("energy_model"), energy),
+            (String::from("emissions"), emissions),
+            (String::from("toll"), toll),
+            (String::from("active_mode"), active_mode),
+            (String::from("combined"), combined_tm),
         ]);
 
         // Access model builders
@@ -0,0 +1,38 @@
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::access::{
+    AccessModelBuilder, AccessModelError, AccessModelService,
+};
+use routee_compass_core::model::transit::{TransitAccessService, TransitLayer};
+use std::sync::Arc;
+
+/// builds the transit boarding model. only available when the graph has a transit layer.
+pub struct TransitAccessBuilder {
+    pub layer: Arc<TransitLayer>,
+}
+
+impl AccessModelBuilder for TransitAccessBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn AccessModelService>, AccessModelError> {
+        let boarding_penalty_seconds = parameters
+            .get_config_serde_optional::<f64>(&"boarding_penalty_seconds", &"transit access model")
+            .map_err(|e| {
+                AccessModelError::BuildError(format!(
+                    "failure reading 'boarding_penalty_seconds' from access model configuration: {}",
+                    e
+                ))
+            })?
+            .unwrap_or(60.0);
+        if boarding_penalty_seconds.is_nan() || boarding_penalty_seconds < 0.0 {
+            return Err(AccessModelError::BuildError(format!(
+                "boarding_penalty_seconds must be non-negative, found {}",
+                boarding_penalty_seconds
+            )));
+        }
+        Ok(Arc::new(TransitAccessService {
+            layer: self.layer.clone(),
+            boarding_penalty_seconds,
+        }))
+    }
+}
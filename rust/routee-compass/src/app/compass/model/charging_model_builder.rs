@@ -0,0 +1,43 @@
+use kdam::Bar;
+use routee_compass_core::config::{CompassConfigurationError, CompassConfigurationField};
+use routee_compass_core::model::charging::{ChargingModel, ChargingModelConfig, ChargingStation};
+use routee_compass_core::util::fs::read_utils;
+
+pub struct ChargingModelBuilder {}
+
+impl ChargingModelBuilder {
+    /// builds a charging model from the `[charging]` section of the configuration,
+    /// loading the charging stations file it references.
+    pub fn build(config: &serde_json::Value) -> Result<ChargingModel, CompassConfigurationError> {
+        let scope = CompassConfigurationField::Charging.to_string();
+        let charging_config: ChargingModelConfig =
+            serde_json::from_value(config.clone()).map_err(|e| {
+                CompassConfigurationError::UserConfigurationError(format!(
+                    "failure reading {} configuration: {}",
+                    scope, e
+                ))
+            })?;
+        let file_path = &charging_config.stations_input_file;
+        let stations: Box<[ChargingStation]> = read_utils::from_csv(
+            &file_path.as_path(),
+            true,
+            Some(Bar::builder().desc("charging stations")),
+            None,
+        )
+        .map_err(|e| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "error reading charging stations from file {:?}: {}",
+                file_path, e
+            ))
+        })?;
+        let model = ChargingModel::new(&charging_config, stations.into_vec())
+            .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?;
+        log::info!(
+            "app charging model: {} stations, reserve {}%, target {}%",
+            model.stations.len(),
+            model.reserve_soc_percent,
+            model.target_soc_percent
+        );
+        Ok(model)
+    }
+}
@@ -17,6 +17,18 @@ pub struct CostModelService {
 }
 
 impl CostModelService {
+    /// query keys that replace the cost parameters of this service
+    const QUERY_COST_PARAMETERS: [&'static str; 3] =
+        ["weights", "vehicle_rates", "cost_aggregation"];
+
+    /// tests if the query sets any cost parameters, in which case its cost model
+    /// differs from the default cost model of this service
+    pub fn has_query_cost_parameters(query: &serde_json::Value) -> bool {
+        Self::QUERY_COST_PARAMETERS
+            .iter()
+            .any(|key| query.get(key).is_some())
+    }
+
     /// builds a CostModel based on the incoming query parameters along with the
     /// state variable names of the traversal model.
     ///
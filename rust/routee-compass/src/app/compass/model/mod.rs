@@ -1,5 +1,7 @@
 pub mod access_model;
+pub mod charging_model_builder;
 pub mod cost_model;
 pub mod frontier_model;
 pub mod termination_model_builder;
+pub mod transit_model_builder;
 pub mod traversal_model;
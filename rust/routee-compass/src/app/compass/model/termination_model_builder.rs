@@ -1,8 +1,9 @@
 use log;
+use routee_compass_core::algorithm::search::SearchAlgorithm;
 use routee_compass_core::config::{
     CompassConfigurationError, CompassConfigurationField, ConfigJsonExtensions,
 };
-use routee_compass_core::model::termination::TerminationModel;
+use routee_compass_core::model::termination::{TerminationBehavior, TerminationModel};
 use routee_compass_core::util::conversion::duration_extension::DurationExtension;
 
 pub struct TerminationModelBuilder {}
@@ -67,4 +68,26 @@ impl TerminationModelBuilder {
         log::info!("app termination model: {:?}", result);
         Ok(result)
     }
+
+    /// reads the optional `behavior` of the termination section, which decides whether
+    /// a terminated query fails or returns its partial result. defaults to failing.
+    /// partial results are rejected for search algorithms that do not produce them.
+    pub fn build_behavior(
+        config: &serde_json::Value,
+        search_algorithm: &SearchAlgorithm,
+    ) -> Result<TerminationBehavior, CompassConfigurationError> {
+        let scope = CompassConfigurationField::Termination.to_string();
+        let behavior = config
+            .get_config_serde_optional::<TerminationBehavior>(&"behavior", &scope)?
+            .unwrap_or_default();
+        if let TerminationBehavior::PartialResult = behavior {
+            if !search_algorithm.supports_partial_result() {
+                return Err(CompassConfigurationError::UserConfigurationError(String::from(
+                    "termination behavior 'partial_result' is only supported by the dijkstra, a* and anytime_a* search algorithms",
+                )));
+            }
+        }
+        log::info!("app termination behavior: {:?}", behavior);
+        Ok(behavior)
+    }
 }
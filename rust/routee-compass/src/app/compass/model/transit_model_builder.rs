@@ -0,0 +1,29 @@
+use routee_compass_core::config::CompassConfigurationError;
+use routee_compass_core::model::network::Graph;
+use routee_compass_core::model::transit::{TransitLayer, TransitLayerConfig};
+use std::sync::Arc;
+
+pub struct TransitModelBuilder {}
+
+impl TransitModelBuilder {
+    /// builds a transit layer from the `[graph.transit]` section of the configuration
+    /// and appends its stops and edges to the road graph.
+    pub fn build(
+        config: &serde_json::Value,
+        graph: &mut Graph,
+    ) -> Result<Arc<TransitLayer>, CompassConfigurationError> {
+        let transit_config: TransitLayerConfig =
+            serde_json::from_value(config.clone()).map_err(|e| {
+                CompassConfigurationError::UserConfigurationError(format!(
+                    "failure reading graph.transit configuration: {}",
+                    e
+                ))
+            })?;
+        let layer = TransitLayer::new(&transit_config, graph)
+            .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?;
+        layer
+            .extend_graph(graph)
+            .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?;
+        Ok(Arc::new(layer))
+    }
+}
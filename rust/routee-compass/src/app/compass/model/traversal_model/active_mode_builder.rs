@@ -0,0 +1,77 @@
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::TraversalModelBuilder;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::traversal::TraversalModelService;
+use routee_compass_core::model::unit::{
+    DistanceUnit, GradeUnit, TimeUnit, BASE_DISTANCE_UNIT, BASE_TIME_UNIT,
+};
+use routee_compass_powertrain::model::active_mode::ActiveMode;
+use routee_compass_powertrain::model::active_mode_service::ActiveModeService;
+use routee_compass_powertrain::model::elevation::ElevationConfig;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::grade_table_cache::GradeTableCache;
+
+pub struct ActiveModeBuilder {
+    /// shared with the energy model builder, so both models use one copy of a grade table
+    pub grade_tables: Rc<GradeTableCache>,
+}
+
+impl TraversalModelBuilder for ActiveModeBuilder {
+    fn build(
+        &self,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let parent_key = String::from("active mode traversal model");
+
+        let mode = params
+            .get_config_serde::<ActiveMode>(&"active_mode", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        mode.validate()?;
+
+        // grades are read the same way as the energy model, either from a grade table
+        // or derived from vertex elevations
+        let grade_table_path_option = params
+            .get_config_path_optional(&"grade_table_input_file", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let elevation_option = params
+            .get_config_serde_optional::<ElevationConfig>(&"elevation", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let grade_table_grade_unit = params
+            .get_config_serde_optional::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(GradeUnit::Decimal);
+        let edge_elevation_option = elevation_option
+            .map(|elevation| elevation.build())
+            .transpose()?;
+        let grade_table = self.grade_tables.get(
+            &grade_table_path_option,
+            grade_table_grade_unit,
+            edge_elevation_option.as_ref(),
+        )?;
+
+        let time_unit = params
+            .get_config_serde_optional::<TimeUnit>(&"time_unit", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(BASE_TIME_UNIT);
+        let distance_unit = params
+            .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(BASE_DISTANCE_UNIT);
+        let metabolic_energy = params
+            .get_config_serde_optional::<bool>(&"metabolic_energy", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+
+        let service = ActiveModeService {
+            mode,
+            grade_table,
+            grade_table_grade_unit,
+            time_unit,
+            distance_unit,
+            metabolic_energy,
+        };
+        Ok(Arc::new(service))
+    }
+}
@@ -0,0 +1,54 @@
+use itertools::Itertools;
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::{
+    default::CombinedTraversalModelService, TraversalModelBuilder, TraversalModelError,
+    TraversalModelService,
+};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+pub struct CombinedTraversalBuilder {
+    pub builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+}
+
+impl TraversalModelBuilder for CombinedTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let model_params = parameters
+            .get_config_array(&"traversal_models", &"combined")
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "unable to decode combined.traversal_models: {}",
+                    e
+                ))
+            })?;
+        if model_params.is_empty() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "combined.traversal_models must list at least one traversal model",
+            )));
+        }
+        let services = model_params
+            .iter()
+            .map(|params| {
+                let model_type = params
+                    .get_config_string(&"type", &"combined.traversal_models")
+                    .map_err(|e| {
+                        TraversalModelError::BuildError(format!(
+                            "unable to find 'type' of combined.traversal_model listing: {}",
+                            e
+                        ))
+                    })?;
+                let builder = self.builders.get(&model_type).ok_or_else(|| {
+                    let alts = self.builders.keys().join(",");
+                    TraversalModelError::BuildError(format!(
+                        "unregistered traversal model {}, should be one of: {{{}}}",
+                        model_type, alts
+                    ))
+                })?;
+                builder.build(params)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Arc::new(CombinedTraversalModelService::new(services)?))
+    }
+}
@@ -20,8 +20,14 @@ impl TraversalModelBuilder for DistanceTraversalBuilder {
             .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
         let distance_unit = distance_unit_option.unwrap_or(BASE_DISTANCE_UNIT);
-        let m: Arc<dyn TraversalModelService> =
-            Arc::new(DistanceTraversalService { distance_unit });
+        let precompute_edge_deltas = parameters
+            .get_config_serde_optional::<bool>(&"precompute_edge_deltas", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        let m: Arc<dyn TraversalModelService> = Arc::new(DistanceTraversalService {
+            distance_unit,
+            precompute_edge_deltas,
+        });
         Ok(m)
     }
 }
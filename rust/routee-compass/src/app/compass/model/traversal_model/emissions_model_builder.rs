@@ -0,0 +1,61 @@
+use itertools::Itertools;
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::TraversalModelBuilder;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::traversal::TraversalModelService;
+use routee_compass_powertrain::model::emission_factors::EmissionFactorsConfig;
+use routee_compass_powertrain::model::emissions_model_service::EmissionsModelService;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub struct EmissionsModelBuilder {
+    energy_models: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+}
+
+impl EmissionsModelBuilder {
+    pub fn new(
+        energy_models: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    ) -> EmissionsModelBuilder {
+        EmissionsModelBuilder { energy_models }
+    }
+}
+
+impl TraversalModelBuilder for EmissionsModelBuilder {
+    fn build(
+        &self,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let parent_key = String::from("emissions traversal model");
+
+        // load the underlying energy model
+        let energy_model_params = params.get("energy_model").ok_or_else(|| {
+            TraversalModelError::BuildError(format!(
+                "{} missing energy_model parameters",
+                parent_key
+            ))
+        })?;
+        let energy_model_type = energy_model_params
+            .get_config_string(&"type", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let energy_builder = self.energy_models.get(&energy_model_type).ok_or_else(|| {
+            let valid_models = self.energy_models.keys().join(",");
+            TraversalModelError::BuildError(format!(
+                "unknown energy_model {}, must be one of [{}]",
+                energy_model_type, valid_models
+            ))
+        })?;
+        let energy_model_service = energy_builder.build(energy_model_params)?;
+
+        let emission_factors = params
+            .get_config_serde::<EmissionFactorsConfig>(&"emission_factors", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .build()?;
+
+        let service = EmissionsModelService {
+            energy_model_service,
+            emission_factors: Arc::new(emission_factors),
+        };
+        Ok(Arc::new(service))
+    }
+}
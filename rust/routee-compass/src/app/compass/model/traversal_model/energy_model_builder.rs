@@ -4,20 +4,29 @@ use routee_compass_core::model::traversal::TraversalModelBuilder;
 use routee_compass_core::model::traversal::TraversalModelError;
 use routee_compass_core::model::traversal::TraversalModelService;
 use routee_compass_core::model::unit::{DistanceUnit, GradeUnit, SpeedUnit, TimeUnit};
+use routee_compass_powertrain::model::elevation::ElevationConfig;
 use routee_compass_powertrain::model::energy_model_service::EnergyModelService;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use super::energy_model_vehicle_builders::VehicleBuilder;
+use super::grade_table_cache::GradeTableCache;
 
 pub struct EnergyModelBuilder {
     time_models: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    grade_tables: Rc<GradeTableCache>,
 }
 
 impl EnergyModelBuilder {
-    pub fn new(time_models: HashMap<String, Rc<dyn TraversalModelBuilder>>) -> EnergyModelBuilder {
-        EnergyModelBuilder { time_models }
+    pub fn new(
+        time_models: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+        grade_tables: Rc<GradeTableCache>,
+    ) -> EnergyModelBuilder {
+        EnergyModelBuilder {
+            time_models,
+            grade_tables,
+        }
     }
 }
 
@@ -53,9 +62,28 @@ impl TraversalModelBuilder for EnergyModelBuilder {
         let grade_table_path_option = params
             .get_config_path_optional(&"grade_table_input_file", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
-        let grade_table_grade_unit = params
-            .get_config_serde::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
+        // grades may instead be derived from vertex elevations, in which case the
+        // grade unit is optional
+        let elevation_option = params
+            .get_config_serde_optional::<ElevationConfig>(&"elevation", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let grade_table_grade_unit = match elevation_option {
+            None => params
+                .get_config_serde::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?,
+            Some(_) => params
+                .get_config_serde_optional::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+                .unwrap_or(GradeUnit::Decimal),
+        };
+        let edge_elevation_option = elevation_option
+            .map(|elevation| elevation.build())
+            .transpose()?;
+        let grade_table = self.grade_tables.get(
+            &grade_table_path_option,
+            grade_table_grade_unit,
+            edge_elevation_option.as_ref(),
+        )?;
 
         let vehicle_configs = params
             .get_config_array(&"vehicles", &parent_key)
@@ -86,8 +114,9 @@ impl TraversalModelBuilder for EnergyModelBuilder {
         let service = EnergyModelService::new(
             time_model_service,
             time_model_speed_unit,
-            &grade_table_path_option,
+            grade_table,
             grade_table_grade_unit,
+            edge_elevation_option,
             time_unit_option,
             distance_unit_option,
             vehicle_library,
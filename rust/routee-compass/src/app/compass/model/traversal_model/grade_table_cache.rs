@@ -0,0 +1,76 @@
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::unit::{Grade, GradeUnit};
+use routee_compass_powertrain::model::elevation::EdgeElevation;
+use routee_compass_powertrain::model::energy_model_ops::read_grade_table;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+type GradeTable = Arc<Option<Box<[Grade]>>>;
+
+/// grade tables read by the energy and active mode builders, keyed by grade table file,
+/// so that models configured with the same file share one copy of the table. grades
+/// derived from elevations are not cached.
+#[derive(Default)]
+pub struct GradeTableCache {
+    tables: RefCell<HashMap<PathBuf, GradeTable>>,
+}
+
+impl GradeTableCache {
+    /// gets the grade table for the configured grade table file or elevations, reading
+    /// the file on first use. see [`read_grade_table`].
+    pub fn get<P: AsRef<Path>>(
+        &self,
+        grade_table_path_option: &Option<P>,
+        grade_table_grade_unit: GradeUnit,
+        edge_elevation_option: Option<&EdgeElevation>,
+    ) -> Result<GradeTable, TraversalModelError> {
+        let path = match (grade_table_path_option, edge_elevation_option) {
+            (Some(path), None) => path.as_ref().to_path_buf(),
+            _ => {
+                let table = read_grade_table(
+                    grade_table_path_option,
+                    grade_table_grade_unit,
+                    edge_elevation_option,
+                )?;
+                return Ok(Arc::new(table));
+            }
+        };
+        if let Some(table) = self.tables.borrow().get(&path) {
+            return Ok(table.clone());
+        }
+        let table = Arc::new(read_grade_table(
+            &Some(&path),
+            grade_table_grade_unit,
+            None,
+        )?);
+        self.tables.borrow_mut().insert(path, table.clone());
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_grade_table() {
+        let grade_file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("routee-compass-powertrain")
+            .join("src")
+            .join("model")
+            .join("test")
+            .join("grades.txt");
+        let cache = GradeTableCache::default();
+        let energy = cache
+            .get(&Some(&grade_file_path), GradeUnit::Millis, None)
+            .unwrap();
+        let active_mode = cache
+            .get(&Some(&grade_file_path), GradeUnit::Millis, None)
+            .unwrap();
+        assert!(energy.is_some());
+        assert!(Arc::ptr_eq(&energy, &active_mode));
+    }
+}
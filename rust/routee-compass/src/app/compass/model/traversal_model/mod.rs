@@ -1,4 +1,11 @@
+pub mod active_mode_builder;
+pub mod combined_traversal_builder;
 pub mod distance_traversal_builder;
+pub mod emissions_model_builder;
 pub mod energy_model_builder;
 pub mod energy_model_vehicle_builders;
+pub mod grade_table_cache;
 pub mod speed_lookup_builder;
+pub mod time_dependent_speed_builder;
+pub mod toll_traversal_builder;
+pub mod transit_traversal_builder;
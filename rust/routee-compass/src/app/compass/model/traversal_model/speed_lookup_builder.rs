@@ -30,8 +30,16 @@ impl TraversalModelBuilder for SpeedLookupBuilder {
             .get_config_serde_optional::<TimeUnit>(&"time_unit", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
+        let precompute_edge_deltas = params
+            .get_config_serde_optional::<bool>(&"precompute_edge_deltas", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+
         let e = SpeedTraversalEngine::new(&filename, speed_unit, distance_unit, time_unit)?;
-        let service = Arc::new(SpeedLookupService { e: Arc::new(e) });
+        let service = Arc::new(SpeedLookupService {
+            e: Arc::new(e),
+            precompute_edge_deltas,
+        });
         Ok(service)
     }
 }
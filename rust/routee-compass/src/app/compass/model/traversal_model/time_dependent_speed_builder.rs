@@ -0,0 +1,49 @@
+use routee_compass_core::config::CompassConfigurationField;
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::default::TimeDependentSpeedEngine;
+use routee_compass_core::model::traversal::default::TimeDependentSpeedService;
+use routee_compass_core::model::traversal::TraversalModelBuilder;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::traversal::TraversalModelService;
+use routee_compass_core::model::unit::{DistanceUnit, SpeedUnit, TimeUnit};
+use std::sync::Arc;
+
+/// default width of a speed profile time bin
+const DEFAULT_BIN_MINUTES: u64 = 15;
+
+pub struct TimeDependentSpeedBuilder {}
+
+impl TraversalModelBuilder for TimeDependentSpeedBuilder {
+    fn build(
+        &self,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let traversal_key = CompassConfigurationField::Traversal.to_string();
+        let filename = params
+            .get_config_path(&"speed_profile_input_file", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let speed_unit = params
+            .get_config_serde::<SpeedUnit>(&"speed_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let bin_minutes = params
+            .get_config_serde_optional::<u64>(&"bin_minutes", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(DEFAULT_BIN_MINUTES);
+        let distance_unit = params
+            .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let time_unit = params
+            .get_config_serde_optional::<TimeUnit>(&"time_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+        let e = TimeDependentSpeedEngine::new(
+            &filename,
+            speed_unit,
+            bin_minutes,
+            distance_unit,
+            time_unit,
+        )?;
+        let service = Arc::new(TimeDependentSpeedService { e: Arc::new(e) });
+        Ok(service)
+    }
+}
@@ -0,0 +1,31 @@
+use routee_compass_core::config::CompassConfigurationField;
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::traversal::default::TollTraversalEngine;
+use routee_compass_core::model::traversal::default::TollTraversalService;
+use routee_compass_core::model::traversal::TraversalModelBuilder;
+use routee_compass_core::model::traversal::TraversalModelError;
+use routee_compass_core::model::traversal::TraversalModelService;
+use routee_compass_core::model::unit::CurrencyUnit;
+use std::sync::Arc;
+
+pub struct TollTraversalBuilder {}
+
+impl TraversalModelBuilder for TollTraversalBuilder {
+    fn build(
+        &self,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let traversal_key = CompassConfigurationField::Traversal.to_string();
+        let filename = params
+            .get_config_path(&"toll_input_file", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let currency_unit = params
+            .get_config_serde_optional::<CurrencyUnit>(&"currency_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(CurrencyUnit::Dollars);
+
+        let e = TollTraversalEngine::new(&filename, currency_unit)?;
+        let service = Arc::new(TollTraversalService { e: Arc::new(e) });
+        Ok(service)
+    }
+}
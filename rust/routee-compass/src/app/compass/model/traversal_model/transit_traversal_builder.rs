@@ -0,0 +1,52 @@
+use itertools::Itertools;
+use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::transit::{TransitLayer, TransitTraversalService};
+use routee_compass_core::model::traversal::{
+    TraversalModelBuilder, TraversalModelError, TraversalModelService,
+};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+/// builds the transit traversal model, which wraps a road traversal model listed
+/// under the `road_model` key. only available when the graph has a transit layer.
+pub struct TransitTraversalBuilder {
+    pub layer: Arc<TransitLayer>,
+    pub builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+}
+
+impl TraversalModelBuilder for TransitTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let parent_key = String::from("transit traversal model");
+        let road_params = parameters.get("road_model").ok_or_else(|| {
+            TraversalModelError::BuildError(format!("{} missing road_model parameters", parent_key))
+        })?;
+        let road_model_type = road_params
+            .get_config_string(&"type", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let road_builder = self.builders.get(&road_model_type).ok_or_else(|| {
+            let alts = self.builders.keys().join(",");
+            TraversalModelError::BuildError(format!(
+                "unregistered traversal model {}, should be one of: {{{}}}",
+                road_model_type, alts
+            ))
+        })?;
+        let road_model_service = road_builder.build(road_params)?;
+        let connector_speed_kph = parameters
+            .get_config_serde_optional::<f64>(&"connector_speed_kph", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(5.0);
+        if connector_speed_kph.is_nan() || connector_speed_kph <= 0.0 {
+            return Err(TraversalModelError::BuildError(format!(
+                "connector_speed_kph must be positive, found {}",
+                connector_speed_kph
+            )));
+        }
+        Ok(Arc::new(TransitTraversalService {
+            road_model_service,
+            layer: self.layer.clone(),
+            connector_speed_kph,
+        }))
+    }
+}
@@ -5,4 +5,4 @@ mod search_app_result;
 
 pub use search_app::SearchApp;
 pub use search_app_graph_ops::SearchAppGraphOps;
-pub use search_app_result::SearchAppResult;
+pub use search_app_result::{RouteLeg, SearchAppMatrixResult, SearchAppResult};
@@ -1,18 +1,36 @@
-use super::{search_app_ops, search_app_result::SearchAppResult};
+use super::{
+    search_app_ops,
+    search_app_result::{RouteLeg, SearchAppMatrixResult, SearchAppResult},
+};
 use crate::{
     app::compass::{model::cost_model::cost_model_service::CostModelService, CompassAppError},
-    plugin::{input::InputJsonExtensions, PluginError},
+    plugin::{
+        input::{InputField, InputJsonExtensions, InputPluginError},
+        PluginError,
+    },
 };
 use chrono::Local;
 use routee_compass_core::{
-    algorithm::search::{Direction, SearchAlgorithm, SearchError, SearchInstance},
+    algorithm::search::{
+        a_star, a_star::multi_source_a_star_algorithm, backtrack,
+        many_to_many::many_to_many_algorithm, resource_constrained::ResourceConstraint,
+        tsp::tsp_algorithm, Direction, EdgeTraversal, PartialSearchResult, SearchAlgorithm,
+        SearchAlgorithmResult, SearchError, SearchInstance,
+    },
     model::{
-        access::AccessModelService, frontier::FrontierModelService, map::MapModel,
-        network::graph::Graph, state::StateModel, termination::TerminationModel,
-        traversal::TraversalModelService,
+        access::AccessModelService,
+        charging::{ChargingModel, ChargingStop},
+        frontier::FrontierModelService,
+        map::MapModel,
+        network::{graph::Graph, vertex_id::VertexId},
+        state::{StateModel, StateVariable},
+        termination::{TerminationBehavior, TerminationModel, TerminationModelError},
+        traversal::{EdgeDeltaTable, TraversalModelService},
+        unit::Cost,
     },
 };
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
 use std::time;
 
 /// a configured and loaded application to execute searches.
@@ -22,10 +40,16 @@ pub struct SearchApp {
     pub map_model: Arc<MapModel>,
     pub state_model: Arc<StateModel>,
     pub traversal_model_service: Arc<dyn TraversalModelService>,
+    pub traversal_config: Arc<serde_json::Value>,
+    pub edge_delta_table: Option<Arc<EdgeDeltaTable>>,
     pub access_model_service: Arc<dyn AccessModelService>,
     pub cost_model_service: Arc<CostModelService>,
     pub frontier_model_service: Arc<dyn FrontierModelService>,
     pub termination_model: Arc<TerminationModel>,
+    pub termination_behavior: TerminationBehavior,
+    pub charging_model: Option<Arc<ChargingModel>>,
+    /// preprocessing info of queries using the default cost model, set by the first one
+    default_preprocessing_info: OnceLock<Arc<serde_json::Value>>,
 }
 
 impl SearchApp {
@@ -38,10 +62,14 @@ impl SearchApp {
         map_model: Arc<MapModel>,
         state_model: Arc<StateModel>,
         traversal_model_service: Arc<dyn TraversalModelService>,
+        traversal_config: serde_json::Value,
+        edge_delta_table: Option<Arc<EdgeDeltaTable>>,
         access_model_service: Arc<dyn AccessModelService>,
         cost_model_service: CostModelService,
         frontier_model_service: Arc<dyn FrontierModelService>,
         termination_model: TerminationModel,
+        termination_behavior: TerminationBehavior,
+        charging_model: Option<ChargingModel>,
     ) -> Self {
         SearchApp {
             search_algorithm,
@@ -49,10 +77,15 @@ impl SearchApp {
             map_model,
             state_model,
             traversal_model_service,
+            traversal_config: Arc::new(traversal_config),
+            edge_delta_table,
             access_model_service,
             cost_model_service: Arc::new(cost_model_service),
             frontier_model_service,
             termination_model: Arc::new(termination_model),
+            termination_behavior,
+            charging_model: charging_model.map(Arc::new),
+            default_preprocessing_info: OnceLock::new(),
         }
     }
 
@@ -60,6 +93,10 @@ impl SearchApp {
     /// search assets and then executes a search. if a destination is set on the query, then the
     /// route is computed. if the algorithm produces more than one route, then the result contains
     /// each route. the SearchAlgorithm determines the order and number of routes and trees in the result.
+    /// if the query lists stops instead of an origin, the stops are visited in their least-cost order.
+    /// if the query enables charging stops, they are inserted wherever the battery would otherwise
+    /// fall below its reserve. if the termination model interrupts the search, the termination
+    /// behavior decides whether the query fails or returns its partial result.
     ///
     /// # Arguments
     ///
@@ -74,10 +111,122 @@ impl SearchApp {
     ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
         let search_start_time = Local::now();
         let si = self.build_search_instance(query)?;
-        self.map_model.map_match(query, &si)?;
+        let search_result = match query.get_stops()? {
+            Some(stops) => self
+                .run_stop_order(query, &stops, &si)
+                .map(|(results, legs, order)| (results, legs, Some(order), vec![]))
+                .map_err(|e| self.without_partial_result(e, "stop order")),
+            None if query.get_insert_charging_stops()? => {
+                self.map_model.map_match(query, &si)?;
+                self.run_charging(query, &si)
+                    .map(|(results, legs, charging_stops)| (results, legs, None, charging_stops))
+                    .map_err(|e| self.without_partial_result(e, "charging stop"))
+            }
+            None => {
+                self.map_model.map_match(query, &si)?;
+                self.run_route(query, &si)
+                    .map(|(results, legs)| (results, legs, None, vec![]))
+            }
+        };
+        let (results, legs, stop_order, charging_stops, termination) = match search_result {
+            Ok((results, legs, stop_order, charging_stops)) => {
+                (results, legs, stop_order, charging_stops, None)
+            }
+            Err(CompassAppError::SearchFailure(SearchError::QueryTerminatedWithPartialResult(
+                partial,
+            ))) => {
+                let (results, explanation) = self.partial_result(query, *partial)?;
+                (results, vec![], None, vec![], Some(explanation))
+            }
+            Err(e) => return Err(e),
+        };
+
+        let search_end_time = Local::now();
+        let search_runtime = (search_end_time - search_start_time)
+            .to_std()
+            .unwrap_or(time::Duration::ZERO);
+
+        log::debug!(
+            "Search Completed in {:?} miliseconds",
+            search_runtime.as_millis()
+        );
+
+        let result = SearchAppResult {
+            routes: results.routes,
+            trees: results.trees,
+            search_executed_time: search_start_time.to_rfc3339(),
+            search_runtime,
+            iterations: results.iterations,
+            legs,
+            stop_order,
+            charging_stops,
+            suboptimality_bound: results.suboptimality_bound,
+            termination,
+        };
+
+        Ok((result, si))
+    }
+
+    /// runs a matrix query, which finds the least-cost path between every origin and every
+    /// destination listed on the query. the locations are matched to the map and all
+    /// searches share a single search instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON matrix query provided by the user
+    ///
+    /// # Results
+    ///
+    /// The origin-destination matrix and the search assets for this run.
+    pub fn run_matrix(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<(SearchAppMatrixResult, SearchInstance), CompassAppError> {
+        let search_start_time = Local::now();
+        let origin_list =
+            query
+                .get_origin_list()?
+                .ok_or(InputPluginError::MissingExpectedQueryField(
+                    InputField::OriginList,
+                ))?;
+        let destination_list =
+            query
+                .get_destination_list()?
+                .ok_or(InputPluginError::MissingExpectedQueryField(
+                    InputField::DestinationList,
+                ))?;
+        let include_routes = query.get_include_routes()?;
+        let si = self.build_search_instance(query)?;
+        let origins = search_app_ops::match_matrix_locations(&origin_list, true, "matrix", &si)?;
+        let destinations =
+            search_app_ops::match_matrix_locations(&destination_list, false, "matrix", &si)?;
+
+        let matrix = many_to_many_algorithm::run_vertex_oriented(
+            &origins,
+            &destinations,
+            include_routes,
+            &si,
+        )?;
+
+        let search_runtime = (Local::now() - search_start_time)
+            .to_std()
+            .unwrap_or(time::Duration::ZERO);
+        let result = SearchAppMatrixResult {
+            matrix,
+            search_executed_time: search_start_time.to_rfc3339(),
+            search_runtime,
+        };
+        Ok((result, si))
+    }
 
+    /// runs a search rooted at the origin that expands forward toward the destination.
+    fn run_forward(
+        &self,
+        query: &serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
         // depending on the presence of an origin edge or origin vertex, we run each type of query
-        let results = if query.get_origin_edge().is_ok() {
+        if query.get_origin_edge().is_ok() {
             let o = query.get_origin_edge().map_err(|e| {
                 CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
             })?;
@@ -85,7 +234,7 @@ impl SearchApp {
                 CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
             })?;
             self.search_algorithm
-                .run_edge_oriented(o, d_opt, query, &Direction::Forward, &si)
+                .run_edge_oriented(o, d_opt, query, &Direction::Forward, si)
                 .map_err(CompassAppError::SearchFailure)
         } else if query.get_origin_vertex().is_ok() {
             let o = query.get_origin_vertex().map_err(|e| {
@@ -96,31 +245,513 @@ impl SearchApp {
             })?;
 
             self.search_algorithm
-                .run_vertex_oriented(o, d, query, &Direction::Forward, &si)
+                .run_vertex_oriented(o, d, query, &Direction::Forward, si)
                 .map_err(CompassAppError::SearchFailure)
         } else {
             Err(CompassAppError::CompassFailure(String::from("SearchApp.run called with query that lacks origin_edge and origin_vertex, at least one required")))
-        }?;
+        }
+    }
 
-        let search_end_time = Local::now();
-        let search_runtime = (search_end_time - search_start_time)
-            .to_std()
-            .unwrap_or(time::Duration::ZERO);
+    /// runs a route query between the origin and optional destination of a map-matched
+    /// query, in the query direction, visiting any waypoints along the way. forward queries
+    /// with snapping candidates search between all candidates at once, and other queries
+    /// with snapping candidates fail.
+    fn run_route(
+        &self,
+        query: &serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<(SearchAlgorithmResult, Vec<RouteLeg>), CompassAppError> {
+        let direction = query.get_direction().map_err(|e| {
+            CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
+        })?;
+        let waypoints = query.get_waypoints()?;
+        let result = match (waypoints, direction) {
+            (None, Direction::Forward) => match search_app_ops::candidate_endpoints(query, si)? {
+                Some((sources, targets)) => (
+                    self.run_candidates(&sources, &targets, si)
+                        .map_err(|e| self.without_partial_result(e, "snapping candidate"))?,
+                    vec![],
+                ),
+                None => (self.run_forward(query, si)?, vec![]),
+            },
+            (None, Direction::Reverse) => {
+                search_app_ops::reject_candidates(query, "reverse")?;
+                (self.run_reverse(query, si)?, vec![])
+            }
+            (Some(waypoints), Direction::Forward) => {
+                search_app_ops::reject_candidates(query, "waypoint")?;
+                self.run_waypoints(query, &waypoints, si)
+                    .map_err(|e| self.without_partial_result(e, "waypoint"))?
+            }
+            (Some(_), Direction::Reverse) => {
+                return Err(CompassAppError::CompassFailure(String::from(
+                    "waypoint queries do not support reverse direction",
+                )))
+            }
+        };
+        Ok(result)
+    }
 
-        log::debug!(
-            "Search Completed in {:?} miliseconds",
-            search_runtime.as_millis()
-        );
+    /// runs a stop order query, which visits an unordered set of stops in the order of
+    /// least cost. the costs between every pair of stops are found with a many-to-many
+    /// search and the order is solved over those costs. the stops are then routed in
+    /// order as a multi-stop query.
+    ///
+    /// the query may fix the stops that begin and end the tour by index. a tour that
+    /// begins and ends at the same stop is a round trip. otherwise, unfixed ends are open.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the user query
+    /// * `stops` - the stops listed on the query
+    /// * `si`    - the search instance
+    ///
+    /// # Results
+    ///
+    /// The route visiting each stop, the span of each leg, and the order of the stops
+    fn run_stop_order(
+        &self,
+        query: &serde_json::Value,
+        stops: &[serde_json::Value],
+        si: &SearchInstance,
+    ) -> Result<(SearchAlgorithmResult, Vec<RouteLeg>, Vec<usize>), CompassAppError> {
+        if let Direction::Reverse = query.get_direction()? {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "stop order queries do not support reverse direction",
+            )));
+        }
+        if stops.is_empty() {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "stop order query requires at least one stop",
+            )));
+        }
+        let start = query.get_tour_start()?;
+        let end = query.get_tour_end()?;
+        let vertices = search_app_ops::match_matrix_locations(stops, false, "stop order", si)?;
+        let matrix = many_to_many_algorithm::run_vertex_oriented(&vertices, &vertices, false, si)?;
+        let solution = tsp_algorithm::run(&matrix, start, end)?;
 
-        let result = SearchAppResult {
-            routes: results.routes,
-            trees: results.trees,
-            search_executed_time: search_start_time.to_rfc3339(),
-            search_runtime,
-            iterations: results.iterations,
+        let route_query = search_app_ops::stop_order_query(query, &vertices, &solution.order)?;
+        let waypoints = route_query.get_waypoints()?.unwrap_or_default();
+        let (mut result, legs) = self.run_waypoints(&route_query, &waypoints, si)?;
+        result.iterations += matrix.iterations;
+        Ok((result, legs, solution.order))
+    }
+
+    /// runs a multi-stop query leg by leg, visiting each waypoint in order between the
+    /// origin and destination. each leg starts from the final state of the previous leg,
+    /// so accumulated features such as time and energy continue across stops. the legs
+    /// are concatenated into a single route. the trees of every leg are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`     - the map-matched user query
+    /// * `waypoints` - the waypoints listed on the query
+    /// * `si`        - the search instance of the first leg
+    ///
+    /// # Results
+    ///
+    /// The concatenated route along with the span of each leg within it
+    fn run_waypoints(
+        &self,
+        query: &serde_json::Value,
+        waypoints: &[serde_json::Value],
+        si: &SearchInstance,
+    ) -> Result<(SearchAlgorithmResult, Vec<RouteLeg>), CompassAppError> {
+        let leg_queries = search_app_ops::waypoint_leg_queries(query, waypoints, si)?;
+        let mut route: Vec<EdgeTraversal> = vec![];
+        let mut trees = vec![];
+        let mut legs = Vec::with_capacity(leg_queries.len());
+        let mut iterations = 0;
+        for (leg_idx, leg_query) in leg_queries.iter().enumerate() {
+            let leg_initial_state = match route.last() {
+                None => si.state_model.initial_state()?,
+                Some(last) => last.result_state.clone(),
+            };
+            let leg_si = search_app_ops::continue_search_instance(si, &leg_initial_state)?;
+            let result = self.run_forward(leg_query, &leg_si)?;
+            iterations += result.iterations;
+            trees.extend(result.trees);
+
+            let mut leg_route = match result.routes.into_iter().next() {
+                Some(leg_route) => leg_route,
+                None if leg_has_same_stops(leg_query) => vec![],
+                None => {
+                    return Err(CompassAppError::CompassFailure(format!(
+                        "no route found for leg {} of waypoint query",
+                        leg_idx
+                    )))
+                }
+            };
+            // edge-oriented legs begin on the edge where the previous leg ended
+            if let (Some(prev), Some(next)) = (route.last(), leg_route.first()) {
+                if prev.edge_id == next.edge_id {
+                    leg_route.remove(0);
+                }
+            }
+            let start_index = route.len();
+            route.extend(leg_route);
+            legs.push(RouteLeg {
+                start_index,
+                end_index: route.len(),
+            });
+        }
+
+        let result = SearchAlgorithmResult {
+            trees,
+            routes: vec![route],
+            iterations,
+            suboptimality_bound: None,
         };
+        Ok((result, legs))
+    }
 
-        Ok((result, si))
+    /// runs a route query that inserts charging stops so that the battery state of charge
+    /// never falls below the reserve of the charging model. the route to the destination is
+    /// searched with a* under the reserve constraint. when no feasible route exists, the
+    /// vehicle detours to the reachable charging station that minimizes the cost to the
+    /// station, the cost of charging there and the estimated cost from the station to the
+    /// destination, charges there, and the search continues from the station with the
+    /// charged state. the stations are found by a search that stops once every unvisited
+    /// station is settled. each station is visited at most once. the cost of a charging
+    /// stop is added to the traversal cost of the edge arriving at the station. the
+    /// termination model bounds the runtime and iterations of all searches combined.
+    ///
+    /// the search is vertex-oriented. an origin edge is entered from its source vertex and
+    /// a destination edge is exited at its destination vertex.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the map-matched user query
+    /// * `si`    - the search instance
+    ///
+    /// # Results
+    ///
+    /// The route, the span of each leg between charging stops, and each charging stop
+    fn run_charging(
+        &self,
+        query: &serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<(SearchAlgorithmResult, Vec<RouteLeg>, Vec<ChargingStop>), CompassAppError> {
+        let charging_model = self.charging_model.as_ref().ok_or_else(|| {
+            CompassAppError::CompassFailure(String::from(
+                "query enables charging stops but no [charging] model is configured",
+            ))
+        })?;
+        if let Direction::Reverse = query.get_direction()? {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "charging stop queries do not support reverse direction",
+            )));
+        }
+        if query.get_waypoints()?.is_some() {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "charging stop queries do not support waypoints",
+            )));
+        }
+        search_app_ops::reject_candidates(query, "charging stop")?;
+        let (origin, destination) = charging_endpoints(query, si)?;
+        let reserve = vec![ResourceConstraint {
+            feature: charging_model.soc_feature.clone(),
+            min: Some(charging_model.reserve_soc_percent),
+            max: None,
+        }];
+
+        let mut route: Vec<EdgeTraversal> = vec![];
+        let mut trees = vec![];
+        let mut legs = vec![];
+        let mut charging_stops = vec![];
+        let mut visited: HashSet<VertexId> = HashSet::new();
+        let start_time = time::Instant::now();
+        let mut iterations = 0;
+        let mut current = origin;
+        let mut state = si.state_model.initial_state()?;
+        loop {
+            let leg_si = search_app_ops::continue_search_instance(si, &state)?;
+            let direct = a_star::run_vertex_oriented_since(
+                current,
+                Some(destination),
+                &Direction::Forward,
+                None,
+                None,
+                &reserve,
+                &start_time,
+                iterations,
+                &leg_si,
+            );
+            let start_index = route.len();
+            match direct {
+                Ok(result) => {
+                    iterations += result.iterations;
+                    route.extend(backtrack::vertex_oriented_route(
+                        current,
+                        destination,
+                        &result.tree,
+                    )?);
+                    trees.push(result.tree);
+                    legs.push(RouteLeg {
+                        start_index,
+                        end_index: route.len(),
+                    });
+                    break;
+                }
+                Err(SearchError::NoPathExistsBetweenVertices(_, _)) => {}
+                Err(e) => return Err(CompassAppError::SearchFailure(e)),
+            }
+
+            // no feasible route remains, so detour to the most promising reachable station
+            let unvisited: HashSet<VertexId> = charging_model
+                .stations
+                .keys()
+                .filter(|station| !visited.contains(station))
+                .cloned()
+                .collect();
+            let reachable = a_star::run_vertex_oriented_to_targets(
+                current,
+                &unvisited,
+                &reserve,
+                &start_time,
+                iterations,
+                &leg_si,
+            )?;
+            iterations += reachable.iterations;
+            let mut best: Option<(Cost, Vec<EdgeTraversal>)> = None;
+            for station in unvisited.iter() {
+                if !reachable.tree.contains_key(station) {
+                    continue;
+                }
+                let station_route =
+                    backtrack::vertex_oriented_route(current, *station, &reachable.tree)?;
+                let arrival_state = match station_route.last() {
+                    Some(last) => &last.result_state,
+                    None => continue,
+                };
+                let cost_to_station: Cost = station_route.iter().map(|e| e.total_cost()).sum();
+                let mut departure_state = arrival_state.clone();
+                let (_, charging_cost) =
+                    charge_at_station(charging_model, *station, 0, &mut departure_state, si)?;
+                let estimate =
+                    si.estimate_traversal_cost(*station, destination, &departure_state)?;
+                let score = cost_to_station + charging_cost + estimate;
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, _)| score < *best_score)
+                {
+                    best = Some((score, station_route));
+                }
+            }
+            trees.push(reachable.tree);
+            let (_, mut station_route) = best.ok_or_else(|| {
+                CompassAppError::CompassFailure(format!(
+                    "no reachable charging station from vertex {} without falling below {}% {}",
+                    current, charging_model.reserve_soc_percent, charging_model.soc_feature
+                ))
+            })?;
+
+            let route_index = start_index + station_route.len();
+            let arrival = station_route.last_mut().ok_or_else(|| {
+                CompassAppError::InternalError(String::from("charging station route is empty"))
+            })?;
+            current = si.graph.dst_vertex_id(&arrival.edge_id)?;
+            visited.insert(current);
+            state = arrival.result_state.clone();
+            let (stop, charging_cost) =
+                charge_at_station(charging_model, current, route_index, &mut state, si)?;
+            arrival.traversal_cost = arrival.traversal_cost + charging_cost;
+            route.extend(station_route);
+            legs.push(RouteLeg {
+                start_index,
+                end_index: route.len(),
+            });
+            charging_stops.push(stop);
+        }
+
+        let result = SearchAlgorithmResult {
+            trees,
+            routes: vec![route],
+            iterations,
+            suboptimality_bound: None,
+        };
+        Ok((result, legs, charging_stops))
+    }
+
+    /// runs a single search from every origin candidate to every destination candidate
+    /// of a map-matched query, which finds the least-cost route including the access
+    /// cost of the candidates it connects. the search is a multi-source a* that is used
+    /// in place of the configured search algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - the origin candidates and their access costs
+    /// * `targets` - the destination candidates and their access costs
+    /// * `si`      - the search instance
+    ///
+    /// # Results
+    ///
+    /// The route between the chosen candidates and the search tree
+    fn run_candidates(
+        &self,
+        sources: &[(VertexId, Cost)],
+        targets: &[(VertexId, Cost)],
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
+        let result =
+            multi_source_a_star_algorithm::run_vertex_oriented(sources, targets, None, si)?;
+        let route = backtrack::vertex_oriented_route(result.source, result.target, &result.tree)?;
+        Ok(SearchAlgorithmResult {
+            trees: vec![result.tree],
+            routes: vec![route],
+            iterations: result.iterations,
+            suboptimality_bound: None,
+        })
+    }
+
+    /// runs a search rooted at the destination that expands backward over incoming edges,
+    /// which answers "arrive by" queries. routes are re-oriented into forward order so that
+    /// state accumulates from the origin. when the query has no destination, the search is
+    /// rooted at the origin and the tree describes every vertex that can reach it.
+    fn run_reverse(
+        &self,
+        query: &serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
+        if !self.search_algorithm.supports_reverse_search() {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "configured search algorithm does not support reverse direction queries",
+            )));
+        }
+
+        // find the root and target of the reverse search along with any origin and
+        // destination edges that must be re-attached to the re-oriented route
+        let (root, target, edges) = if query.get_origin_edge().is_ok() {
+            let o = query.get_origin_edge().map_err(|e| {
+                CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
+            })?;
+            let d_opt = query.get_destination_edge().map_err(|e| {
+                CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
+            })?;
+            match d_opt {
+                None => (si.graph.src_vertex_id(&o)?, None, None),
+                Some(d) if d == o => return Ok(SearchAlgorithmResult::default()),
+                Some(d) => (
+                    si.graph.src_vertex_id(&d)?,
+                    Some(si.graph.dst_vertex_id(&o)?),
+                    Some((o, d)),
+                ),
+            }
+        } else if query.get_origin_vertex().is_ok() {
+            let o = query.get_origin_vertex().map_err(|e| {
+                CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
+            })?;
+            let d_opt = query.get_destination_vertex().map_err(|e| {
+                CompassAppError::PluginError(PluginError::InputPluginFailed { source: e })
+            })?;
+            match d_opt {
+                None => (o, None, None),
+                Some(d) => (d, Some(o), None),
+            }
+        } else {
+            return Err(CompassAppError::CompassFailure(String::from("SearchApp.run called with query that lacks origin_edge and origin_vertex, at least one required")));
+        };
+
+        let SearchAlgorithmResult {
+            trees,
+            routes,
+            iterations,
+            suboptimality_bound,
+        } = self.search_algorithm.run_vertex_oriented(
+            root,
+            target,
+            query,
+            &Direction::Reverse,
+            si,
+        )?;
+        let routes = routes
+            .iter()
+            .map(|route| search_app_ops::reorient_reverse_route(route, edges, si))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SearchAlgorithmResult {
+            trees,
+            routes,
+            iterations,
+            suboptimality_bound,
+        })
+    }
+
+    /// handles a search interrupted by the termination model according to the termination
+    /// behavior. a partial result holds the search tree at the time of termination along
+    /// with the route from the search origin to the vertex estimated to be closest to the
+    /// destination. routes are only built for forward searches.
+    ///
+    /// only single a*-based searches produce partial results. queries composed of several
+    /// searches, such as waypoint, stop order and charging stop queries, fail instead, since
+    /// the tree of the interrupted search alone does not describe their progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`   - the user query
+    /// * `partial` - the state of the terminated search
+    ///
+    /// # Results
+    ///
+    /// The partial search result and the explanation of its termination, or an error if
+    /// the termination behavior is to fail
+    fn partial_result(
+        &self,
+        query: &serde_json::Value,
+        partial: PartialSearchResult,
+    ) -> Result<(SearchAlgorithmResult, String), CompassAppError> {
+        let PartialSearchResult {
+            explanation,
+            source,
+            closest_vertex,
+            result,
+        } = partial;
+        if let TerminationBehavior::Error = self.termination_behavior {
+            return Err(CompassAppError::SearchFailure(
+                SearchError::TerminationModelFailure {
+                    source: TerminationModelError::QueryTerminated(explanation),
+                },
+            ));
+        }
+        let routes = match (query.get_direction()?, closest_vertex) {
+            (Direction::Forward, Some(closest)) => {
+                vec![backtrack::vertex_oriented_route(
+                    source,
+                    closest,
+                    &result.tree,
+                )?]
+            }
+            _ => vec![],
+        };
+        let results = SearchAlgorithmResult {
+            trees: vec![result.tree],
+            routes,
+            iterations: result.iterations,
+            suboptimality_bound: None,
+        };
+        Ok((results, explanation))
+    }
+
+    /// replaces a partial search result with a termination error, for queries that do not
+    /// return partial results. when the termination behavior asks for partial results,
+    /// the termination error names the unsupported query type.
+    fn without_partial_result(&self, error: CompassAppError, query_type: &str) -> CompassAppError {
+        let error = match error {
+            CompassAppError::SearchFailure(e) => e.without_partial_result(),
+            other => return other,
+        };
+        match (self.termination_behavior, error) {
+            (
+                TerminationBehavior::PartialResult,
+                SearchError::TerminationModelFailure {
+                    source: TerminationModelError::QueryTerminated(explanation),
+                },
+            ) => CompassAppError::SearchFailure(SearchError::PartialResultUnsupported(
+                String::from(query_type),
+                explanation,
+            )),
+            (_, e) => CompassAppError::SearchFailure(e),
+        }
     }
 
     /// builds the assets that will run the search for this query instance.
@@ -152,17 +783,100 @@ impl SearchApp {
             .frontier_model_service
             .build(query, state_model.clone())?;
 
-        let search_assets = SearchInstance {
+        // queries whose traversal model matches the precomputed deltas skip the model
+        let edge_delta_cache = match &self.edge_delta_table {
+            Some(table) if traversal_model.is_query_invariant() => table.bind(&state_model),
+            _ => None,
+        };
+
+        let mut search_assets = SearchInstance {
             graph: self.graph.clone(),
             map_model: self.map_model.clone(),
             state_model,
             traversal_model,
+            traversal_config: self.traversal_config.clone(),
+            default_preprocessing_info: None,
+            edge_delta_cache,
             access_model,
             cost_model: Arc::new(cost_model),
             frontier_model,
             termination_model: self.termination_model.clone(),
         };
 
+        // the preprocessing info only varies by query with query-time edge costs or costs
+        if search_assets.traversal_model.is_query_invariant()
+            && !CostModelService::has_query_cost_parameters(query)
+        {
+            let info = match self.default_preprocessing_info.get() {
+                Some(info) => info.clone(),
+                None => {
+                    let info = Arc::new(search_assets.preprocessing_info()?);
+                    self.default_preprocessing_info.get_or_init(|| info).clone()
+                }
+            };
+            search_assets.default_preprocessing_info = Some(info);
+        }
+
         Ok(search_assets)
     }
 }
+
+/// true if the origin and destination of a leg query are the same stop, in which case
+/// searches return no route
+fn leg_has_same_stops(leg_query: &serde_json::Value) -> bool {
+    let origin = leg_query
+        .get(InputField::OriginVertex.to_str())
+        .or_else(|| leg_query.get(InputField::OriginEdge.to_str()));
+    let destination = leg_query
+        .get(InputField::DestinationVertex.to_str())
+        .or_else(|| leg_query.get(InputField::DestinationEdge.to_str()));
+    origin.is_some() && origin == destination
+}
+
+/// charges the vehicle at a station, updating the state in place.
+///
+/// # Returns
+///
+/// the charging stop and the cost of the time spent charging
+fn charge_at_station(
+    charging_model: &ChargingModel,
+    station: VertexId,
+    route_index: usize,
+    state: &mut [StateVariable],
+    si: &SearchInstance,
+) -> Result<(ChargingStop, Cost), CompassAppError> {
+    let arrival_state = state.to_vec();
+    let stop = charging_model
+        .charge(station, route_index, state, &si.state_model)
+        .map_err(|e| CompassAppError::CompassFailure(e.to_string()))?;
+    let cost = si
+        .cost_model
+        .cost_estimate(&arrival_state, state)
+        .map_err(SearchError::from)?;
+    Ok((stop, cost))
+}
+
+/// the origin and destination vertices of a charging stop query
+fn charging_endpoints(
+    query: &serde_json::Value,
+    si: &SearchInstance,
+) -> Result<(VertexId, VertexId), CompassAppError> {
+    let missing_destination = || {
+        CompassAppError::CompassFailure(String::from("charging stop queries require a destination"))
+    };
+    if query.get_origin_edge().is_ok() {
+        let o = query.get_origin_edge()?;
+        let d = query
+            .get_destination_edge()?
+            .ok_or_else(missing_destination)?;
+        Ok((si.graph.src_vertex_id(&o)?, si.graph.dst_vertex_id(&d)?))
+    } else if query.get_origin_vertex().is_ok() {
+        let o = query.get_origin_vertex()?;
+        let d = query
+            .get_destination_vertex()?
+            .ok_or_else(missing_destination)?;
+        Ok((o, d))
+    } else {
+        Err(CompassAppError::CompassFailure(String::from("SearchApp.run called with query that lacks origin_edge and origin_vertex, at least one required")))
+    }
+}
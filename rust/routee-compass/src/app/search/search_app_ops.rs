@@ -1,9 +1,17 @@
+use crate::app::compass::CompassAppError;
+use crate::plugin::input::{InputField, InputJsonExtensions};
 use itertools::Itertools;
+use routee_compass_core::algorithm::search::{
+    a_star::bidirectional_ops, EdgeTraversal, SearchError, SearchInstance,
+};
 use routee_compass_core::config::ConfigJsonExtensions;
 use routee_compass_core::model::{
     access::AccessModel,
-    state::{StateFeature, StateModelError},
+    map,
+    network::{EdgeId, VertexId},
+    state::{StateFeature, StateModelError, StateVariable},
     traversal::TraversalModel,
+    unit::Cost,
 };
 use std::{collections::HashMap, sync::Arc};
 
@@ -54,3 +62,365 @@ pub fn collect_features(
     added_features.extend(user_features);
     Ok(added_features)
 }
+
+/// re-orients a route found by a reverse search into forward order. the reverse route
+/// is ordered from the search root (the destination) outward, with states accumulated
+/// backward from the destination. each edge is re-traversed in the forward direction so
+/// that state accumulates from the origin.
+///
+/// for edge-oriented queries, the origin and destination edges are attached to the route
+/// as zero-cost traversals, matching the forward edge-oriented search.
+///
+/// # Arguments
+///
+/// * `rev_route` - route produced by backtracking a reverse search tree
+/// * `edges`     - origin and destination edges of an edge-oriented query, if any
+/// * `si`        - the search instance
+///
+/// # Returns
+///
+/// the route in forward order
+pub fn reorient_reverse_route(
+    rev_route: &[EdgeTraversal],
+    edges: Option<(EdgeId, EdgeId)>,
+    si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let prefix = match edges {
+        None => vec![],
+        Some((origin_edge, _)) => vec![EdgeTraversal {
+            edge_id: origin_edge,
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::ZERO,
+            result_state: si.state_model.initial_state()?,
+        }],
+    };
+    let mut route = prefix.clone();
+    route.extend(bidirectional_ops::reorient_reverse_route(
+        &prefix, rev_route, si,
+    )?);
+    if let Some((_, destination_edge)) = edges {
+        let final_state = match route.last() {
+            Some(last) => last.result_state.clone(),
+            None => si.state_model.initial_state()?,
+        };
+        route.push(EdgeTraversal {
+            edge_id: destination_edge,
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::ZERO,
+            result_state: final_state,
+        });
+    }
+    Ok(route)
+}
+
+/// map matches the locations of a matrix query to graph vertices. each location is an
+/// object holding the fields of a query origin or destination without the prefix, such as
+/// `{"x": -105.0, "y": 39.7}`, `{"vertex": 12}` or `{"edge": 40}`. a matched edge is
+/// replaced by the vertex where the search leaves or enters it: the end of an origin edge
+/// or the start of a destination edge.
+///
+/// # Arguments
+///
+/// * `locations`  - the locations listed on the matrix query
+/// * `origins`    - true if the locations are origins, false if they are destinations
+/// * `query_type` - the type of query listing the locations, used in error messages
+/// * `si`         - the search instance
+///
+/// # Returns
+///
+/// the vertex for each location, in the order provided
+pub fn match_matrix_locations(
+    locations: &[serde_json::Value],
+    origins: bool,
+    query_type: &str,
+    si: &SearchInstance,
+) -> Result<Vec<VertexId>, CompassAppError> {
+    let prefix = if origins { "origin" } else { "destination" };
+    locations
+        .iter()
+        .map(|location| {
+            let fields = location.as_object().ok_or_else(|| {
+                CompassAppError::CompassFailure(format!(
+                    "{} list entries must be JSON objects, found {}",
+                    prefix, location
+                ))
+            })?;
+            let mut location_query = serde_json::Value::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (format!("{}_{}", prefix, k), v.clone()))
+                    .collect(),
+            );
+            if origins {
+                si.map_model.match_origin(&mut location_query, si)?;
+                reject_candidates(&location_query, query_type)?;
+                match location_query.get_origin_vertex() {
+                    Ok(vertex_id) => Ok(vertex_id),
+                    Err(_) => {
+                        let edge_id = location_query.get_origin_edge()?;
+                        Ok(si.graph.dst_vertex_id(&edge_id)?)
+                    }
+                }
+            } else {
+                si.map_model.match_destination(&mut location_query, si)?;
+                reject_candidates(&location_query, query_type)?;
+                match location_query.get_destination_vertex()? {
+                    Some(vertex_id) => Ok(vertex_id),
+                    None => {
+                        let edge_id = location_query.get_destination_edge()?.ok_or_else(|| {
+                            CompassAppError::CompassFailure(format!(
+                                "unable to match destination {} to the map",
+                                location
+                            ))
+                        })?;
+                        Ok(si.graph.src_vertex_id(&edge_id)?)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// splits a multi-stop query into one query per leg. the stops of the route are the
+/// origin, each waypoint in order, and the destination. waypoints are objects holding
+/// the fields of a query destination without the prefix, such as `{"x": -105.0, "y": 39.7}`,
+/// `{"vertex": 12}` or `{"edge": 40}`, and are matched to the map like destinations.
+///
+/// each leg query is a copy of the user query where the origin and destination are
+/// replaced by the matched stops of that leg. when the query origin is an edge, every
+/// stop must match to an edge, otherwise every stop must match to a vertex.
+///
+/// # Arguments
+///
+/// * `query`     - the map-matched user query
+/// * `waypoints` - the waypoints listed on the query
+/// * `si`        - the search instance
+///
+/// # Returns
+///
+/// a query for each leg of the route, in order
+pub fn waypoint_leg_queries(
+    query: &serde_json::Value,
+    waypoints: &[serde_json::Value],
+    si: &SearchInstance,
+) -> Result<Vec<serde_json::Value>, CompassAppError> {
+    let edge_oriented = query.get_origin_edge().is_ok();
+    let (origin_field, destination_field) = if edge_oriented {
+        (InputField::OriginEdge, InputField::DestinationEdge)
+    } else {
+        (InputField::OriginVertex, InputField::DestinationVertex)
+    };
+    let origin = query.get(origin_field.to_str()).cloned().ok_or_else(|| {
+        CompassAppError::CompassFailure(String::from(
+            "waypoint query lacks origin_edge and origin_vertex, at least one required",
+        ))
+    })?;
+    let destination = query
+        .get(destination_field.to_str())
+        .cloned()
+        .ok_or_else(|| {
+            CompassAppError::CompassFailure(format!(
+                "waypoint query requires a destination matching {}",
+                destination_field
+            ))
+        })?;
+
+    let mut stops = vec![origin];
+    for waypoint in waypoints.iter() {
+        let fields = waypoint.as_object().ok_or_else(|| {
+            CompassAppError::CompassFailure(format!(
+                "waypoints must be JSON objects, found {}",
+                waypoint
+            ))
+        })?;
+        let mut waypoint_query = serde_json::Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| (format!("destination_{}", k), v.clone()))
+                .collect(),
+        );
+        si.map_model.match_destination(&mut waypoint_query, si)?;
+        let stop = waypoint_query
+            .get(destination_field.to_str())
+            .cloned()
+            .ok_or_else(|| {
+                CompassAppError::CompassFailure(format!(
+                    "unable to match waypoint {} to a {}",
+                    waypoint, destination_field
+                ))
+            })?;
+        stops.push(stop);
+    }
+    stops.push(destination);
+
+    let location_fields = [
+        InputField::OriginX,
+        InputField::OriginY,
+        InputField::DestinationX,
+        InputField::DestinationY,
+        InputField::OriginVertex,
+        InputField::DestinationVertex,
+        InputField::OriginEdge,
+        InputField::DestinationEdge,
+        InputField::Waypoints,
+    ];
+    stops
+        .iter()
+        .tuple_windows()
+        .map(|(leg_origin, leg_destination)| {
+            let mut leg_query = query.clone();
+            let leg_map = leg_query.as_object_mut().ok_or_else(|| {
+                CompassAppError::CompassFailure(String::from("query must be a JSON object"))
+            })?;
+            for field in location_fields.iter() {
+                leg_map.remove(field.to_str());
+            }
+            leg_map.insert(origin_field.to_string(), leg_origin.clone());
+            leg_map.insert(destination_field.to_string(), leg_destination.clone());
+            Ok(leg_query)
+        })
+        .collect()
+}
+
+/// creates a copy of a search instance whose searches start from the provided state,
+/// such as the final state of the previous leg of a multi-stop route.
+pub fn continue_search_instance(
+    si: &SearchInstance,
+    state: &[StateVariable],
+) -> Result<SearchInstance, SearchError> {
+    let state_model = si.state_model.with_initial_state(state)?;
+    Ok(SearchInstance {
+        graph: si.graph.clone(),
+        map_model: si.map_model.clone(),
+        state_model: Arc::new(state_model),
+        traversal_model: si.traversal_model.clone(),
+        traversal_config: si.traversal_config.clone(),
+        default_preprocessing_info: si.default_preprocessing_info.clone(),
+        edge_delta_cache: si.edge_delta_cache.clone(),
+        access_model: si.access_model.clone(),
+        cost_model: si.cost_model.clone(),
+        frontier_model: si.frontier_model.clone(),
+        termination_model: si.termination_model.clone(),
+    })
+}
+
+/// creates the multi-stop query that visits the stops of a stop order query in order.
+/// the first stop is the origin, the last stop is the destination, and the stops in
+/// between are waypoints.
+///
+/// # Arguments
+///
+/// * `query`    - the stop order query
+/// * `vertices` - the vertex matched to each stop
+/// * `order`    - the indices of the stops in the order visited
+///
+/// # Returns
+///
+/// a copy of the query with the origin, waypoints and destination set
+pub fn stop_order_query(
+    query: &serde_json::Value,
+    vertices: &[VertexId],
+    order: &[usize],
+) -> Result<serde_json::Value, CompassAppError> {
+    let ordered = order
+        .iter()
+        .map(|i| {
+            vertices.get(*i).cloned().ok_or_else(|| {
+                CompassAppError::InternalError(format!("stop order index {} out of range", i))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (origin, destination) = match (ordered.first(), ordered.last()) {
+        (Some(o), Some(d)) => (*o, *d),
+        _ => {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "stop order is empty",
+            )))
+        }
+    };
+    let waypoints = ordered
+        .iter()
+        .skip(1)
+        .take(ordered.len().saturating_sub(2))
+        .map(|v| serde_json::json!({ "vertex": v }))
+        .collect::<Vec<_>>();
+    let mut route_query = query.clone();
+    route_query.add_origin_vertex(origin)?;
+    route_query.add_destination_vertex(destination)?;
+    route_query[InputField::Waypoints.to_str()] = serde_json::json!(waypoints);
+    Ok(route_query)
+}
+
+/// fails if map matching added snapping candidates to the query. only forward route
+/// queries without waypoints search between candidates.
+///
+/// # Arguments
+///
+/// * `query`      - the map-matched user query
+/// * `query_type` - the type of query, used in the error message
+pub fn reject_candidates(
+    query: &serde_json::Value,
+    query_type: &str,
+) -> Result<(), CompassAppError> {
+    let origin_candidates = map::MapJsonExtensions::get_origin_candidates(query)?;
+    let destination_candidates = map::MapJsonExtensions::get_destination_candidates(query)?;
+    if origin_candidates.is_some() || destination_candidates.is_some() {
+        return Err(CompassAppError::CompassFailure(format!(
+            "{} queries do not support snapping candidates, remove [mapping.candidates] or query by vertex or edge id",
+            query_type
+        )));
+    }
+    Ok(())
+}
+
+/// collects the sources and targets of a map-matched query with snapping candidates,
+/// each with its access cost. a side of the query without candidates contributes its
+/// matched vertex with no access cost, where an origin edge is replaced by its end vertex
+/// and a destination edge by its start vertex.
+///
+/// # Arguments
+///
+/// * `query` - the map-matched user query
+/// * `si`    - the search instance
+///
+/// # Returns
+///
+/// the sources and targets of the query, or None if the query has no candidates
+#[allow(clippy::type_complexity)]
+pub fn candidate_endpoints(
+    query: &serde_json::Value,
+    si: &SearchInstance,
+) -> Result<Option<(Vec<(VertexId, Cost)>, Vec<(VertexId, Cost)>)>, CompassAppError> {
+    let origin_candidates = map::MapJsonExtensions::get_origin_candidates(query)?;
+    let destination_candidates = map::MapJsonExtensions::get_destination_candidates(query)?;
+    if origin_candidates.is_none() && destination_candidates.is_none() {
+        return Ok(None);
+    }
+    let sources = match origin_candidates {
+        Some(candidates) => candidates
+            .iter()
+            .map(|c| (c.vertex_id, c.access_cost))
+            .collect(),
+        None => match query.get_origin_vertex() {
+            Ok(vertex_id) => vec![(vertex_id, Cost::ZERO)],
+            Err(_) => {
+                let edge_id = query.get_origin_edge()?;
+                vec![(si.graph.dst_vertex_id(&edge_id)?, Cost::ZERO)]
+            }
+        },
+    };
+    let targets = match destination_candidates {
+        Some(candidates) => candidates
+            .iter()
+            .map(|c| (c.vertex_id, c.access_cost))
+            .collect(),
+        None => match query.get_destination_vertex()? {
+            Some(vertex_id) => vec![(vertex_id, Cost::ZERO)],
+            None => match query.get_destination_edge()? {
+                Some(edge_id) => vec![(si.graph.src_vertex_id(&edge_id)?, Cost::ZERO)],
+                None => return Ok(None),
+            },
+        },
+    };
+    Ok(Some((sources, targets)))
+}
@@ -1,8 +1,8 @@
 use allocative::Allocative;
 
 use routee_compass_core::{
-    algorithm::search::{EdgeTraversal, SearchTreeBranch},
-    model::network::vertex_id::VertexId,
+    algorithm::search::{many_to_many::OdMatrix, EdgeTraversal, SearchTreeBranch},
+    model::{charging::ChargingStop, network::vertex_id::VertexId},
 };
 
 use std::{collections::HashMap, time::Duration};
@@ -14,4 +14,29 @@ pub struct SearchAppResult {
     pub search_executed_time: String,
     pub search_runtime: Duration,
     pub iterations: u64,
+    /// for multi-stop queries, the span of the route covered by each leg. empty otherwise.
+    pub legs: Vec<RouteLeg>,
+    /// for stop order queries, the indices of the query stops in the order visited
+    pub stop_order: Option<Vec<usize>>,
+    /// for queries that insert charging stops, each stop in the order visited
+    pub charging_stops: Vec<ChargingStop>,
+    /// for anytime algorithms, the proven bound on the route cost relative to optimal
+    pub suboptimality_bound: Option<f64>,
+    /// for queries that returned a partial result, the explanation of why the search was terminated
+    pub termination: Option<String>,
+}
+
+/// a leg of a multi-stop route, between two consecutive stops. the leg covers the
+/// route edge traversals in the range `[start_index, end_index)`.
+#[derive(Allocative, Clone, Copy, Debug)]
+pub struct RouteLeg {
+    pub start_index: usize,
+    pub end_index: usize,
+}
+
+/// result of a matrix query, which runs a search between every origin and destination
+pub struct SearchAppMatrixResult {
+    pub matrix: OdMatrix,
+    pub search_executed_time: String,
+    pub search_runtime: Duration,
 }
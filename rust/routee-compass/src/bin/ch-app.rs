@@ -0,0 +1,45 @@
+use clap::Parser;
+use routee_compass::app::compass::compass_app::CompassApp;
+use routee_compass_core::algorithm::search::contraction_hierarchy::ContractionHierarchy;
+use std::error::Error;
+use std::path::Path;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct ChAppCliArgs {
+    /// Compass configuration file whose graph, traversal, cost and frontier models define edge costs
+    pub config_file: String,
+    /// output file for the vertex ranks
+    pub vertex_rank_file: String,
+    /// output file for the hierarchy arcs
+    pub arc_file: String,
+    /// output file for the cost and traversal model description used to build the hierarchy
+    pub cost_info_file: String,
+    /// max vertices settled by each witness search during contraction
+    #[arg(long, default_value_t = 500)]
+    pub witness_search_limit: usize,
+}
+
+/// preprocessing application that builds a contraction hierarchy for the models
+/// of a Compass configuration, using an empty query. the configuration should use
+/// a search algorithm that loads without preprocessed files (such as "a*"). the
+/// output files can then be referenced by a "contraction_hierarchy" algorithm
+/// configuration as `vertex_rank_input_file`, `arc_input_file` and `cost_info_input_file`.
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+    let args = ChAppCliArgs::parse();
+    let compass_app = CompassApp::try_from(Path::new(&args.config_file))?;
+    let si = compass_app
+        .search_app
+        .build_search_instance(&serde_json::json!({}))?;
+    let ch = ContractionHierarchy::build(&si, args.witness_search_limit)?;
+    ch.write_files(&args.vertex_rank_file, &args.arc_file, &args.cost_info_file)?;
+    log::info!(
+        "wrote contraction hierarchy with {} arcs to {}, {} and {}",
+        ch.n_arcs(),
+        args.vertex_rank_file,
+        args.arc_file,
+        args.cost_info_file
+    );
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use routee_compass::app::compass::compass_app::CompassApp;
+use routee_compass_core::algorithm::search::landmark::LandmarkHeuristic;
+use std::error::Error;
+use std::path::Path;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct LandmarkAppCliArgs {
+    /// Compass configuration file whose graph, traversal and cost models define edge costs
+    pub config_file: String,
+    /// output file for the landmark cost tables
+    pub landmark_cost_file: String,
+    /// output file for the cost and traversal model description used to build the tables
+    pub cost_info_file: String,
+    /// number of landmarks to select
+    #[arg(long, default_value_t = 16)]
+    pub n_landmarks: usize,
+}
+
+/// preprocessing application that selects landmarks and computes their cost tables
+/// for the models of a Compass configuration, using an empty query. the output files
+/// can then be referenced by an "a*" algorithm configuration under `landmarks` as
+/// `landmark_cost_input_file` and `cost_info_input_file`.
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+    let args = LandmarkAppCliArgs::parse();
+    let compass_app = CompassApp::try_from(Path::new(&args.config_file))?;
+    let si = compass_app
+        .search_app
+        .build_search_instance(&serde_json::json!({}))?;
+    let landmarks = LandmarkHeuristic::build(&si, args.n_landmarks)?;
+    landmarks.write_files(&args.landmark_cost_file, &args.cost_info_file)?;
+    log::info!(
+        "wrote {} landmarks to {} and {}",
+        landmarks.landmarks().len(),
+        args.landmark_cost_file,
+        args.cost_info_file
+    );
+    Ok(())
+}
@@ -13,6 +13,15 @@ pub enum InputField {
     DestinationEdge,
     GridSearch,
     QueryWeightEstimate,
+    Direction,
+    OriginList,
+    DestinationList,
+    IncludeRoutes,
+    Waypoints,
+    Stops,
+    TourStart,
+    TourEnd,
+    InsertChargingStops,
     Custom(String),
 }
 
@@ -30,6 +39,15 @@ impl InputField {
             I::DestinationEdge => "destination_edge",
             I::GridSearch => "grid_search",
             I::QueryWeightEstimate => "query_weight_estimate",
+            I::Direction => "direction",
+            I::OriginList => "origin_list",
+            I::DestinationList => "destination_list",
+            I::IncludeRoutes => "include_routes",
+            I::Waypoints => "waypoints",
+            I::Stops => "stops",
+            I::TourStart => "tour_start",
+            I::TourEnd => "tour_end",
+            I::InsertChargingStops => "insert_charging_stops",
             I::Custom(field) => field,
         }
     }
@@ -2,7 +2,10 @@ use crate::{app::compass::CompassAppError, plugin::PluginError};
 
 use super::{InputField, InputPluginError};
 use geo;
-use routee_compass_core::model::network::{edge_id::EdgeId, vertex_id::VertexId};
+use routee_compass_core::{
+    algorithm::search::Direction,
+    model::network::{edge_id::EdgeId, vertex_id::VertexId},
+};
 use serde_json::{self, json};
 
 pub trait InputJsonExtensions {
@@ -19,6 +22,15 @@ pub trait InputJsonExtensions {
     fn get_grid_search(&self) -> Option<&serde_json::Value>;
     fn add_query_weight_estimate(&mut self, weight: f64) -> Result<(), InputPluginError>;
     fn get_query_weight_estimate(&self) -> Result<Option<f64>, CompassAppError>;
+    fn get_direction(&self) -> Result<Direction, InputPluginError>;
+    fn get_origin_list(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError>;
+    fn get_destination_list(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError>;
+    fn get_include_routes(&self) -> Result<bool, InputPluginError>;
+    fn get_waypoints(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError>;
+    fn get_stops(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError>;
+    fn get_tour_start(&self) -> Result<Option<usize>, InputPluginError>;
+    fn get_tour_end(&self) -> Result<Option<usize>, InputPluginError>;
+    fn get_insert_charging_stops(&self) -> Result<bool, InputPluginError>;
 }
 
 impl InputJsonExtensions for serde_json::Value {
@@ -207,6 +219,96 @@ impl InputJsonExtensions for serde_json::Value {
             }),
         }
     }
+
+    fn get_direction(&self) -> Result<Direction, InputPluginError> {
+        match self.get(InputField::Direction.to_str()) {
+            None => Ok(Direction::default()),
+            Some(v) => serde_json::from_value(v.clone()).map_err(|_| {
+                InputPluginError::QueryFieldHasInvalidType(
+                    InputField::Direction,
+                    String::from("'forward' or 'reverse'"),
+                )
+            }),
+        }
+    }
+
+    fn get_origin_list(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError> {
+        get_location_list(self, InputField::OriginList)
+    }
+
+    fn get_destination_list(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError> {
+        get_location_list(self, InputField::DestinationList)
+    }
+
+    fn get_include_routes(&self) -> Result<bool, InputPluginError> {
+        match self.get(InputField::IncludeRoutes.to_str()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                InputPluginError::QueryFieldHasInvalidType(
+                    InputField::IncludeRoutes,
+                    String::from("bool"),
+                )
+            }),
+        }
+    }
+
+    fn get_waypoints(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError> {
+        get_location_list(self, InputField::Waypoints)
+    }
+
+    fn get_stops(&self) -> Result<Option<Vec<serde_json::Value>>, InputPluginError> {
+        get_location_list(self, InputField::Stops)
+    }
+
+    fn get_tour_start(&self) -> Result<Option<usize>, InputPluginError> {
+        get_stop_index(self, InputField::TourStart)
+    }
+
+    fn get_tour_end(&self) -> Result<Option<usize>, InputPluginError> {
+        get_stop_index(self, InputField::TourEnd)
+    }
+
+    fn get_insert_charging_stops(&self) -> Result<bool, InputPluginError> {
+        match self.get(InputField::InsertChargingStops.to_str()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                InputPluginError::QueryFieldHasInvalidType(
+                    InputField::InsertChargingStops,
+                    String::from("bool"),
+                )
+            }),
+        }
+    }
+}
+
+/// reads an optional index into the stops of a stop order query
+fn get_stop_index(
+    query: &serde_json::Value,
+    field: InputField,
+) -> Result<Option<usize>, InputPluginError> {
+    match query.get(field.to_str()) {
+        None => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .map(|i| Some(i as usize))
+            .ok_or_else(|| InputPluginError::QueryFieldHasInvalidType(field, String::from("u64"))),
+    }
+}
+
+/// reads an optional list of location objects, such as the origins of a matrix
+/// query or the waypoints of a multi-stop query
+fn get_location_list(
+    query: &serde_json::Value,
+    field: InputField,
+) -> Result<Option<Vec<serde_json::Value>>, InputPluginError> {
+    match query.get(field.to_str()) {
+        None => Ok(None),
+        Some(serde_json::Value::Array(locations)) => Ok(Some(locations.to_vec())),
+        Some(_) => Err(InputPluginError::QueryFieldHasInvalidType(
+            field,
+            String::from("array"),
+        )),
+    }
 }
 
 // pub type DecodeOp<T> = Box<dyn Fn(&serde_json::Value) -> Option<T>>;
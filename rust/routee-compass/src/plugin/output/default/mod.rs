@@ -1,3 +1,4 @@
 pub mod summary;
+pub mod transit;
 pub mod traversal;
 pub mod uuid;
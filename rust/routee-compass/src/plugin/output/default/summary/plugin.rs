@@ -28,6 +28,14 @@ impl OutputPlugin for SummaryOutputPlugin {
                 output["tree_size_count"] = json![tree_edges];
                 output["search_result_size_mib"] = json![memory_mib];
                 output["iterations"] = json![result.iterations];
+                if !result.legs.is_empty() {
+                    let leg_edges = result
+                        .legs
+                        .iter()
+                        .map(|leg| leg.end_index - leg.start_index)
+                        .collect::<Vec<_>>();
+                    output["route_leg_edges"] = json![leg_edges];
+                }
                 Ok(())
             }
         }
@@ -0,0 +1,23 @@
+use super::plugin::TransitOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::output::{OutputPlugin, OutputPluginBuilder},
+};
+use routee_compass_core::model::transit::TransitLayer;
+use std::sync::Arc;
+
+/// builds the transit output plugin. only available when the graph has a transit layer.
+pub struct TransitOutputPluginBuilder {
+    pub layer: Arc<TransitLayer>,
+}
+
+impl OutputPluginBuilder for TransitOutputPluginBuilder {
+    fn build(
+        &self,
+        _parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        Ok(Arc::new(TransitOutputPlugin {
+            layer: self.layer.clone(),
+        }))
+    }
+}
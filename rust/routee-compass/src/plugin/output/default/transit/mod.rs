@@ -0,0 +1,5 @@
+mod builder;
+mod plugin;
+
+pub use builder::TransitOutputPluginBuilder;
+pub use plugin::TransitOutputPlugin;
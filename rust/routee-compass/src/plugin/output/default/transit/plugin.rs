@@ -0,0 +1,60 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::transit::TransitLayer;
+use serde_json::json;
+use std::sync::Arc;
+
+/// labels each route with the modes used along it. consecutive edges of the same mode
+/// are grouped into a segment with the span of the route it covers, where the mode is
+/// `road`, `walk` for stop connectors, or the name of a transit route.
+pub struct TransitOutputPlugin {
+    pub layer: Arc<TransitLayer>,
+}
+
+impl TransitOutputPlugin {
+    fn route_modes(&self, route: &[EdgeTraversal]) -> serde_json::Value {
+        let mut segments: Vec<(&str, usize, usize)> = vec![];
+        for (index, edge) in route.iter().enumerate() {
+            let mode = self.layer.mode(&edge.edge_id);
+            match segments.last_mut() {
+                Some((last_mode, _, end_index)) if *last_mode == mode => *end_index = index + 1,
+                _ => segments.push((mode, index, index + 1)),
+            }
+        }
+        let segments_json = segments
+            .into_iter()
+            .map(|(mode, start_index, end_index)| {
+                json![{"mode": mode, "start_index": start_index, "end_index": end_index}]
+            })
+            .collect::<Vec<_>>();
+        json![segments_json]
+    }
+}
+
+impl OutputPlugin for TransitOutputPlugin {
+    /// append "route_modes" to the output JSON, with one entry per route
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        match search_result {
+            Err(_e) => Ok(()),
+            Ok((result, _)) => {
+                let modes = result
+                    .routes
+                    .iter()
+                    .map(|route| self.route_modes(route))
+                    .collect::<Vec<_>>();
+                output["route_modes"] = match modes.as_slice() {
+                    [] => serde_json::Value::Null,
+                    [route] => route.to_owned(),
+                    _ => json![modes],
+                };
+                Ok(())
+            }
+        }
+    }
+}
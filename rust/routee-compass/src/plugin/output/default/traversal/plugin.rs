@@ -1,7 +1,7 @@
 use super::json_extensions::TraversalJsonField;
 use super::traversal_output_format::TraversalOutputFormat;
 use crate::app::compass::CompassAppError;
-use crate::app::search::SearchAppResult;
+use crate::app::search::{RouteLeg, SearchAppResult};
 use crate::plugin::output::output_plugin::OutputPlugin;
 use crate::plugin::output::OutputPluginError;
 use routee_compass_core::algorithm::search::EdgeTraversal;
@@ -48,7 +48,7 @@ impl OutputPlugin for TraversalPlugin {
                             .iter()
                             .map(|route| {
                                 // construct_route_output(route, si, &route_args, &self.geoms)
-                                construct_route_output(route, si, &route_args)
+                                construct_route_output(route, &result.legs, si, &route_args)
                             })
                             .collect::<Result<Vec<_>, _>>()
                             .map_err(OutputPluginError::OutputPluginFailed)?;
@@ -91,9 +91,11 @@ impl OutputPlugin for TraversalPlugin {
     }
 }
 
-/// creates the JSON output for a route.
+/// creates the JSON output for a route. for multi-stop routes, the output lists each leg
+/// with its span of the path and the accumulated traversal summary at the end of the leg.
 fn construct_route_output(
     route: &Vec<EdgeTraversal>,
+    legs: &[RouteLeg],
     si: &SearchInstance,
     output_format: &TraversalOutputFormat,
 ) -> Result<serde_json::Value, String> {
@@ -113,12 +115,29 @@ fn construct_route_output(
         .cost_model
         .serialize_cost_info()
         .map_err(|e| e.to_string())?;
-    let result = serde_json::json![{
+    let mut result = serde_json::json![{
         "traversal_summary": traversal_summary,
         "state_model": state_model,
         "cost_model": cost_model,
         "cost": cost,
         "path": path_json
     }];
+    if !legs.is_empty() {
+        let legs_json = legs
+            .iter()
+            .map(|leg| {
+                let leg_state = match leg.end_index.checked_sub(1).and_then(|i| route.get(i)) {
+                    Some(et) => et.result_state.clone(),
+                    None => si.state_model.initial_state().map_err(|e| e.to_string())?,
+                };
+                Ok(json![{
+                    "start_index": leg.start_index,
+                    "end_index": leg.end_index,
+                    "traversal_summary": si.state_model.serialize_state(&leg_state)
+                }])
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        result["legs"] = json![legs_json];
+    }
     Ok(result)
 }
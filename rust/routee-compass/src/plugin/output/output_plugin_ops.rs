@@ -1,8 +1,12 @@
 use crate::app::{
     compass::CompassAppError,
-    search::{SearchApp, SearchAppResult},
+    search::{SearchApp, SearchAppMatrixResult, SearchAppResult},
+};
+use crate::plugin::input::InputJsonExtensions;
+use routee_compass_core::{
+    algorithm::search::SearchInstance, model::unit::AsF64,
+    util::duration_extension::DurationExtension,
 };
-use routee_compass_core::algorithm::search::SearchInstance;
 use serde_json::{json, Value};
 
 /// creates the initial output with summary information from the search app,
@@ -14,10 +18,23 @@ pub fn create_initial_output(
 ) -> Result<Value, Value> {
     match &res {
         Err(e) => Err(package_error(req, e)),
-        Ok((_, _)) => {
+        Ok((result, _)) => {
             let mut init_output = serde_json::json!({
                 "request": req,
             });
+            if let Some(stop_order) = &result.stop_order {
+                init_output["stop_order"] = json!(stop_order);
+            }
+            if !result.charging_stops.is_empty() {
+                init_output["charging_stops"] = json!(result.charging_stops);
+            }
+            if let Some(bound) = result.suboptimality_bound {
+                init_output["suboptimality_bound"] = json!(bound);
+            }
+            if let Some(explanation) = &result.termination {
+                init_output["terminated"] = json!(true);
+                init_output["termination_explanation"] = json!(explanation);
+            }
 
             let output_plugin_executed_time = chrono::Local::now();
             init_output["output_plugin_executed_time"] =
@@ -36,3 +53,73 @@ pub fn package_error<E: ToString>(req: &Value, error: E) -> Value {
         "error": error.to_string()
     })
 }
+
+/// creates the response for a matrix query. rows of the matrix follow the origin list
+/// and columns follow the destination list. pairs without a path are null. state values
+/// are listed in the order of `state_features`. routes are lists of edge ids and are only
+/// included when requested on the query. output plugins are not applied to matrix queries.
+pub fn create_matrix_output(
+    req: &Value,
+    res: &Result<(SearchAppMatrixResult, SearchInstance), CompassAppError>,
+) -> Value {
+    let (result, si) = match res {
+        Err(e) => return package_error(req, e),
+        Ok(r) => r,
+    };
+    let matrix = &result.matrix;
+    let cost = matrix
+        .cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.as_ref().map(|c| c.cost.as_f64()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let state = matrix
+        .cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.as_ref().map(|c| c.state.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let state_features = si
+        .state_model
+        .iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+
+    let mut matrix_json = json!({
+        "origins": matrix.origins,
+        "destinations": matrix.destinations,
+        "state_features": state_features,
+        "cost": cost,
+        "state": state,
+    });
+    if req.get_include_routes().unwrap_or_default() {
+        let routes = matrix
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| {
+                        cell.as_ref()
+                            .and_then(|c| c.route.as_ref())
+                            .map(|r| r.iter().map(|et| et.edge_id).collect::<Vec<_>>())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        matrix_json["route"] = json!(routes);
+    }
+
+    json!({
+        "request": req,
+        "matrix": matrix_json,
+        "search_executed_time": result.search_executed_time,
+        "search_runtime": result.search_runtime.hhmmss(),
+        "iterations": matrix.iterations,
+    })
+}